@@ -1,6 +1,8 @@
-use crate::error::Result;
+use crate::error::{PhonemeReverserError, Result};
 use std::path::Path;
+use std::time::Duration;
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct AudioData {
     pub samples: Vec<f32>,
     pub sample_rate: u32,
@@ -47,6 +49,258 @@ pub fn write_wav(path: &Path, data: &AudioData) -> Result<()> {
     Ok(())
 }
 
+/// Write 32-bit float samples, so that reading the file back gives exactly
+/// the same `AudioData`.
+pub fn write_wav_float(path: &Path, data: &AudioData) -> Result<()> {
+    let spec = hound::WavSpec {
+        channels: data.channels,
+        sample_rate: data.sample_rate,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+
+    let mut writer = hound::WavWriter::create(path, spec)?;
+    for &sample in &data.samples {
+        writer.write_sample(sample)?;
+    }
+
+    writer.finalize()?;
+    Ok(())
+}
+
+impl AudioData {
+    /// Silence of `duration` in this format.
+    pub fn silence(sample_rate: u32, channels: u16, duration: Duration) -> Self {
+        let frames = (duration.as_secs_f64() * f64::from(sample_rate)).round() as usize;
+        Self {
+            samples: vec![0.0; frames * usize::from(channels)],
+            sample_rate,
+            channels,
+        }
+    }
+}
+
+/// Play the samples backwards (plain waveform reversal).
+///
+/// Frames are reversed as a whole, so interleaved stereo keeps its left and
+/// right channels.
+pub fn reverse_samples(data: &AudioData) -> AudioData {
+    let channels = usize::from(data.channels.max(1));
+    let samples = data
+        .samples
+        .chunks(channels)
+        .rev()
+        .flatten()
+        .copied()
+        .collect();
+    AudioData {
+        samples,
+        sample_rate: data.sample_rate,
+        channels: data.channels,
+    }
+}
+
+/// Drop leading and trailing frames whose samples all stay below
+/// `threshold`. A clip that is silent throughout becomes empty.
+pub fn trim_silence(data: &AudioData, threshold: f32) -> AudioData {
+    let channels = usize::from(data.channels.max(1));
+    let loud = |frame: &[f32]| frame.iter().any(|s| s.abs() >= threshold);
+    let frames: Vec<&[f32]> = data.samples.chunks(channels).collect();
+    let start = frames.iter().position(|f| loud(f)).unwrap_or(frames.len());
+    let end = frames
+        .iter()
+        .rposition(|f| loud(f))
+        .map_or(start, |i| i + 1);
+    AudioData {
+        samples: data.samples[start * channels..end * channels].to_vec(),
+        sample_rate: data.sample_rate,
+        channels: data.channels,
+    }
+}
+
+/// Join clips of the same format, with `gap` of silence between them.
+pub fn concatenate(parts: &[AudioData], gap: Duration) -> Result<AudioData> {
+    let Some(first) = parts.first() else {
+        return Err(PhonemeReverserError::Synthesis(
+            "Nothing to concatenate".to_string(),
+        ));
+    };
+    if let Some(other) = parts
+        .iter()
+        .find(|p| (p.sample_rate, p.channels) != (first.sample_rate, first.channels))
+    {
+        return Err(PhonemeReverserError::Synthesis(format!(
+            "Cannot join {} Hz / {} ch audio with {} Hz / {} ch",
+            other.sample_rate, other.channels, first.sample_rate, first.channels
+        )));
+    }
+
+    let silence = AudioData::silence(first.sample_rate, first.channels, gap);
+    let mut samples = Vec::new();
+    for (i, part) in parts.iter().enumerate() {
+        if i > 0 {
+            samples.extend_from_slice(&silence.samples);
+        }
+        samples.extend_from_slice(&part.samples);
+    }
+    Ok(AudioData {
+        samples,
+        sample_rate: first.sample_rate,
+        channels: first.channels,
+    })
+}
+
+/// Interpolation used by [`resample`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResampleQuality {
+    /// Linear interpolation: cheapest, slightly dull and aliased.
+    Linear,
+    /// Windowed sinc with 8 zero crossings per side.
+    #[default]
+    Medium,
+    /// Windowed sinc with 32 zero crossings per side.
+    High,
+}
+
+impl ResampleQuality {
+    // sinc の片側の零点の数（Linear は補間のみ）
+    fn half_width(&self) -> Option<usize> {
+        match self {
+            ResampleQuality::Linear => None,
+            ResampleQuality::Medium => Some(8),
+            ResampleQuality::High => Some(32),
+        }
+    }
+}
+
+/// Convert `data` to `sample_rate`, channel by channel.
+///
+/// The sinc qualities low-pass at the lower of the two Nyquist frequencies,
+/// so downsampling does not alias.
+pub fn resample(data: &AudioData, sample_rate: u32, quality: ResampleQuality) -> AudioData {
+    let channels = usize::from(data.channels.max(1));
+    let frames_in = data.samples.len() / channels;
+    if data.sample_rate == sample_rate || frames_in == 0 || data.sample_rate == 0 {
+        return AudioData {
+            sample_rate,
+            ..data.clone()
+        };
+    }
+
+    let ratio = f64::from(sample_rate) / f64::from(data.sample_rate);
+    let frames_out = (frames_in as f64 * ratio).round() as usize;
+    let frame = |index: isize, channel: usize| -> f64 {
+        if index < 0 || index as usize >= frames_in {
+            0.0
+        } else {
+            f64::from(data.samples[index as usize * channels + channel])
+        }
+    };
+
+    let mut samples = Vec::with_capacity(frames_out * channels);
+    for out in 0..frames_out {
+        let center = out as f64 / ratio;
+        let base = center.floor() as isize;
+        for channel in 0..channels {
+            let value = match quality.half_width() {
+                None => {
+                    let next = if base + 1 < frames_in as isize { base + 1 } else { base };
+                    let frac = center - base as f64;
+                    frame(base, channel) * (1.0 - frac) + frame(next, channel) * frac
+                }
+                Some(zero_crossings) => {
+                    // 縮小時はカットオフを下げ、その分フィルタを広げる
+                    let cutoff = ratio.min(1.0);
+                    let half = (zero_crossings as f64 / cutoff).ceil() as isize;
+                    let (mut sum, mut weights) = (0.0, 0.0);
+                    for index in base - half + 1..=base + half {
+                        let x = center - index as f64;
+                        let weight = cutoff * sinc(cutoff * x) * blackman(x / half as f64);
+                        sum += frame(index, channel) * weight;
+                        weights += weight;
+                    }
+                    if weights.abs() > f64::EPSILON {
+                        sum / weights
+                    } else {
+                        0.0
+                    }
+                }
+            };
+            samples.push(value as f32);
+        }
+    }
+
+    AudioData {
+        samples,
+        sample_rate,
+        channels: data.channels,
+    }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-9 {
+        1.0
+    } else {
+        let x = std::f64::consts::PI * x;
+        x.sin() / x
+    }
+}
+
+// -1..=1 の外は 0
+fn blackman(x: f64) -> f64 {
+    if x.abs() >= 1.0 {
+        return 0.0;
+    }
+    let x = std::f64::consts::PI * x;
+    0.42 + 0.5 * x.cos() + 0.08 * (2.0 * x).cos()
+}
+
+/// Up- or down-mix to `channels`.
+///
+/// Mono is copied to every output channel and any layout mixed down to mono
+/// is averaged; otherwise channels are matched by position and missing ones
+/// are silent.
+pub fn remix(data: &AudioData, channels: u16) -> AudioData {
+    let from = usize::from(data.channels.max(1));
+    let to = usize::from(channels.max(1));
+    if from == to {
+        return data.clone();
+    }
+
+    let samples = data
+        .samples
+        .chunks(from)
+        .flat_map(|frame| {
+            let mean = frame.iter().sum::<f32>() / frame.len() as f32;
+            (0..to).map(move |channel| match (from, to) {
+                (_, 1) => mean,
+                (1, _) => frame[0],
+                _ => frame.get(channel).copied().unwrap_or(0.0),
+            })
+        })
+        .collect();
+    AudioData {
+        samples,
+        sample_rate: data.sample_rate,
+        channels,
+    }
+}
+
+/// Resample and remix to the given output format (mixing down before
+/// resampling and up after, to resample as few channels as possible).
+pub fn convert(
+    data: &AudioData,
+    sample_rate: u32,
+    channels: u16,
+    quality: ResampleQuality,
+) -> AudioData {
+    if channels < data.channels {
+        resample(&remix(data, channels), sample_rate, quality)
+    } else {
+        remix(&resample(data, sample_rate, quality), channels)
+    }
+}
+
 pub fn play_audio(_data: &AudioData) -> Result<()> {
     // TODO: Implement CPAL integration in Phase 2
     // For MVP, skip audio playback
@@ -69,7 +323,7 @@ mod tests {
         assert!(result.is_ok(), "Failed to read valid WAV file");
 
         let data = result.unwrap();
-        assert!(data.samples.len() > 0, "Sample data is empty");
+        assert!(!data.samples.is_empty(), "Sample data is empty");
         assert!(data.sample_rate > 0, "Sample rate is invalid");
         assert!(data.channels > 0, "Channel count is invalid");
     }
@@ -118,4 +372,165 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_write_wav_float_is_lossless() {
+        let original = AudioData {
+            samples: vec![0.0, 0.123_456_7, -0.5, 1.0],
+            sample_rate: 22050,
+            channels: 1,
+        };
+
+        let temp_dir = tempdir().unwrap();
+        let temp_path = temp_dir.path().join("float.wav");
+        write_wav_float(&temp_path, &original).unwrap();
+
+        assert_eq!(read_wav(&temp_path).unwrap(), original);
+    }
+
+    #[test]
+    fn test_reverse_samples_keeps_channels_together() {
+        let mono = AudioData {
+            samples: vec![0.1, 0.2, 0.3],
+            sample_rate: 16000,
+            channels: 1,
+        };
+        assert_eq!(reverse_samples(&mono).samples, vec![0.3, 0.2, 0.1]);
+
+        // L/R が交互に並ぶステレオはフレーム単位で反転する
+        let stereo = AudioData {
+            samples: vec![1.0, -1.0, 2.0, -2.0, 3.0, -3.0],
+            sample_rate: 16000,
+            channels: 2,
+        };
+        let reversed = reverse_samples(&stereo);
+        assert_eq!(reversed.samples, vec![3.0, -3.0, 2.0, -2.0, 1.0, -1.0]);
+        assert_eq!(reverse_samples(&reversed), stereo);
+    }
+
+    #[test]
+    fn test_trim_silence() {
+        let data = AudioData {
+            samples: vec![0.0, 0.001, 0.0, 0.3, 0.0, -0.2, 0.0, 0.0],
+            sample_rate: 16000,
+            channels: 2,
+        };
+        // フレーム (0.0, 0.3) から (0.0, -0.2) まで残る
+        assert_eq!(trim_silence(&data, 0.01).samples, vec![0.0, 0.3, 0.0, -0.2]);
+
+        let silent = AudioData {
+            samples: vec![0.0; 4],
+            ..data
+        };
+        assert!(trim_silence(&silent, 0.01).samples.is_empty());
+    }
+
+    fn sine(frequency: f32, sample_rate: u32, frames: usize) -> AudioData {
+        AudioData {
+            samples: (0..frames)
+                .map(|i| (2.0 * std::f32::consts::PI * frequency * i as f32 / sample_rate as f32).sin())
+                .collect(),
+            sample_rate,
+            channels: 1,
+        }
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_resample_keeps_duration_and_pitch() {
+        let input = sine(440.0, 22050, 22050);
+        for quality in [
+            ResampleQuality::Linear,
+            ResampleQuality::Medium,
+            ResampleQuality::High,
+        ] {
+            let output = resample(&input, 48000, quality);
+            assert_eq!(output.sample_rate, 48000);
+            assert_eq!(output.samples.len(), 48000);
+
+            // 端を除いて元の正弦波と一致する
+            let expected = sine(440.0, 48000, 48000);
+            let error: Vec<f32> = output.samples[1000..47000]
+                .iter()
+                .zip(&expected.samples[1000..47000])
+                .map(|(a, b)| a - b)
+                .collect();
+            assert!(rms(&error) < 0.01, "{:?}: {}", quality, rms(&error));
+        }
+    }
+
+    #[test]
+    fn test_downsampling_removes_frequencies_above_nyquist() {
+        // 10kHz は 16kHz のナイキスト周波数（8kHz）を超える
+        let input = sine(10_000.0, 44100, 44100);
+        let high = resample(&input, 16000, ResampleQuality::High);
+        assert_eq!(high.samples.len(), 16000);
+        assert!(rms(&high.samples[500..15500]) < 0.05);
+
+        let linear = resample(&input, 16000, ResampleQuality::Linear);
+        assert!(rms(&linear.samples[500..15500]) > rms(&high.samples[500..15500]));
+    }
+
+    #[test]
+    fn test_resample_interleaved_channels_separately() {
+        let stereo = AudioData {
+            samples: [0.5, -0.5].repeat(100),
+            sample_rate: 8000,
+            channels: 2,
+        };
+        let output = resample(&stereo, 16000, ResampleQuality::Medium);
+        assert_eq!(output.samples.len(), 400);
+        for frame in output.samples[40..360].chunks(2) {
+            assert!((frame[0] - 0.5).abs() < 1e-3 && (frame[1] + 0.5).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_remix() {
+        let mono = AudioData {
+            samples: vec![0.1, 0.2],
+            sample_rate: 8000,
+            channels: 1,
+        };
+        let stereo = remix(&mono, 2);
+        assert_eq!(stereo.samples, vec![0.1, 0.1, 0.2, 0.2]);
+        assert_eq!(remix(&stereo, 1), mono);
+
+        let surround = remix(&stereo, 4);
+        assert_eq!(surround.samples[..4], [0.1, 0.1, 0.0, 0.0]);
+        assert_eq!(remix(&surround, 2), stereo);
+    }
+
+    #[test]
+    fn test_convert_to_device_format() {
+        let input = sine(220.0, 22050, 2205);
+        let output = convert(&input, 48000, 2, ResampleQuality::Medium);
+        assert_eq!((output.sample_rate, output.channels), (48000, 2));
+        assert_eq!(output.samples.len(), 4800 * 2);
+    }
+
+    #[test]
+    fn test_concatenate_inserts_gaps() {
+        let clip = AudioData {
+            samples: vec![0.5, -0.5],
+            sample_rate: 1000,
+            channels: 2,
+        };
+        let joined = concatenate(&[clip.clone(), clip.clone()], Duration::from_millis(2)).unwrap();
+        assert_eq!(
+            joined.samples,
+            vec![0.5, -0.5, 0.0, 0.0, 0.0, 0.0, 0.5, -0.5]
+        );
+        assert_eq!(joined.channels, 2);
+
+        let other_rate = AudioData {
+            sample_rate: 2000,
+            ..clip.clone()
+        };
+        assert!(concatenate(&[clip, other_rate], Duration::ZERO).is_err());
+        assert!(concatenate(&[], Duration::ZERO).is_err());
+    }
 }
@@ -0,0 +1,227 @@
+use crate::audio::AudioData;
+use crate::error::{PhonemeReverserError, Result};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SizedSample};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub struct AudioRecorder {
+    // レコーダーは状態を持たない（録音ごとの状態は RecordingHandle が持つ）
+}
+
+impl AudioRecorder {
+    pub fn new() -> Result<Self> {
+        Ok(Self {})
+    }
+
+    /// Start recording from the default input device and return immediately.
+    ///
+    /// Input is downmixed to mono, which is what the recognizer expects.
+    pub fn start(&self) -> Result<RecordingHandle> {
+        // 1. デフォルトホストと入力デバイスを取得
+        let host = cpal::default_host();
+        let device = host.default_input_device().ok_or_else(|| {
+            PhonemeReverserError::AudioCapture("No input device available".to_string())
+        })?;
+
+        // 2. デバイスの既定設定をそのまま使う（入力はレートを指定できないことが多い）
+        let supported = device.default_input_config().map_err(|e| {
+            PhonemeReverserError::AudioCapture(format!("Failed to get default config: {}", e))
+        })?;
+        let sample_format = supported.sample_format();
+        let config: cpal::StreamConfig = supported.into();
+
+        // 3. コールバックと共有する録音状態
+        let state = Arc::new(CaptureState::new(config.channels));
+
+        // 4. 入力ストリームを構築して録音開始
+        let stream = match sample_format {
+            cpal::SampleFormat::F32 => build_stream::<f32>(&device, &config, state.clone()),
+            cpal::SampleFormat::I16 => build_stream::<i16>(&device, &config, state.clone()),
+            cpal::SampleFormat::U16 => build_stream::<u16>(&device, &config, state.clone()),
+            other => {
+                return Err(PhonemeReverserError::AudioCapture(format!(
+                    "Unsupported sample format: {}",
+                    other
+                )))
+            }
+        }
+        .map_err(|e| {
+            PhonemeReverserError::AudioCapture(format!("Failed to build stream: {}", e))
+        })?;
+
+        stream.play().map_err(|e| {
+            PhonemeReverserError::AudioCapture(format!("Failed to start stream: {}", e))
+        })?;
+
+        Ok(RecordingHandle {
+            _stream: stream,
+            state,
+            sample_rate: config.sample_rate.0,
+        })
+    }
+}
+
+impl Default for AudioRecorder {
+    fn default() -> Self {
+        Self::new().unwrap()
+    }
+}
+
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    state: Arc<CaptureState>,
+) -> std::result::Result<cpal::Stream, cpal::BuildStreamError>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    let error_state = state.clone();
+    device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            let block: Vec<f32> = data.iter().map(|s| s.to_sample::<f32>()).collect();
+            state.push(&block);
+        },
+        move |err| error_state.set_error(err.to_string()),
+        None,
+    )
+}
+
+/// State shared between a `RecordingHandle` and the stream callback.
+struct CaptureState {
+    channels: u16,
+    samples: Mutex<Vec<f32>>,
+    // 直近ブロックのRMS（f32のビット列）
+    level: AtomicU32,
+    error: Mutex<Option<String>>,
+}
+
+impl CaptureState {
+    fn new(channels: u16) -> Self {
+        Self {
+            channels: channels.max(1),
+            samples: Mutex::new(Vec::new()),
+            level: AtomicU32::new(0.0f32.to_bits()),
+            error: Mutex::new(None),
+        }
+    }
+
+    /// Stream callback body: downmix an interleaved block and append it.
+    fn push(&self, data: &[f32]) {
+        let channels = self.channels as usize;
+        let mono: Vec<f32> = data
+            .chunks(channels)
+            .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+            .collect();
+
+        let rms = if mono.is_empty() {
+            0.0
+        } else {
+            (mono.iter().map(|s| s * s).sum::<f32>() / mono.len() as f32).sqrt()
+        };
+        self.level.store(rms.to_bits(), Ordering::Release);
+
+        self.samples.lock().unwrap().extend_from_slice(&mono);
+    }
+
+    fn set_error(&self, message: String) {
+        *self.error.lock().unwrap() = Some(message);
+    }
+}
+
+/// Handle to a running recording.
+///
+/// Dropping the handle stops the stream and discards the recording.
+pub struct RecordingHandle {
+    _stream: cpal::Stream,
+    state: Arc<CaptureState>,
+    sample_rate: u32,
+}
+
+impl RecordingHandle {
+    /// RMS level of the most recent input block in `0.0..=1.0`.
+    pub fn level(&self) -> f32 {
+        f32::from_bits(self.state.level.load(Ordering::Acquire)).clamp(0.0, 1.0)
+    }
+
+    pub fn duration(&self) -> Duration {
+        let frames = self.state.samples.lock().unwrap().len();
+        Duration::from_secs_f64(frames as f64 / self.sample_rate as f64)
+    }
+
+    /// Stream error reported by the device, if any.
+    pub fn error(&self) -> Option<String> {
+        self.state.error.lock().unwrap().clone()
+    }
+
+    /// Stop recording and return the captured audio.
+    pub fn stop(self) -> Result<AudioData> {
+        if let Some(message) = self.error() {
+            return Err(PhonemeReverserError::AudioCapture(format!(
+                "Stream error: {}",
+                message
+            )));
+        }
+
+        // ストリームを先に止めてからバッファを取り出す
+        let RecordingHandle {
+            _stream: stream,
+            state,
+            sample_rate,
+        } = self;
+        drop(stream);
+
+        let samples = std::mem::take(&mut *state.samples.lock().unwrap());
+        Ok(AudioData {
+            samples,
+            sample_rate,
+            channels: 1,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_downmixes_to_mono() {
+        let state = CaptureState::new(2);
+        state.push(&[0.2, 0.4, -0.5, -0.1]);
+        assert_eq!(*state.samples.lock().unwrap(), vec![0.3, -0.3]);
+    }
+
+    #[test]
+    fn test_push_updates_level() {
+        let state = CaptureState::new(1);
+        state.push(&[0.5, -0.5, 0.5, -0.5]);
+        let level = f32::from_bits(state.level.load(Ordering::Acquire));
+        assert!((level - 0.5).abs() < 1e-6);
+
+        // 無音ブロックでレベルは0に戻る
+        state.push(&[0.0; 4]);
+        assert_eq!(f32::from_bits(state.level.load(Ordering::Acquire)), 0.0);
+    }
+
+    #[test]
+    fn test_recorded_audio_round_trips_through_wav() {
+        let state = CaptureState::new(1);
+        state.push(&[0.25, -0.25, 0.5]);
+        let audio = AudioData {
+            samples: state.samples.lock().unwrap().clone(),
+            sample_rate: 16000,
+            channels: 1,
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("recording.wav");
+        crate::audio::write_wav(&path, &audio).unwrap();
+
+        let read = crate::audio::read_wav(&path).unwrap();
+        assert_eq!(read.sample_rate, 16000);
+        assert_eq!(read.samples.len(), 3);
+    }
+}
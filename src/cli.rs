@@ -0,0 +1,1461 @@
+use crate::audio::{write_wav, AudioData, ResampleQuality};
+use crate::config::Config;
+use crate::converter::PhonemeConverter;
+use crate::error::{PhonemeReverserError, Result};
+use crate::kana;
+use crate::phoneme::{map_to_database, PhonemeMapping, PhonemeRecognizer};
+use crate::phonotactics::{repair_all, validate};
+use crate::playback::{
+    output_devices, AudioPlayer, AudioSink, DeviceInfo, NullSink, OutputDevice, WavSink,
+};
+use crate::project::{Project, Sequence};
+use crate::reversal::{flip_diphthong, reverse, DiphthongPolicy, ReversalMode, ReversalStrategy};
+use crate::segment::{DEFAULT_CROSSFADE, MAX_CROSSFADE};
+use crate::synth::{
+    CachedSynthesizer, EspeakCli, FormantSynthesizer, Language, SynthConfig, SynthParam,
+    Synthesizer, TestSynthesizer, VOICE_VARIANTS,
+};
+use crate::text::english_to_phonemes;
+use crate::tui::phoneme_db::{Phoneme, PhonemeCategory, PhonemeDatabase};
+use clap::builder::PossibleValuesParser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "phoneme-reverser",
+    version,
+    about = "Phoneme-based reverse speech generator"
+)]
+pub struct Cli {
+    #[command(flatten)]
+    pub backend: BackendArgs,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Backend selection shared by the TUI and the headless subcommands.
+#[derive(Debug, Args)]
+pub struct BackendArgs {
+    /// IPA to eSpeak conversion backend
+    #[arg(long, global = true, value_enum, default_value_t = ConverterArg::Native)]
+    pub converter: ConverterArg,
+
+    /// Speech synthesis backend
+    #[arg(long, global = true, value_enum, default_value_t = SynthArg::Espeak)]
+    pub synth: SynthArg,
+
+    /// Output sample rate of the formant backend
+    #[arg(
+        long,
+        global = true,
+        default_value_t = FormantSynthesizer::DEFAULT_SAMPLE_RATE,
+        value_parser = clap::value_parser!(u32)
+            .range(1..=i64::from(FormantSynthesizer::MAX_SAMPLE_RATE))
+    )]
+    pub sample_rate: u32,
+
+    /// Extra phoneme inventory file loaded after the bundled one (TOML, or
+    /// JSON with a `.json` extension); may be repeated
+    #[arg(long = "inventory", global = true, value_name = "FILE")]
+    pub inventories: Vec<PathBuf>,
+
+    /// Keep rendered audio in this directory and reuse it across runs (the
+    /// in-memory cache is always on)
+    #[arg(long, global = true, value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Settings file (defaults to $XDG_CONFIG_HOME/phoneme-reverser/config.toml)
+    #[arg(long, global = true, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Where played audio goes
+    #[arg(long, global = true, value_enum, default_value_t = SinkArg::Cpal)]
+    pub audio_sink: SinkArg,
+
+    /// WAV file written by `--audio-sink wav` (replaced by each playback)
+    #[arg(
+        long,
+        global = true,
+        value_name = "FILE",
+        required_if_eq("audio_sink", "wav")
+    )]
+    pub sink_output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ConverterArg {
+    /// Built-in mapping table
+    Native,
+    /// lexconvert executable (requires the `lexconvert` feature)
+    #[cfg(feature = "lexconvert")]
+    Lexconvert,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Synthesize the phoneme-reversed sequence and write it to a WAV file
+    Reverse(ReverseArgs),
+    /// Synthesize a sequence and play it on the configured output device
+    Play(PlayArgs),
+    /// Add a sequence and its settings to a project file (replacing one with
+    /// the same name)
+    Save(SaveArgs),
+    /// List the sequences of a project file, or render one of them reversed
+    Load(LoadArgs),
+    /// List audio hosts and output devices with their supported formats
+    Devices,
+    /// Start the interactive TUI (default when no subcommand is given)
+    Tui,
+}
+
+#[derive(Debug, Args)]
+pub struct SequenceArgs {
+    /// Space-separated IPA phonemes, e.g. "a k a"
+    #[arg(
+        long,
+        required_unless_present_any = ["wav", "text"],
+        conflicts_with_all = ["wav", "text"]
+    )]
+    pub ipa: Option<String>,
+
+    /// Text to convert: kana/romaji for --lang ja, English words (through
+    /// espeak-ng) for --lang en
+    #[arg(long, conflicts_with = "wav")]
+    pub text: Option<String>,
+
+    /// Recognize the phonemes of a WAV recording (requires allosaurus)
+    #[arg(long)]
+    pub wav: Option<PathBuf>,
+
+    /// Synthesis language
+    #[arg(long, value_enum, default_value_t = LanguageArg::Ja)]
+    pub lang: LanguageArg,
+
+    /// Speaking rate in words per minute (80-450)
+    #[arg(long, default_value_t = SynthConfig::default().speed,
+          value_parser = param_parser(SynthParam::Speed))]
+    pub speed: u32,
+
+    /// Pitch (0-99)
+    #[arg(long, default_value_t = SynthConfig::default().pitch,
+          value_parser = param_parser(SynthParam::Pitch))]
+    pub pitch: u32,
+
+    /// Volume (0-200)
+    #[arg(long, default_value_t = SynthConfig::default().amplitude,
+          value_parser = param_parser(SynthParam::Amplitude))]
+    pub amplitude: u32,
+
+    /// Extra pause between words in units of 10 ms (0-100)
+    #[arg(long, default_value_t = SynthConfig::default().word_gap,
+          value_parser = param_parser(SynthParam::WordGap))]
+    pub word_gap: u32,
+
+    /// espeak-ng voice variant, e.g. f3 or m1
+    #[arg(long, value_parser = PossibleValuesParser::new(VOICE_VARIANTS))]
+    pub variant: Option<String>,
+
+    /// Whether diphthongs are flipped internally when reversing
+    #[arg(long, value_enum, default_value_t = DiphthongArg::Atomic)]
+    pub diphthongs: DiphthongArg,
+
+    /// Unit that is reversed
+    #[arg(long, value_enum, default_value_t = StrategyArg::Segment)]
+    pub strategy: StrategyArg,
+
+    /// Fix phonotactic violations of the reversed sequence (e.g. insert
+    /// epenthetic vowels) instead of only warning about them
+    #[arg(long)]
+    pub repair: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ReverseArgs {
+    #[command(flatten)]
+    pub sequence: SequenceArgs,
+
+    /// Output WAV path
+    #[arg(short, long)]
+    pub output: PathBuf,
+
+    #[command(flatten)]
+    pub mode: ModeArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct PlayArgs {
+    #[command(flatten)]
+    pub sequence: SequenceArgs,
+
+    /// Play the sequence in its original order instead of reversed
+    #[arg(long, conflicts_with = "mode")]
+    pub original: bool,
+
+    #[command(flatten)]
+    pub mode: ModeArgs,
+
+    /// Resampler used when the output device does not support the clip's
+    /// sample rate
+    #[arg(long, value_enum, default_value_t = QualityArg::Medium)]
+    pub resample_quality: QualityArg,
+
+    /// Output device name as listed by `devices` (overrides the config file)
+    #[arg(long, value_name = "NAME")]
+    pub device: Option<String>,
+
+    /// Audio host of `--device` (defaults to the default host)
+    #[arg(long, value_name = "HOST", requires = "device")]
+    pub host: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct ModeArgs {
+    /// How the reversed audio is produced
+    #[arg(long, value_enum, default_value_t = ModeArg::Phoneme)]
+    pub mode: ModeArg,
+
+    /// Overlap in milliseconds between phonemes joined by `--mode concat`
+    #[arg(
+        long,
+        value_name = "MS",
+        default_value_t = DEFAULT_CROSSFADE.as_millis() as u64,
+        value_parser = clap::value_parser!(u64).range(0..=MAX_CROSSFADE.as_millis() as u64)
+    )]
+    pub crossfade: u64,
+}
+
+#[derive(Debug, Args)]
+pub struct SaveArgs {
+    #[command(flatten)]
+    pub sequence: SequenceArgs,
+
+    /// Project file to update (created if missing)
+    #[arg(long)]
+    pub project: PathBuf,
+
+    /// Name of the sequence in the project
+    #[arg(long)]
+    pub name: String,
+}
+
+#[derive(Debug, Args)]
+pub struct LoadArgs {
+    /// Project file to read
+    #[arg(long)]
+    pub project: PathBuf,
+
+    /// Sequence to render (defaults to the first one)
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// Render the reversed sequence to this WAV path instead of listing
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Fix phonotactic violations of the reversed sequence
+    #[arg(long)]
+    pub repair: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LanguageArg {
+    Ja,
+    En,
+}
+
+impl From<LanguageArg> for Language {
+    fn from(arg: LanguageArg) -> Self {
+        match arg {
+            LanguageArg::Ja => Language::Japanese,
+            LanguageArg::En => Language::English,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DiphthongArg {
+    /// Keep diphthongs as units (aɪ stays aɪ)
+    Atomic,
+    /// Reverse the glide too (aɪ becomes ɪa)
+    Flip,
+}
+
+impl From<DiphthongArg> for DiphthongPolicy {
+    fn from(arg: DiphthongArg) -> Self {
+        match arg {
+            DiphthongArg::Atomic => DiphthongPolicy::Atomic,
+            DiphthongArg::Flip => DiphthongPolicy::Flip,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StrategyArg {
+    /// Reverse phoneme by phoneme (ka → ak)
+    Segment,
+    /// Reverse mora order (ka-mi → mi-ka)
+    Mora,
+    /// Reverse syllable order, keeping onsets and codas
+    Syllable,
+    /// Reverse inside each syllable, keeping syllable order
+    CvSwap,
+}
+
+impl From<StrategyArg> for ReversalStrategy {
+    fn from(arg: StrategyArg) -> Self {
+        match arg {
+            StrategyArg::Segment => ReversalStrategy::Segment,
+            StrategyArg::Mora => ReversalStrategy::Mora,
+            StrategyArg::Syllable => ReversalStrategy::Syllable,
+            StrategyArg::CvSwap => ReversalStrategy::CvSwap,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ModeArg {
+    /// Synthesize the reversed phoneme sequence
+    Phoneme,
+    /// Synthesize the original and reverse the waveform
+    Waveform,
+    /// Synthesize each phoneme alone and join them in reverse order
+    Concat,
+    /// Original, waveform-reversed and phoneme-reversed in a row
+    Demo,
+}
+
+impl From<ModeArg> for ReversalMode {
+    fn from(arg: ModeArg) -> Self {
+        match arg {
+            ModeArg::Phoneme => ReversalMode::Phoneme,
+            ModeArg::Waveform => ReversalMode::Waveform,
+            ModeArg::Concat => ReversalMode::Concatenative,
+            ModeArg::Demo => ReversalMode::Demo,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum QualityArg {
+    /// Linear interpolation (fastest)
+    Linear,
+    /// Windowed sinc with 8 zero crossings
+    Medium,
+    /// Windowed sinc with 32 zero crossings
+    High,
+}
+
+impl From<QualityArg> for ResampleQuality {
+    fn from(arg: QualityArg) -> Self {
+        match arg {
+            QualityArg::Linear => ResampleQuality::Linear,
+            QualityArg::Medium => ResampleQuality::Medium,
+            QualityArg::High => ResampleQuality::High,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SinkArg {
+    /// Sound card through cpal
+    Cpal,
+    /// Discard audio, keeping real-time timing (no sound card needed)
+    Null,
+    /// Write what would have been played to `--sink-output`
+    Wav,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SynthArg {
+    /// espeak-ng executable
+    Espeak,
+    /// libespeak-ng linked in-process (requires the `espeak-ffi` feature)
+    #[cfg(feature = "espeak-ffi")]
+    EspeakLib,
+    /// Built-in formant synthesizer (no external binaries)
+    Formant,
+    /// Deterministic sine-tone backend for tests
+    Test,
+}
+
+impl BackendArgs {
+    pub fn database(&self) -> Result<PhonemeDatabase> {
+        PhonemeDatabase::load(&self.inventories)
+    }
+
+    pub fn converter(&self, db: &PhonemeDatabase) -> Result<PhonemeConverter> {
+        match self.converter {
+            ConverterArg::Native => Ok(PhonemeConverter::with_database(db)),
+            #[cfg(feature = "lexconvert")]
+            ConverterArg::Lexconvert => PhonemeConverter::lexconvert(),
+        }
+    }
+
+    /// Path of the settings file, if one can be determined.
+    pub fn config_path(&self) -> Option<PathBuf> {
+        self.config.clone().or_else(Config::default_path)
+    }
+
+    /// The saved settings (defaults when there is no file).
+    pub fn load_config(&self) -> Result<Config> {
+        match self.config_path() {
+            Some(path) => Config::load(&path),
+            None => Ok(Config::default()),
+        }
+    }
+
+    /// The selected audio sink; `player` is used for `--audio-sink cpal`.
+    pub fn audio_sink(&self, player: AudioPlayer) -> Box<dyn AudioSink> {
+        match (self.audio_sink, &self.sink_output) {
+            (SinkArg::Null, _) => Box::new(NullSink),
+            (SinkArg::Wav, Some(path)) => Box::new(WavSink::new(path)),
+            // clap の指定で wav には必ず出力先がある
+            (SinkArg::Cpal, _) | (SinkArg::Wav, None) => Box::new(player),
+        }
+    }
+
+    /// The selected backend behind a rendering cache.
+    pub fn synthesizer(&self) -> Result<Box<dyn Synthesizer>> {
+        let cached = CachedSynthesizer::new(self.backend()?, CachedSynthesizer::DEFAULT_CAPACITY);
+        Ok(Box::new(match &self.cache_dir {
+            Some(dir) => cached.with_disk_cache(dir)?,
+            None => cached,
+        }))
+    }
+
+    fn backend(&self) -> Result<Box<dyn Synthesizer>> {
+        Ok(match self.synth {
+            SynthArg::Espeak => Box::new(EspeakCli),
+            #[cfg(feature = "espeak-ffi")]
+            SynthArg::EspeakLib => Box::new(crate::synth::EspeakLib::new()?),
+            SynthArg::Formant => Box::new(FormantSynthesizer::new(self.sample_rate)),
+            SynthArg::Test => Box::new(TestSynthesizer),
+        })
+    }
+}
+
+impl ModeArgs {
+    fn render<F>(&self, original: &[String], reversed: &[String], render: F) -> Result<AudioData>
+    where
+        F: FnMut(&[String]) -> Result<AudioData>,
+    {
+        let crossfade = Duration::from_millis(self.crossfade);
+        let mode = ReversalMode::from(self.mode);
+        Ok(mode.render(original, reversed, crossfade, render)?.audio)
+    }
+}
+
+impl PlayArgs {
+    // --device の指定がなければ設定ファイルのデバイスを使う
+    fn output_device(&self, config: &Config) -> Option<OutputDevice> {
+        match &self.device {
+            Some(name) => Some(OutputDevice {
+                host: self
+                    .host
+                    .clone()
+                    .unwrap_or_else(|| cpal::default_host().id().name().to_string()),
+                name: name.clone(),
+            }),
+            None => config.output_device.clone(),
+        }
+    }
+}
+
+impl SequenceArgs {
+    fn phonemes(&self, db: &PhonemeDatabase) -> Result<Vec<String>> {
+        let lang_db = db.for_language(self.lang.into());
+        match (&self.ipa, &self.text, &self.wav) {
+            (Some(ipa), _, _) => Ok(ipa.split_whitespace().map(|s| s.to_string()).collect()),
+            (None, Some(text), _) => convert_text(text, self.lang.into(), &lang_db),
+            (None, None, Some(wav)) => recognize_wav(wav, &lang_db),
+            // clap の指定でいずれか1つは必ず与えられる
+            (None, None, None) => Ok(Vec::new()),
+        }
+    }
+
+    fn synth_config(&self) -> SynthConfig {
+        SynthConfig {
+            language: self.lang.into(),
+            speed: self.speed,
+            pitch: self.pitch,
+            amplitude: self.amplitude,
+            word_gap: self.word_gap,
+            variant: self.variant.clone(),
+        }
+    }
+
+    fn to_sequence(&self, name: &str, db: &PhonemeDatabase) -> Result<Sequence> {
+        Ok(Sequence {
+            phonemes: self.phonemes(db)?,
+            strategy: self.strategy.into(),
+            diphthongs: self.diphthongs.into(),
+            ..Sequence::new(name, &[], &self.synth_config())
+        })
+    }
+}
+
+// 範囲外の値は clap の引数エラー（終了コード2）にする
+fn param_parser(param: SynthParam) -> impl Fn(&str) -> std::result::Result<u32, String> + Clone {
+    move |value| {
+        let value = value.parse::<u32>().map_err(|e| e.to_string())?;
+        param.check(value).map_err(|e| e.to_string())
+    }
+}
+
+/// Run a headless subcommand. `Command::Tui` is handled by the binary.
+pub fn run(command: Command, backend: &BackendArgs) -> Result<()> {
+    let db = backend.database()?;
+    let converter = backend.converter(&db)?;
+    let synthesizer = backend.synthesizer()?;
+
+    match command {
+        Command::Reverse(args) => {
+            let original = args.sequence.phonemes(&db)?;
+            let phonemes = reversed(
+                original.clone(),
+                &db,
+                args.sequence.strategy.into(),
+                args.sequence.diphthongs.into(),
+            );
+            let phonemes = checked(
+                phonemes,
+                &db,
+                args.sequence.lang.into(),
+                args.sequence.repair,
+            );
+            let config = args.sequence.synth_config();
+            let audio = args.mode.render(&original, &phonemes, |ipa| {
+                render(&converter, synthesizer.as_ref(), ipa, &config)
+            })?;
+            write_wav(&args.output, &audio)?;
+            println!("{}", args.output.display());
+            Ok(())
+        }
+        Command::Play(args) => {
+            let original = args.sequence.phonemes(&db)?;
+            let config = args.sequence.synth_config();
+            let audio = if args.original {
+                render(&converter, synthesizer.as_ref(), &original, &config)?
+            } else {
+                let phonemes = reversed(
+                    original.clone(),
+                    &db,
+                    args.sequence.strategy.into(),
+                    args.sequence.diphthongs.into(),
+                );
+                let phonemes = checked(
+                    phonemes,
+                    &db,
+                    args.sequence.lang.into(),
+                    args.sequence.repair,
+                );
+                args.mode.render(&original, &phonemes, |ipa| {
+                    render(&converter, synthesizer.as_ref(), ipa, &config)
+                })?
+            };
+            let player = AudioPlayer::new()?
+                .with_quality(args.resample_quality.into())
+                .with_device(args.output_device(&backend.load_config()?));
+            backend.audio_sink(player).play(&audio)?.wait()
+        }
+        Command::Save(args) => {
+            // 既存のファイルには追記し、同名のシーケンスは置き換える
+            let mut project = if args.project.exists() {
+                Project::load(&args.project)?
+            } else {
+                Project::default()
+            };
+            project.upsert(args.sequence.to_sequence(&args.name, &db)?);
+            project.save(&args.project)?;
+            println!("{}", args.project.display());
+            Ok(())
+        }
+        Command::Load(args) => {
+            let project = Project::load(&args.project)?;
+            let Some(output) = &args.output else {
+                for sequence in &project.sequences {
+                    println!(
+                        "{}\t{}\t{}\t{}",
+                        sequence.name,
+                        sequence.language.to_espeak_code(),
+                        sequence.strategy.display_name(),
+                        sequence.phonemes.join(" ")
+                    );
+                }
+                return Ok(());
+            };
+
+            let sequence = match &args.name {
+                Some(name) => project.find(name),
+                None => project.sequences.first(),
+            }
+            .ok_or_else(|| {
+                PhonemeReverserError::Project(format!(
+                    "{}: no sequence named {}",
+                    args.project.display(),
+                    args.name.as_deref().unwrap_or("(first)")
+                ))
+            })?;
+            let phonemes = reversed(
+                sequence.phonemes.clone(),
+                &db,
+                sequence.strategy,
+                sequence.diphthongs,
+            );
+            let phonemes = checked(phonemes, &db, sequence.language, args.repair);
+            let audio = render(
+                &converter,
+                synthesizer.as_ref(),
+                &phonemes,
+                &sequence.synth_config(),
+            )?;
+            write_wav(output, &audio)?;
+            println!("{}", output.display());
+            Ok(())
+        }
+        Command::Devices => {
+            let config = backend.load_config()?;
+            print!(
+                "{}",
+                format_devices(&output_devices(), config.output_device.as_ref())
+            );
+            Ok(())
+        }
+        Command::Tui => Ok(()),
+    }
+}
+
+// ホストごとにデバイスと対応形式を並べる（* は既定、> は設定ファイルで選択中）
+fn format_devices(devices: &[DeviceInfo], selected: Option<&OutputDevice>) -> String {
+    let mut text = String::new();
+    let mut host = None;
+    for info in devices {
+        if host != Some(&info.device.host) {
+            host = Some(&info.device.host);
+            text.push_str(&format!("{}\n", info.device.host));
+        }
+        let mark = if selected == Some(&info.device) {
+            '>'
+        } else if info.is_default {
+            '*'
+        } else {
+            ' '
+        };
+        text.push_str(&format!("  {} {}\n", mark, info.device.name));
+        for format in &info.formats {
+            text.push_str(&format!("      {}\n", format));
+        }
+    }
+    if devices.is_empty() {
+        text.push_str("No output devices found\n");
+    }
+    text
+}
+
+// 認識結果を音素データベースに対応付け、対応のない記号は警告して読み飛ばす
+fn recognize_wav(path: &std::path::Path, db: &PhonemeDatabase) -> Result<Vec<String>> {
+    crate::audio::read_wav(path)?;
+    let symbols = PhonemeRecognizer::new()?.recognize(path)?;
+    Ok(warn_unknown(map_to_database(&symbols, db)))
+}
+
+// 日本語はかな・ローマ字、英語は espeak-ng で音素に変換する
+fn convert_text(text: &str, language: Language, db: &PhonemeDatabase) -> Result<Vec<String>> {
+    let mapping = match language {
+        Language::Japanese => kana::to_phonemes(text, db),
+        Language::English => english_to_phonemes(text, db)?,
+    };
+    Ok(warn_unknown(mapping))
+}
+
+fn warn_unknown(mapping: PhonemeMapping) -> Vec<String> {
+    for unknown in &mapping.unknown {
+        eprintln!(
+            "warning: skipping unknown phoneme '{}' at position {}",
+            unknown.symbol,
+            unknown.index + 1
+        );
+    }
+
+    mapping.phonemes.into_iter().map(|p| p.ipa).collect()
+}
+
+// 入力の各IPAトークンを1音素として逆順にする（二重母音は方針に従って反転）
+fn reversed(
+    phonemes: Vec<String>,
+    db: &PhonemeDatabase,
+    strategy: ReversalStrategy,
+    policy: DiphthongPolicy,
+) -> Vec<String> {
+    let phonemes: Vec<Phoneme> = phonemes
+        .into_iter()
+        .map(|ipa| token_phoneme(db, ipa))
+        .collect();
+    reverse(&phonemes, strategy, policy)
+        .into_iter()
+        .map(|p| p.ipa)
+        .collect()
+}
+
+// 逆順結果の音素配列を検査し、違反を警告する（repair 指定時は修正する）
+fn checked(
+    phonemes: Vec<String>,
+    db: &PhonemeDatabase,
+    language: Language,
+    repair: bool,
+) -> Vec<String> {
+    let db = db.for_language(language);
+    let phonemes: Vec<Phoneme> = phonemes
+        .into_iter()
+        .map(|ipa| token_phoneme(&db, ipa))
+        .collect();
+
+    if repair {
+        return repair_all(&phonemes, language, &db)
+            .into_iter()
+            .map(|p| p.ipa)
+            .collect();
+    }
+
+    for violation in validate(&phonemes, language, &db) {
+        eprintln!(
+            "warning: {} at position {} ('{}'), use --repair to fix",
+            violation.kind.display_name(),
+            violation.index + 1,
+            phonemes[violation.index].ipa
+        );
+    }
+    phonemes.into_iter().map(|p| p.ipa).collect()
+}
+
+// 音節分けのためにトークンを音素へ解決する。反転した二重母音（ɪa）は元の項目から
+// 作り直す。データベースにないトークンはそのまま変換器に渡し、音節分けでは子音として扱う
+fn token_phoneme(db: &PhonemeDatabase, ipa: String) -> Phoneme {
+    let known = db
+        .find_by_ipa(&ipa)
+        .cloned()
+        .or_else(|| {
+            let base = db.find_by_ipa(ipa.strip_suffix('ː')?)?;
+            db.lengthen(base)
+        })
+        .or_else(|| {
+            db.get_vowels()
+                .iter()
+                .filter(|p| p.category == PhonemeCategory::Diphthong)
+                .map(flip_diphthong)
+                .find(|flipped| flipped.ipa == ipa)
+        });
+
+    known.unwrap_or(Phoneme {
+        ipa,
+        espeak: String::new(),
+        description_ja: String::new(),
+        key: ' ',
+        category: PhonemeCategory::Consonant,
+        components: Vec::new(),
+    })
+}
+
+// 合成ワーカー（tui::worker）と同じ IPA → eSpeak → 音声合成の経路
+fn render(
+    converter: &PhonemeConverter,
+    synthesizer: &dyn Synthesizer,
+    ipa_phonemes: &[String],
+    config: &SynthConfig,
+) -> Result<AudioData> {
+    let espeak_phonemes = converter.convert_ipa_to_espeak(ipa_phonemes)?;
+    synthesizer.synthesize(&espeak_phonemes, config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reverse_command() {
+        let cli = Cli::try_parse_from([
+            "phoneme-reverser",
+            "reverse",
+            "--ipa",
+            "a k a",
+            "--lang",
+            "ja",
+            "-o",
+            "out.wav",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Some(Command::Reverse(args)) => {
+                assert_eq!(
+                    args.sequence.phonemes(&PhonemeDatabase::new()).unwrap(),
+                    vec!["a", "k", "a"]
+                );
+                assert_eq!(args.sequence.synth_config().language, Language::Japanese);
+                assert_eq!(args.output, PathBuf::from("out.wav"));
+            }
+            other => panic!("Unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_play_command_defaults() {
+        let cli = Cli::try_parse_from([
+            "phoneme-reverser",
+            "play",
+            "--ipa",
+            "h ə l oʊ",
+            "--lang",
+            "en",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Some(Command::Play(args)) => {
+                assert!(!args.original);
+                assert_eq!(args.sequence.synth_config().language, Language::English);
+            }
+            other => panic!("Unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_no_subcommand_means_tui() {
+        let cli = Cli::try_parse_from(["phoneme-reverser"]).unwrap();
+        assert!(cli.command.is_none());
+        assert_eq!(cli.backend.converter, ConverterArg::Native);
+        assert_eq!(cli.backend.synth, SynthArg::Espeak);
+    }
+
+    #[test]
+    fn test_formant_backend_uses_sample_rate() {
+        let cli = Cli::try_parse_from([
+            "phoneme-reverser",
+            "--synth",
+            "formant",
+            "--sample-rate",
+            "16000",
+            "tui",
+        ])
+        .unwrap();
+        let synthesizer = cli.backend.synthesizer().unwrap();
+        let audio = synthesizer
+            .synthesize("aka", &SynthConfig::default())
+            .unwrap();
+        assert_eq!(synthesizer.name(), "formant");
+        assert_eq!(audio.sample_rate, 16000);
+    }
+
+    #[test]
+    fn test_sample_rate_range() {
+        let parse = |rate: &str| Cli::try_parse_from(["phoneme-reverser", "--sample-rate", rate]);
+        assert!(parse("0").is_err());
+        assert!(parse("192001").is_err());
+        assert_eq!(parse("192000").unwrap().backend.sample_rate, 192_000);
+    }
+
+    #[test]
+    fn test_disk_cache_is_reused_across_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("cache");
+        let args = |output: &str| {
+            Cli::try_parse_from([
+                "phoneme-reverser",
+                "--synth",
+                "test",
+                "--cache-dir",
+                cache.to_str().unwrap(),
+                "reverse",
+                "--ipa",
+                "a k a",
+                "-o",
+                dir.path().join(output).to_str().unwrap(),
+            ])
+            .unwrap()
+        };
+
+        let cli = args("first.wav");
+        run(cli.command.unwrap(), &cli.backend).unwrap();
+        let wavs = std::fs::read_dir(&cache)
+            .unwrap()
+            .filter(|entry| {
+                entry
+                    .as_ref()
+                    .unwrap()
+                    .path()
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    == Some("wav")
+            })
+            .count();
+        assert_eq!(wavs, 1);
+
+        let cli = args("second.wav");
+        let synthesizer = cli.backend.synthesizer().unwrap();
+        synthesizer
+            .synthesize("aka", &SynthConfig::default())
+            .unwrap();
+        assert_eq!(synthesizer.cache_stats().unwrap().disk_hits, 1);
+    }
+
+    #[test]
+    fn test_reverse_with_test_synthesizer() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("out.wav");
+        let cli = Cli::try_parse_from([
+            "phoneme-reverser",
+            "--synth",
+            "test",
+            "reverse",
+            "--ipa",
+            "a k a",
+            "-o",
+            output.to_str().unwrap(),
+        ])
+        .unwrap();
+
+        run(cli.command.unwrap(), &cli.backend).unwrap();
+
+        let audio = crate::audio::read_wav(&output).unwrap();
+        assert!(!audio.samples.is_empty());
+    }
+
+    #[test]
+    fn test_reverse_modes() {
+        let dir = tempfile::tempdir().unwrap();
+        let render = |mode: &str| {
+            let output = dir.path().join(format!("{}.wav", mode));
+            let cli = Cli::try_parse_from([
+                "phoneme-reverser",
+                "--synth",
+                "test",
+                "reverse",
+                "--ipa",
+                "k a m i",
+                "--mode",
+                mode,
+                "-o",
+                output.to_str().unwrap(),
+            ])
+            .unwrap();
+            run(cli.command.unwrap(), &cli.backend).unwrap();
+            crate::audio::read_wav(&output).unwrap()
+        };
+
+        let phoneme = render("phoneme");
+        let waveform = render("waveform");
+        let demo = render("demo");
+        assert!(!render("concat").samples.is_empty());
+        // 元の音声と波形の反転は同じ長さで、デモは3つの音声と2つの間を含む
+        assert_eq!(waveform.samples.len(), phoneme.samples.len());
+        let gap = crate::audio::AudioData::silence(
+            demo.sample_rate,
+            demo.channels,
+            crate::reversal::DEMO_GAP,
+        );
+        assert_eq!(
+            demo.samples.len(),
+            3 * phoneme.samples.len() + 2 * gap.samples.len()
+        );
+
+        assert!(Cli::try_parse_from([
+            "phoneme-reverser",
+            "play",
+            "--ipa",
+            "a",
+            "--original",
+            "--mode",
+            "demo"
+        ])
+        .is_err());
+        assert!(Cli::try_parse_from([
+            "phoneme-reverser",
+            "play",
+            "--ipa",
+            "a",
+            "--mode",
+            "concat",
+            "--crossfade",
+            "500"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_save_and_load_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("project.json");
+        let output = dir.path().join("out.wav");
+        let project_arg = project.to_str().unwrap();
+
+        for (name, ipa) in [
+            ("kami", "k a m i"),
+            ("aka", "a k a"),
+            ("kami", "k a m i k a"),
+        ] {
+            let cli = Cli::try_parse_from([
+                "phoneme-reverser",
+                "save",
+                "--ipa",
+                ipa,
+                "--strategy",
+                "mora",
+                "--speed",
+                "120",
+                "--project",
+                project_arg,
+                "--name",
+                name,
+            ])
+            .unwrap();
+            run(cli.command.unwrap(), &cli.backend).unwrap();
+        }
+
+        let saved = Project::load(&project).unwrap();
+        assert_eq!(saved.sequences.len(), 2);
+        let kami = saved.find("kami").unwrap();
+        assert_eq!(kami.phonemes, vec!["k", "a", "m", "i", "k", "a"]);
+        assert_eq!(kami.strategy, ReversalStrategy::Mora);
+        assert_eq!(kami.speed, 120);
+
+        let cli = Cli::try_parse_from([
+            "phoneme-reverser",
+            "--synth",
+            "test",
+            "load",
+            "--project",
+            project_arg,
+            "--name",
+            "aka",
+            "-o",
+            output.to_str().unwrap(),
+        ])
+        .unwrap();
+        run(cli.command.unwrap(), &cli.backend).unwrap();
+        assert!(!crate::audio::read_wav(&output).unwrap().samples.is_empty());
+
+        let cli = Cli::try_parse_from([
+            "phoneme-reverser",
+            "--synth",
+            "test",
+            "load",
+            "--project",
+            project_arg,
+            "--name",
+            "missing",
+            "-o",
+            output.to_str().unwrap(),
+        ])
+        .unwrap();
+        let err = run(cli.command.unwrap(), &cli.backend).unwrap_err();
+        assert_eq!(err.exit_code(), 19);
+    }
+
+    #[test]
+    fn test_sequence_source_is_exclusive() {
+        let both =
+            Cli::try_parse_from(["phoneme-reverser", "play", "--ipa", "a", "--wav", "in.wav"]);
+        assert!(both.is_err());
+
+        let neither = Cli::try_parse_from(["phoneme-reverser", "play"]);
+        assert!(neither.is_err());
+
+        let cli = Cli::try_parse_from(["phoneme-reverser", "play", "--wav", "in.wav"]).unwrap();
+        match cli.command {
+            Some(Command::Play(args)) => {
+                assert_eq!(args.sequence.wav, Some(PathBuf::from("in.wav")))
+            }
+            other => panic!("Unexpected command: {:?}", other),
+        }
+
+        for conflict in [
+            ["--ipa", "a", "--text", "ka"],
+            ["--text", "ka", "--wav", "in.wav"],
+        ] {
+            let args = ["phoneme-reverser", "play"].into_iter().chain(conflict);
+            assert!(Cli::try_parse_from(args).is_err());
+        }
+    }
+
+    #[test]
+    fn test_resample_quality() {
+        let quality = |args: &[&str]| {
+            let cli = Cli::try_parse_from(
+                ["phoneme-reverser", "play", "--ipa", "a"]
+                    .iter()
+                    .chain(args),
+            )
+            .unwrap();
+            let Some(Command::Play(args)) = cli.command else {
+                panic!("Expected play command");
+            };
+            ResampleQuality::from(args.resample_quality)
+        };
+        assert_eq!(quality(&[]), ResampleQuality::default());
+        assert_eq!(
+            quality(&["--resample-quality", "high"]),
+            ResampleQuality::High
+        );
+        assert!(
+            Cli::try_parse_from(["phoneme-reverser", "play", "--resample-quality", "best"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_format_devices() {
+        let device = |host: &str, name: &str, is_default| DeviceInfo {
+            device: OutputDevice {
+                host: host.to_string(),
+                name: name.to_string(),
+            },
+            is_default,
+            formats: vec![crate::playback::SupportedFormat {
+                channels: 2,
+                min_sample_rate: 44100,
+                max_sample_rate: 48000,
+                sample_format: cpal::SampleFormat::F32,
+            }],
+        };
+        let devices = [
+            device("ALSA", "default", true),
+            device("ALSA", "hw:CARD=USB,DEV=0", false),
+            device("JACK", "system", false),
+        ];
+        let selected = devices[1].device.clone();
+        assert_eq!(
+            format_devices(&devices, Some(&selected)),
+            "ALSA\n  * default\n      2 ch, 44100-48000 Hz, f32\n  \
+             > hw:CARD=USB,DEV=0\n      2 ch, 44100-48000 Hz, f32\n\
+             JACK\n    system\n      2 ch, 44100-48000 Hz, f32\n"
+        );
+        assert_eq!(format_devices(&[], None), "No output devices found\n");
+    }
+
+    #[test]
+    fn test_play_device_overrides_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let saved = OutputDevice {
+            host: "ALSA".to_string(),
+            name: "hw:CARD=USB,DEV=0".to_string(),
+        };
+        Config {
+            output_device: Some(saved.clone()),
+        }
+        .save(&path)
+        .unwrap();
+
+        let parse = |extra: &[&str]| {
+            let base = [
+                "phoneme-reverser",
+                "--config",
+                path.to_str().unwrap(),
+                "play",
+            ];
+            let cli =
+                Cli::try_parse_from(base.iter().chain(["--ipa", "a"].iter()).chain(extra)).unwrap();
+            let config = cli.backend.load_config().unwrap();
+            let Some(Command::Play(args)) = cli.command else {
+                panic!("Expected play command");
+            };
+            args.output_device(&config)
+        };
+        assert_eq!(parse(&[]), Some(saved));
+        assert_eq!(
+            parse(&["--device", "pulse", "--host", "ALSA"]),
+            Some(OutputDevice {
+                host: "ALSA".to_string(),
+                name: "pulse".to_string(),
+            })
+        );
+        // --host だけでは指定できない
+        assert!(
+            Cli::try_parse_from(["phoneme-reverser", "play", "--ipa", "a", "--host", "ALSA"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_play_with_wav_sink() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("played.wav");
+        let cli = Cli::try_parse_from([
+            "phoneme-reverser",
+            "--synth",
+            "test",
+            "--audio-sink",
+            "wav",
+            "--sink-output",
+            output.to_str().unwrap(),
+            "play",
+            "--ipa",
+            "k a",
+            "--original",
+        ])
+        .unwrap();
+        run(cli.command.unwrap(), &cli.backend).unwrap();
+
+        let played = crate::audio::read_wav(&output).unwrap();
+        let expected = TestSynthesizer
+            .synthesize("k a", &SynthConfig::default())
+            .unwrap();
+        assert_eq!(played.samples.len(), expected.samples.len());
+
+        // wav には出力先が必要
+        assert!(Cli::try_parse_from(["phoneme-reverser", "--audio-sink", "wav"]).is_err());
+        let cli = Cli::try_parse_from(["phoneme-reverser", "--audio-sink", "null"]).unwrap();
+        assert_eq!(
+            cli.backend.audio_sink(AudioPlayer::new().unwrap()).name(),
+            "null"
+        );
+    }
+
+    #[test]
+    fn test_text_is_converted_by_language() {
+        let cli = Cli::try_parse_from(["phoneme-reverser", "play", "--text", "kitte"]).unwrap();
+        let Some(Command::Play(args)) = cli.command else {
+            panic!("Expected play command");
+        };
+        let db = PhonemeDatabase::new();
+        assert_eq!(
+            args.sequence.phonemes(&db).unwrap(),
+            vec!["k", "i", "t", "t", "e"]
+        );
+    }
+
+    #[test]
+    fn test_inventory_files_are_loaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let inventory = dir.path().join("extra.toml");
+        std::fs::write(
+            &inventory,
+            "[[phoneme]]\nipa = \"ɸ\"\nespeak = \"F\"\ndescription_ja = \"ふ\"\nkey = \"F\"\ncategory = \"consonant\"\n",
+        )
+        .unwrap();
+
+        let cli = Cli::try_parse_from([
+            "phoneme-reverser",
+            "--inventory",
+            inventory.to_str().unwrap(),
+            "tui",
+        ])
+        .unwrap();
+        let db = cli.backend.database().unwrap();
+        assert_eq!(db.get_by_key('F').unwrap().ipa, "ɸ");
+        assert_eq!(
+            cli.backend
+                .converter(&db)
+                .unwrap()
+                .convert_ipa_to_espeak(&["ɸ".to_string()])
+                .unwrap(),
+            "F"
+        );
+    }
+
+    #[test]
+    fn test_flipped_diphthongs_are_checked_as_diphthongs() {
+        let db = PhonemeDatabase::new();
+        let flipped = reversed(
+            vec!["m".to_string(), "aɪ".to_string()],
+            &db,
+            ReversalStrategy::Segment,
+            DiphthongPolicy::Flip,
+        );
+        assert_eq!(flipped, vec!["ɪa", "m"]);
+
+        // 反転した二重母音は元の項目に対応付けられ、未知の子音にはならない
+        let en = db.for_language(Language::English);
+        let phoneme = token_phoneme(&en, "ɪa".to_string());
+        assert_eq!(phoneme.category, PhonemeCategory::Diphthong);
+        assert_eq!(
+            phoneme.espeak,
+            flip_diphthong(en.find_by_ipa("aɪ").unwrap()).espeak
+        );
+        let phonemes: Vec<Phoneme> = flipped
+            .iter()
+            .map(|ipa| token_phoneme(&en, ipa.clone()))
+            .collect();
+        assert!(validate(&phonemes, Language::English, &en).is_empty());
+        assert_eq!(
+            checked(flipped.clone(), &db, Language::English, true),
+            flipped
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("out.wav");
+        let cli = Cli::try_parse_from([
+            "phoneme-reverser",
+            "--synth",
+            "test",
+            "reverse",
+            "--ipa",
+            "m aɪ",
+            "--lang",
+            "en",
+            "--diphthongs",
+            "flip",
+            "-o",
+            output.to_str().unwrap(),
+        ])
+        .unwrap();
+        run(cli.command.unwrap(), &cli.backend).unwrap();
+        assert!(output.exists());
+    }
+
+    #[test]
+    fn test_reversed_applies_diphthong_policy() {
+        let db = PhonemeDatabase::new();
+        let tokens = || vec!["m".to_string(), "aɪ".to_string(), "tʃ".to_string()];
+
+        assert_eq!(
+            reversed(
+                tokens(),
+                &db,
+                ReversalStrategy::Segment,
+                DiphthongPolicy::Atomic
+            ),
+            vec!["tʃ", "aɪ", "m"]
+        );
+        assert_eq!(
+            reversed(
+                tokens(),
+                &db,
+                ReversalStrategy::Segment,
+                DiphthongPolicy::Flip
+            ),
+            vec!["tʃ", "ɪa", "m"]
+        );
+
+        let cli = Cli::try_parse_from([
+            "phoneme-reverser",
+            "play",
+            "--ipa",
+            "aɪ",
+            "--diphthongs",
+            "flip",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Command::Play(args)) => assert_eq!(args.sequence.diphthongs, DiphthongArg::Flip),
+            other => panic!("Unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_reversed_applies_strategy() {
+        let db = PhonemeDatabase::new();
+        let tokens = || {
+            ["k", "aː", "ɴ", "t", "a"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            reversed(
+                tokens(),
+                &db,
+                ReversalStrategy::Mora,
+                DiphthongPolicy::Atomic
+            ),
+            vec!["t", "a", "ɴ", "k", "aː"]
+        );
+        assert_eq!(
+            reversed(
+                tokens(),
+                &db,
+                ReversalStrategy::Syllable,
+                DiphthongPolicy::Atomic
+            ),
+            vec!["t", "a", "k", "aː", "ɴ"]
+        );
+
+        // データベースにないトークンも落とさずに並べ替える
+        let unknown = vec!["ʔ".to_string(), "a".to_string()];
+        assert_eq!(
+            reversed(
+                unknown,
+                &db,
+                ReversalStrategy::CvSwap,
+                DiphthongPolicy::Atomic
+            ),
+            vec!["a", "ʔ"]
+        );
+
+        let cli = Cli::try_parse_from([
+            "phoneme-reverser",
+            "play",
+            "--ipa",
+            "k a",
+            "--strategy",
+            "cv-swap",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Command::Play(args)) => assert_eq!(args.sequence.strategy, StrategyArg::CvSwap),
+            other => panic!("Unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_checked_repairs_violations() {
+        let db = PhonemeDatabase::new();
+        let tokens = || vec!["k".to_string(), "t".to_string(), "a".to_string()];
+
+        assert_eq!(
+            checked(tokens(), &db, Language::Japanese, false),
+            vec!["k", "t", "a"]
+        );
+        assert_eq!(
+            checked(tokens(), &db, Language::Japanese, true),
+            vec!["k", "ɯ", "t", "a"]
+        );
+        // 英語の子音連続は3つまで許容される
+        assert_eq!(
+            checked(tokens(), &db, Language::English, true),
+            vec!["k", "t", "a"]
+        );
+
+        let cli =
+            Cli::try_parse_from(["phoneme-reverser", "play", "--ipa", "a k", "--repair"]).unwrap();
+        match cli.command {
+            Some(Command::Play(args)) => assert!(args.sequence.repair),
+            other => panic!("Unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_reverse_requires_output() {
+        let result = Cli::try_parse_from(["phoneme-reverser", "reverse", "--ipa", "a"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let errors = [
+            PhonemeReverserError::AudioFile(hound::Error::FormatError("")),
+            PhonemeReverserError::AudioPlayback(String::new()),
+            PhonemeReverserError::AudioCapture(String::new()),
+            PhonemeReverserError::Synthesis(String::new()),
+            PhonemeReverserError::PhonemeRecognition(String::new()),
+            PhonemeReverserError::PhonemeConversion(String::new()),
+            PhonemeReverserError::PythonInit(pyo3::exceptions::PyRuntimeError::new_err("")),
+            PhonemeReverserError::PhonemeInventory(String::new()),
+            PhonemeReverserError::Project(String::new()),
+            PhonemeReverserError::Config(String::new()),
+            PhonemeReverserError::Io(std::io::Error::other("io")),
+        ];
+        let mut codes: Vec<u8> = errors.iter().map(|e| e.exit_code()).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), errors.len());
+        assert!(
+            codes.iter().all(|&c| c > 2),
+            "0-2 are reserved for success and usage errors"
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use crate::error::{PhonemeReverserError, Result};
+use crate::playback::OutputDevice;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// User settings kept between runs, stored as TOML.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    /// Output device used for playback; the system default when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_device: Option<OutputDevice>,
+}
+
+impl Config {
+    /// `$XDG_CONFIG_HOME/phoneme-reverser/config.toml`, falling back to
+    /// `~/.config` when the variable is unset.
+    pub fn default_path() -> Option<PathBuf> {
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(base.join("phoneme-reverser").join("config.toml"))
+    }
+
+    /// Read the config file; a missing file yields the defaults.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = std::fs::read_to_string(path).map_err(|e| config_error(path, e))?;
+        toml::from_str(&text).map_err(|e| config_error(path, e))
+    }
+
+    /// Write the config file, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| config_error(path, e))?;
+        }
+        let text = toml::to_string_pretty(self).map_err(|e| config_error(path, e))?;
+        std::fs::write(path, text).map_err(|e| config_error(path, e))
+    }
+}
+
+fn config_error(path: &Path, e: impl std::fmt::Display) -> PhonemeReverserError {
+    PhonemeReverserError::Config(format!("{}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("config.toml");
+        let config = Config {
+            output_device: Some(OutputDevice {
+                host: "ALSA".to_string(),
+                name: "hw:CARD=USB,DEV=0".to_string(),
+            }),
+        };
+        config.save(&path).unwrap();
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.contains("[output_device]"));
+        assert_eq!(Config::load(&path).unwrap(), config);
+    }
+
+    #[test]
+    fn test_missing_file_is_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        assert_eq!(Config::load(&path).unwrap(), Config::default());
+
+        // 既定のデバイスに戻すと項目ごと書かれない
+        Config::default().save(&path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap().trim(), "");
+    }
+
+    #[test]
+    fn test_invalid_file_is_a_config_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "output_device = 3").unwrap();
+        let err = Config::load(&path).unwrap_err();
+        assert!(matches!(err, PhonemeReverserError::Config(_)));
+        assert_eq!(err.exit_code(), 20);
+    }
+}
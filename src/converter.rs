@@ -1,118 +1,282 @@
 use crate::error::{PhonemeReverserError, Result};
-use pyo3::prelude::*;
+use crate::tui::phoneme_db::PhonemeDatabase;
+use std::collections::HashMap;
 
-pub struct PhonemeConverter;
+// PhonemeDatabase に含まれない IPA 記号の eSpeak 表記
+// （多文字の二重母音・破擦音、認識器の出力に現れる異音、長音・強勢記号）
+const EXTRA_IPA_TO_ESPEAK: &[(&str, &str)] = &[
+    // 二重母音
+    ("aɪ", "aI"),
+    ("eɪ", "eI"),
+    ("oʊ", "oU"),
+    ("aʊ", "aU"),
+    ("ɔɪ", "OI"),
+    // 破擦音
+    ("tʃ", "tS"),
+    ("dʒ", "dZ"),
+    ("ts", "ts"),
+    ("dz", "dz"),
+    ("tɕ", "tS;"),
+    ("dʑ", "dZ;"),
+    // 母音
+    ("ɪ", "I"),
+    ("ʊ", "U"),
+    ("ɛ", "E"),
+    ("æ", "a"),
+    ("ʌ", "V"),
+    ("ɒ", "0"),
+    ("ɜ", "3"),
+    ("ɐ", "a#"),
+    ("ɯ", "u-"),
+    // 子音
+    ("θ", "T"),
+    ("ð", "D"),
+    ("ɹ", "r"),
+    ("ɾ", "*"),
+    ("ɡ", "g"),
+    ("ç", "C"),
+    ("x", "x"),
+    ("ɸ", "F"),
+    ("ʔ", "?"),
+    ("ɲ", "n^"),
+    ("ɕ", "S;"),
+    ("ʑ", "Z;"),
+    ("ɴ", "N"),
+    // 長音・強勢
+    ("ː", ":"),
+    ("ˈ", "'"),
+    ("ˌ", ","),
+];
+
+pub struct PhonemeConverter {
+    backend: Backend,
+}
+
+enum Backend {
+    Native(MappingTable),
+    // lexconvert は起動が遅いので変換結果を覚えておく（キーは空白区切りのIPA）
+    #[cfg(feature = "lexconvert")]
+    Lexconvert(std::path::PathBuf, std::sync::Mutex<lexconvert::Memo>),
+}
+
+/// Greedy longest-match table from IPA symbols to eSpeak mnemonics.
+struct MappingTable {
+    entries: HashMap<String, String>,
+    max_symbol_len: usize,
+}
+
+impl MappingTable {
+    fn from_database(db: &PhonemeDatabase) -> Self {
+        let mut entries: HashMap<String, String> = EXTRA_IPA_TO_ESPEAK
+            .iter()
+            .map(|&(ipa, espeak)| (ipa.to_string(), espeak.to_string()))
+            .collect();
+
+        // データベースの espeak フィールドを優先する
+        for phoneme in db.get_vowels().iter().chain(db.get_consonants()) {
+            entries.insert(phoneme.ipa.clone(), phoneme.espeak.clone());
+        }
+
+        let max_symbol_len = entries.keys().map(|k| k.chars().count()).max().unwrap_or(1);
+
+        Self {
+            entries,
+            max_symbol_len,
+        }
+    }
+
+    fn convert(&self, ipa: &str) -> Result<String> {
+        let chars: Vec<char> = ipa.chars().filter(|c| !c.is_whitespace()).collect();
+        let mut espeak = String::new();
+        let mut pos = 0;
+
+        while pos < chars.len() {
+            let longest = self.max_symbol_len.min(chars.len() - pos);
+            let matched = (1..=longest).rev().find_map(|len| {
+                let symbol: String = chars[pos..pos + len].iter().collect();
+                self.entries.get(&symbol).map(|code| (len, code))
+            });
+
+            match matched {
+                Some((len, code)) => {
+                    espeak.push_str(code);
+                    pos += len;
+                }
+                None => {
+                    return Err(PhonemeReverserError::PhonemeConversion(format!(
+                        "Unknown IPA symbol '{}' in '{}'",
+                        chars[pos], ipa
+                    )));
+                }
+            }
+        }
+
+        Ok(espeak)
+    }
+}
 
 impl PhonemeConverter {
+    /// Create a converter backed by the built-in mapping table.
     pub fn new() -> Result<Self> {
-        // PhonemeConverterはstateless（状態を持たない）なので、簡単に初期化
-        pyo3::prepare_freethreaded_python();
-
-        // lexconvertが利用可能か確認
-        Python::with_gil(|py| {
-            // Add venv site-packages to sys.path (same as phoneme.rs)
-            let sys = PyModule::import_bound(py, "sys")
-                .map_err(|e| PhonemeReverserError::PhonemeConversion(format!(
-                    "Failed to import sys: {}",
-                    e
-                )))?;
-
-            let cwd = std::env::current_dir()
-                .map_err(|e| PhonemeReverserError::Io(e))?;
-            let venv_site_packages = cwd.join(".venv/lib/python3.13/site-packages");
-
-            if venv_site_packages.exists() {
-                let path_list = sys.getattr("path")
-                    .map_err(|e| PhonemeReverserError::PhonemeConversion(format!(
-                        "Failed to get sys.path: {}",
-                        e
-                    )))?;
-                let venv_path_str = venv_site_packages.to_str().ok_or_else(|| {
-                    PhonemeReverserError::PhonemeConversion("Invalid UTF-8 in venv path".to_string())
-                })?;
-                path_list.call_method1("insert", (0, venv_path_str))
-                    .map_err(|e| PhonemeReverserError::PhonemeConversion(format!(
-                        "Failed to add venv to sys.path: {}",
-                        e
-                    )))?;
-            }
+        Ok(Self::with_database(&PhonemeDatabase::new()))
+    }
 
-            // Try to import lexconvert to verify it's available
-            PyModule::import_bound(py, "lexconvert")
-                .map_err(|e| PhonemeReverserError::PhonemeConversion(format!(
-                    "Failed to import lexconvert: {}",
-                    e
-                )))?;
+    pub fn with_database(db: &PhonemeDatabase) -> Self {
+        Self {
+            backend: Backend::Native(MappingTable::from_database(db)),
+        }
+    }
 
-            Ok(Self)
+    /// Create a converter that shells out to lexconvert.
+    ///
+    /// The executable is looked up in `$VIRTUAL_ENV/bin`, then `./.venv/bin`,
+    /// then `PATH`.
+    #[cfg(feature = "lexconvert")]
+    pub fn lexconvert() -> Result<Self> {
+        let path = lexconvert::find_executable();
+        lexconvert::check(&path)?;
+        Ok(Self {
+            backend: Backend::Lexconvert(path, Default::default()),
         })
     }
 
     pub fn convert_ipa_to_espeak(&self, ipa_phonemes: &[String]) -> Result<String> {
-        Python::with_gil(|py| {
-            // Join IPA phonemes with spaces
-            let ipa_str = ipa_phonemes.join(" ");
-
-            // Use subprocess to call: .venv/bin/lexconvert --phones2phones unicode-ipa espeak <phonemes>
-            let subprocess = PyModule::import_bound(py, "subprocess")
-                .map_err(|e| PhonemeReverserError::PhonemeConversion(format!(
-                    "Failed to import subprocess: {}",
-                    e
-                )))?;
-
-            // Get lexconvert path from venv
-            let cwd = std::env::current_dir()
-                .map_err(|e| PhonemeReverserError::Io(e))?;
-            let lexconvert_path = cwd.join(".venv/bin/lexconvert");
-
-            // Build command: .venv/bin/lexconvert --phones2phones unicode-ipa espeak <ipa_str>
-            let mut cmd = vec![
-                lexconvert_path.to_str().ok_or_else(|| {
-                    PhonemeReverserError::PhonemeConversion("Invalid UTF-8 in lexconvert path".to_string())
-                })?.to_string(),
-                "--phones2phones".to_string(),
-                "unicode-ipa".to_string(),
-                "espeak".to_string(),
-            ];
-            cmd.push(ipa_str.clone());
-
-            // Call subprocess.run()
-            let kwargs = pyo3::types::PyDict::new_bound(py);
-            kwargs.set_item("capture_output", true)?;
-            kwargs.set_item("text", true)?;
-
-            let result = subprocess
-                .call_method("run", (cmd,), Some(&kwargs))
-                .map_err(|e| PhonemeReverserError::PhonemeConversion(format!(
-                    "Failed to run lexconvert: {}",
-                    e
-                )))?;
-
-            // Check return code
-            let returncode: i32 = result.getattr("returncode")?.extract()?;
-            if returncode != 0 {
-                let stderr: String = result.getattr("stderr")?.extract()?;
-                return Err(PhonemeReverserError::PhonemeConversion(format!(
-                    "lexconvert failed with code {}: {}",
-                    returncode, stderr
-                )));
+        match &self.backend {
+            Backend::Native(table) => ipa_phonemes
+                .iter()
+                .map(|ipa| table.convert(ipa))
+                .collect::<Result<Vec<_>>>()
+                .map(|codes| codes.concat()),
+            #[cfg(feature = "lexconvert")]
+            Backend::Lexconvert(path, memo) => {
+                let lock = || memo.lock().unwrap_or_else(|e| e.into_inner());
+                let key = ipa_phonemes.join(" ");
+                if let Some(espeak) = lock().get(&key) {
+                    return Ok(espeak);
+                }
+                let espeak = lexconvert::convert(path, ipa_phonemes)?;
+                lock().insert(key, espeak.clone());
+                Ok(espeak)
             }
+        }
+    }
+}
 
-            // Extract stdout
-            let stdout: String = result
-                .getattr("stdout")
-                .map_err(|e| PhonemeReverserError::PhonemeConversion(format!(
-                    "Failed to get stdout: {}",
-                    e
-                )))?
-                .extract()
-                .map_err(|e| PhonemeReverserError::PhonemeConversion(format!(
-                    "Failed to extract stdout: {}",
+#[cfg(feature = "lexconvert")]
+mod lexconvert {
+    use crate::error::{PhonemeReverserError, Result};
+    use std::collections::{HashMap, VecDeque};
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    /// Number of conversions remembered, like
+    /// [`CachedSynthesizer::DEFAULT_CAPACITY`](crate::synth::CachedSynthesizer::DEFAULT_CAPACITY).
+    pub(super) const MEMO_CAPACITY: usize = 256;
+
+    /// Least-recently-used memo of lexconvert results.
+    #[derive(Default)]
+    pub(super) struct Memo {
+        entries: HashMap<String, String>,
+        // 先頭が最も古く使われたもの
+        order: VecDeque<String>,
+    }
+
+    impl Memo {
+        pub(super) fn get(&mut self, ipa: &str) -> Option<String> {
+            let espeak = self.entries.get(ipa)?.clone();
+            self.touch(ipa);
+            Some(espeak)
+        }
+
+        pub(super) fn insert(&mut self, ipa: String, espeak: String) {
+            if self.entries.insert(ipa.clone(), espeak).is_some() {
+                self.touch(&ipa);
+                return;
+            }
+            self.order.push_back(ipa);
+            while self.order.len() > MEMO_CAPACITY {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.entries.remove(&oldest);
+                }
+            }
+        }
+
+        fn touch(&mut self, ipa: &str) {
+            if let Some(position) = self.order.iter().position(|k| k == ipa) {
+                if let Some(key) = self.order.remove(position) {
+                    self.order.push_back(key);
+                }
+            }
+        }
+
+        #[cfg(test)]
+        pub(super) fn len(&self) -> usize {
+            self.entries.len()
+        }
+    }
+
+    pub(super) fn find_executable() -> PathBuf {
+        let candidates = std::env::var_os("VIRTUAL_ENV")
+            .map(PathBuf::from)
+            .into_iter()
+            .chain(std::env::current_dir().ok().map(|cwd| cwd.join(".venv")));
+
+        for venv in candidates {
+            let path = venv.join("bin").join("lexconvert");
+            if path.exists() {
+                return path;
+            }
+        }
+
+        PathBuf::from("lexconvert")
+    }
+
+    // 起動できても異常終了する lexconvert（壊れた環境や別のプログラム）は使わない
+    pub(super) fn check(path: &Path) -> Result<()> {
+        let output = Command::new(path)
+            .arg("--version")
+            .output()
+            .map_err(|e| {
+                PhonemeReverserError::PhonemeConversion(format!(
+                    "Failed to execute {}: {}. Is lexconvert installed?",
+                    path.display(),
                     e
-                )))?;
+                ))
+            })?;
 
-            Ok(stdout.trim().to_string())
-        })
+        if !output.status.success() {
+            return Err(PhonemeReverserError::PhonemeConversion(format!(
+                "{} --version failed with code {:?}: {}",
+                path.display(),
+                output.status.code(),
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn convert(path: &Path, ipa_phonemes: &[String]) -> Result<String> {
+        // lexconvert --phones2phones unicode-ipa espeak <phonemes>
+        let output = Command::new(path)
+            .arg("--phones2phones")
+            .arg("unicode-ipa")
+            .arg("espeak")
+            .arg(ipa_phonemes.join(" "))
+            .output()
+            .map_err(|e| {
+                PhonemeReverserError::PhonemeConversion(format!("Failed to run lexconvert: {}", e))
+            })?;
+
+        if !output.status.success() {
+            return Err(PhonemeReverserError::PhonemeConversion(format!(
+                "lexconvert failed with code {:?}: {}",
+                output.status.code(),
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
     }
 }
 
@@ -120,6 +284,10 @@ impl PhonemeConverter {
 mod tests {
     use super::*;
 
+    fn ipa(symbols: &[&str]) -> Vec<String> {
+        symbols.iter().map(|s| s.to_string()).collect()
+    }
+
     #[test]
     fn test_converter_initialization() {
         let result = PhonemeConverter::new();
@@ -131,7 +299,6 @@ mod tests {
         let converter = PhonemeConverter::new().unwrap();
 
         // Test basic IPA to espeak conversion
-        // Note: lexconvert may not support all IPA symbols
         let ipa = vec!["h".to_string(), "ə".to_string(), "l".to_string(), "oʊ".to_string()];
         let result = converter.convert_ipa_to_espeak(&ipa);
 
@@ -146,5 +313,73 @@ mod tests {
 
         // Basic check: result should not be empty
         assert!(!espeak_str.is_empty(), "Converted string is empty");
+        assert_eq!(espeak_str, "h@loU");
+    }
+
+    #[test]
+    fn test_convert_uses_database_espeak_codes() {
+        let converter = PhonemeConverter::new().unwrap();
+        let result = converter.convert_ipa_to_espeak(&ipa(&["ʃ", "ŋ", "ɑ", "j"])).unwrap();
+        assert_eq!(result, "SNAj");
+    }
+
+    #[test]
+    fn test_convert_multi_character_symbols() {
+        let converter = PhonemeConverter::new().unwrap();
+        assert_eq!(converter.convert_ipa_to_espeak(&ipa(&["tʃ", "a"])).unwrap(), "tSa");
+        assert_eq!(converter.convert_ipa_to_espeak(&ipa(&["dʒ", "oʊ"])).unwrap(), "dZoU");
+        // 区切られていない入力も最長一致で分割される
+        assert_eq!(converter.convert_ipa_to_espeak(&ipa(&["tʃaɪ"])).unwrap(), "tSaI");
+    }
+
+    #[test]
+    fn test_convert_length_marks() {
+        let converter = PhonemeConverter::new().unwrap();
+        assert_eq!(converter.convert_ipa_to_espeak(&ipa(&["aː", "k", "iː"])).unwrap(), "a:ki:");
+        assert_eq!(converter.convert_ipa_to_espeak(&ipa(&["ˈh", "ə"])).unwrap(), "'h@");
+    }
+
+    #[test]
+    fn test_convert_unknown_symbol_fails() {
+        let converter = PhonemeConverter::new().unwrap();
+        let result = converter.convert_ipa_to_espeak(&ipa(&["a", "ʘ"]));
+        assert!(matches!(result, Err(PhonemeReverserError::PhonemeConversion(_))));
+    }
+
+    #[test]
+    fn test_convert_empty_sequence() {
+        let converter = PhonemeConverter::new().unwrap();
+        assert_eq!(converter.convert_ipa_to_espeak(&[]).unwrap(), "");
+    }
+
+    #[cfg(feature = "lexconvert")]
+    #[test]
+    fn test_lexconvert_memo_is_bounded() {
+        let mut memo = lexconvert::Memo::default();
+        for i in 0..lexconvert::MEMO_CAPACITY {
+            memo.insert(i.to_string(), format!("e{}", i));
+        }
+        // 最初の項目を使うと、追い出されるのは次に古い項目
+        assert_eq!(memo.get("0").as_deref(), Some("e0"));
+        memo.insert("new".to_string(), "new".to_string());
+        assert_eq!(memo.len(), lexconvert::MEMO_CAPACITY);
+        assert_eq!(memo.get("0").as_deref(), Some("e0"));
+        assert_eq!(memo.get("1"), None);
+    }
+
+    #[cfg(all(feature = "lexconvert", unix))]
+    #[test]
+    fn test_lexconvert_check_rejects_failing_executable() {
+        // `false` は起動できるが常に異常終了する
+        let result = lexconvert::check(std::path::Path::new("false"));
+        assert!(matches!(result, Err(PhonemeReverserError::PhonemeConversion(_))));
+    }
+
+    #[cfg(feature = "lexconvert")]
+    #[test]
+    fn test_lexconvert_backend() {
+        let converter = PhonemeConverter::lexconvert().unwrap();
+        let result = converter.convert_ipa_to_espeak(&ipa(&["h", "ə", "l", "oʊ"]));
+        assert!(!result.unwrap().is_empty());
     }
 }
@@ -8,6 +8,9 @@ pub enum PhonemeReverserError {
     #[error("Audio playback error: {0}")]
     AudioPlayback(String),
 
+    #[error("Audio capture error: {0}")]
+    AudioCapture(String),
+
     #[error("Synthesis error: {0}")]
     Synthesis(String),
 
@@ -17,6 +20,15 @@ pub enum PhonemeReverserError {
     #[error("Phoneme conversion error: {0}")]
     PhonemeConversion(String),
 
+    #[error("Phoneme inventory error: {0}")]
+    PhonemeInventory(String),
+
+    #[error("Project file error: {0}")]
+    Project(String),
+
+    #[error("Config file error: {0}")]
+    Config(String),
+
     #[error("Python initialization error: {0}")]
     PythonInit(#[from] pyo3::PyErr),
 
@@ -25,3 +37,23 @@ pub enum PhonemeReverserError {
 }
 
 pub type Result<T> = std::result::Result<T, PhonemeReverserError>;
+
+impl PhonemeReverserError {
+    /// Process exit code used by the headless CLI, one per variant so that
+    /// CI jobs can tell failures apart.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            PhonemeReverserError::AudioFile(_) => 10,
+            PhonemeReverserError::AudioPlayback(_) => 11,
+            PhonemeReverserError::Synthesis(_) => 12,
+            PhonemeReverserError::PhonemeRecognition(_) => 13,
+            PhonemeReverserError::PhonemeConversion(_) => 14,
+            PhonemeReverserError::PythonInit(_) => 15,
+            PhonemeReverserError::Io(_) => 16,
+            PhonemeReverserError::AudioCapture(_) => 17,
+            PhonemeReverserError::PhonemeInventory(_) => 18,
+            PhonemeReverserError::Project(_) => 19,
+            PhonemeReverserError::Config(_) => 20,
+        }
+    }
+}
@@ -0,0 +1,488 @@
+use crate::phoneme::{PhonemeMapping, UnknownSymbol};
+use crate::tui::phoneme_db::{Phoneme, PhonemeCategory, PhonemeDatabase, IPA_LENGTH_MARK};
+
+const SOKUON: char = 'っ';
+const MORAIC_NASAL: char = 'ん';
+const CHOONPU: char = 'ー';
+
+// かな（1〜2文字）→ IPA。逆変換では先に現れた表記を優先する
+#[rustfmt::skip]
+const KANA: &[(&str, &[&str])] = &[
+    ("あ", &["a"]), ("い", &["i"]), ("う", &["ɯ"]), ("え", &["e"]), ("お", &["o"]),
+    ("か", &["k", "a"]), ("き", &["k", "i"]), ("く", &["k", "ɯ"]), ("け", &["k", "e"]), ("こ", &["k", "o"]),
+    ("が", &["g", "a"]), ("ぎ", &["g", "i"]), ("ぐ", &["g", "ɯ"]), ("げ", &["g", "e"]), ("ご", &["g", "o"]),
+    ("さ", &["s", "a"]), ("し", &["ɕ", "i"]), ("す", &["s", "ɯ"]), ("せ", &["s", "e"]), ("そ", &["s", "o"]),
+    ("ざ", &["z", "a"]), ("じ", &["dʒ", "i"]), ("ず", &["z", "ɯ"]), ("ぜ", &["z", "e"]), ("ぞ", &["z", "o"]),
+    ("た", &["t", "a"]), ("ち", &["tɕ", "i"]), ("つ", &["ts", "ɯ"]), ("て", &["t", "e"]), ("と", &["t", "o"]),
+    ("だ", &["d", "a"]), ("ぢ", &["dʒ", "i"]), ("づ", &["z", "ɯ"]), ("で", &["d", "e"]), ("ど", &["d", "o"]),
+    ("な", &["n", "a"]), ("に", &["n", "i"]), ("ぬ", &["n", "ɯ"]), ("ね", &["n", "e"]), ("の", &["n", "o"]),
+    ("は", &["h", "a"]), ("ひ", &["h", "i"]), ("ふ", &["ɸ", "ɯ"]), ("へ", &["h", "e"]), ("ほ", &["h", "o"]),
+    ("ば", &["b", "a"]), ("び", &["b", "i"]), ("ぶ", &["b", "ɯ"]), ("べ", &["b", "e"]), ("ぼ", &["b", "o"]),
+    ("ぱ", &["p", "a"]), ("ぴ", &["p", "i"]), ("ぷ", &["p", "ɯ"]), ("ぺ", &["p", "e"]), ("ぽ", &["p", "o"]),
+    ("ま", &["m", "a"]), ("み", &["m", "i"]), ("む", &["m", "ɯ"]), ("め", &["m", "e"]), ("も", &["m", "o"]),
+    ("や", &["j", "a"]), ("ゆ", &["j", "ɯ"]), ("よ", &["j", "o"]),
+    ("ら", &["r", "a"]), ("り", &["r", "i"]), ("る", &["r", "ɯ"]), ("れ", &["r", "e"]), ("ろ", &["r", "o"]),
+    ("わ", &["w", "a"]), ("を", &["o"]), ("ん", &["ɴ"]),
+    // 拗音
+    ("きゃ", &["k", "j", "a"]), ("きゅ", &["k", "j", "ɯ"]), ("きょ", &["k", "j", "o"]),
+    ("ぎゃ", &["g", "j", "a"]), ("ぎゅ", &["g", "j", "ɯ"]), ("ぎょ", &["g", "j", "o"]),
+    ("しゃ", &["ɕ", "a"]), ("しゅ", &["ɕ", "ɯ"]), ("しぇ", &["ɕ", "e"]), ("しょ", &["ɕ", "o"]),
+    ("じゃ", &["dʒ", "a"]), ("じゅ", &["dʒ", "ɯ"]), ("じぇ", &["dʒ", "e"]), ("じょ", &["dʒ", "o"]),
+    ("ちゃ", &["tɕ", "a"]), ("ちゅ", &["tɕ", "ɯ"]), ("ちぇ", &["tɕ", "e"]), ("ちょ", &["tɕ", "o"]),
+    ("にゃ", &["n", "j", "a"]), ("にゅ", &["n", "j", "ɯ"]), ("にょ", &["n", "j", "o"]),
+    ("ひゃ", &["h", "j", "a"]), ("ひゅ", &["h", "j", "ɯ"]), ("ひょ", &["h", "j", "o"]),
+    ("びゃ", &["b", "j", "a"]), ("びゅ", &["b", "j", "ɯ"]), ("びょ", &["b", "j", "o"]),
+    ("ぴゃ", &["p", "j", "a"]), ("ぴゅ", &["p", "j", "ɯ"]), ("ぴょ", &["p", "j", "o"]),
+    ("みゃ", &["m", "j", "a"]), ("みゅ", &["m", "j", "ɯ"]), ("みょ", &["m", "j", "o"]),
+    ("りゃ", &["r", "j", "a"]), ("りゅ", &["r", "j", "ɯ"]), ("りょ", &["r", "j", "o"]),
+    // 外来音
+    ("ふぁ", &["ɸ", "a"]), ("ふぃ", &["ɸ", "i"]), ("ふぇ", &["ɸ", "e"]), ("ふぉ", &["ɸ", "o"]),
+    ("てぃ", &["t", "i"]), ("でぃ", &["d", "i"]), ("とぅ", &["t", "ɯ"]), ("どぅ", &["d", "ɯ"]),
+    ("つぁ", &["ts", "a"]), ("つぃ", &["ts", "i"]), ("つぇ", &["ts", "e"]), ("つぉ", &["ts", "o"]),
+    ("うぃ", &["w", "i"]), ("うぇ", &["w", "e"]), ("うぉ", &["w", "o"]),
+    // 単独の小書き文字
+    ("ぁ", &["a"]), ("ぃ", &["i"]), ("ぅ", &["ɯ"]), ("ぇ", &["e"]), ("ぉ", &["o"]),
+    ("ゃ", &["j", "a"]), ("ゅ", &["j", "ɯ"]), ("ょ", &["j", "o"]), ("ゎ", &["w", "a"]),
+];
+
+// ヘボン式・訓令式ローマ字 → ひらがな（長い綴りから照合する）
+#[rustfmt::skip]
+const ROMAJI: &[(&str, &str)] = &[
+    ("a", "あ"), ("i", "い"), ("u", "う"), ("e", "え"), ("o", "お"),
+    ("ka", "か"), ("ki", "き"), ("ku", "く"), ("ke", "け"), ("ko", "こ"),
+    ("ga", "が"), ("gi", "ぎ"), ("gu", "ぐ"), ("ge", "げ"), ("go", "ご"),
+    ("sa", "さ"), ("shi", "し"), ("si", "し"), ("su", "す"), ("se", "せ"), ("so", "そ"),
+    ("za", "ざ"), ("ji", "じ"), ("zi", "じ"), ("zu", "ず"), ("ze", "ぜ"), ("zo", "ぞ"),
+    ("ta", "た"), ("chi", "ち"), ("ti", "ち"), ("tsu", "つ"), ("tu", "つ"), ("te", "て"), ("to", "と"),
+    ("da", "だ"), ("di", "ぢ"), ("du", "づ"), ("de", "で"), ("do", "ど"),
+    ("na", "な"), ("ni", "に"), ("nu", "ぬ"), ("ne", "ね"), ("no", "の"),
+    ("ha", "は"), ("hi", "ひ"), ("fu", "ふ"), ("hu", "ふ"), ("he", "へ"), ("ho", "ほ"),
+    ("ba", "ば"), ("bi", "び"), ("bu", "ぶ"), ("be", "べ"), ("bo", "ぼ"),
+    ("pa", "ぱ"), ("pi", "ぴ"), ("pu", "ぷ"), ("pe", "ぺ"), ("po", "ぽ"),
+    ("ma", "ま"), ("mi", "み"), ("mu", "む"), ("me", "め"), ("mo", "も"),
+    ("ya", "や"), ("yu", "ゆ"), ("yo", "よ"),
+    ("ra", "ら"), ("ri", "り"), ("ru", "る"), ("re", "れ"), ("ro", "ろ"),
+    ("wa", "わ"), ("wo", "を"), ("wi", "うぃ"), ("we", "うぇ"),
+    ("kya", "きゃ"), ("kyu", "きゅ"), ("kyo", "きょ"),
+    ("gya", "ぎゃ"), ("gyu", "ぎゅ"), ("gyo", "ぎょ"),
+    ("sha", "しゃ"), ("shu", "しゅ"), ("she", "しぇ"), ("sho", "しょ"),
+    ("sya", "しゃ"), ("syu", "しゅ"), ("syo", "しょ"),
+    ("ja", "じゃ"), ("ju", "じゅ"), ("je", "じぇ"), ("jo", "じょ"),
+    ("zya", "じゃ"), ("zyu", "じゅ"), ("zyo", "じょ"),
+    ("cha", "ちゃ"), ("chu", "ちゅ"), ("che", "ちぇ"), ("cho", "ちょ"),
+    ("tya", "ちゃ"), ("tyu", "ちゅ"), ("tyo", "ちょ"),
+    ("nya", "にゃ"), ("nyu", "にゅ"), ("nyo", "にょ"),
+    ("hya", "ひゃ"), ("hyu", "ひゅ"), ("hyo", "ひょ"),
+    ("bya", "びゃ"), ("byu", "びゅ"), ("byo", "びょ"),
+    ("pya", "ぴゃ"), ("pyu", "ぴゅ"), ("pyo", "ぴょ"),
+    ("mya", "みゃ"), ("myu", "みゅ"), ("myo", "みょ"),
+    ("rya", "りゃ"), ("ryu", "りゅ"), ("ryo", "りょ"),
+    ("fa", "ふぁ"), ("fi", "ふぃ"), ("fe", "ふぇ"), ("fo", "ふぉ"),
+    ("tsa", "つぁ"), ("tsi", "つぃ"), ("tse", "つぇ"), ("tso", "つぉ"),
+];
+
+// ヘボン式の長音記号（母音 + "-" として扱う）
+#[rustfmt::skip]
+const MACRONS: &[(char, char)] = &[
+    ('ā', 'a'), ('ī', 'i'), ('ū', 'u'), ('ē', 'e'), ('ō', 'o'),
+    ('â', 'a'), ('î', 'i'), ('û', 'u'), ('ê', 'e'), ('ô', 'o'),
+];
+
+/// Convert kana and/or romaji text into phonemes of `db`.
+///
+/// Handles yōon (small kana), sokuon (っ, doubled romaji consonants), the
+/// moraic nasal (ん, `n` / `n'`) and long vowels (ー, macrons). Characters
+/// that cannot be converted, or whose phonemes are missing from `db`, are
+/// reported in `unknown` with their character index in `text`; spaces and
+/// punctuation are skipped.
+pub fn to_phonemes(text: &str, db: &PhonemeDatabase) -> PhonemeMapping {
+    // かな1文字ごとに入力文字列での位置を持つ
+    let (chars, offsets): (Vec<char>, Vec<usize>) = romaji_to_kana_indexed(text)
+        .into_iter()
+        .map(|(c, offset)| (to_hiragana(c), offset))
+        .unzip();
+
+    let mut mapping = PhonemeMapping::default();
+    // 直前の「っ」の位置
+    let mut geminate = None;
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        let unknown = |mapping: &mut PhonemeMapping| {
+            mapping.unknown.push(UnknownSymbol {
+                index: offsets[index],
+                symbol: c.to_string(),
+            })
+        };
+
+        if c.is_whitespace() || is_punctuation(c) {
+            index += 1;
+            continue;
+        }
+
+        match c {
+            SOKUON => {
+                geminate = Some(index);
+                index += 1;
+                continue;
+            }
+            CHOONPU => {
+                // 直前の母音を長音にする
+                let lengthened = mapping.phonemes.last().and_then(|last| db.lengthen(last));
+                match lengthened {
+                    Some(long) => *mapping.phonemes.last_mut().unwrap() = long,
+                    None => unknown(&mut mapping),
+                }
+                index += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        // 拗音など2文字の綴りを優先する
+        let matched = [2, 1].into_iter().find_map(|len| {
+            let end = index + len;
+            if end > chars.len() {
+                return None;
+            }
+            let spelling: String = chars[index..end].iter().collect();
+            KANA.iter()
+                .find(|(kana, _)| *kana == spelling)
+                .map(|(_, ipa)| (len, *ipa))
+        });
+
+        let Some((len, ipa)) = matched else {
+            unknown(&mut mapping);
+            index += 1;
+            continue;
+        };
+
+        let phonemes: Option<Vec<Phoneme>> =
+            ipa.iter().map(|s| db.find_by_ipa(s).cloned()).collect();
+        match phonemes {
+            Some(phonemes) => {
+                if let Some(sokuon) = geminate.take() {
+                    match geminate_consonant(&phonemes[0], db) {
+                        Some(consonant) => mapping.phonemes.push(consonant),
+                        None => mapping.unknown.push(UnknownSymbol {
+                            index: offsets[sokuon],
+                            symbol: SOKUON.to_string(),
+                        }),
+                    }
+                }
+                mapping.phonemes.extend(phonemes);
+            }
+            None => mapping.unknown.push(UnknownSymbol {
+                index: offsets[index],
+                symbol: chars[index..index + len].iter().collect(),
+            }),
+        }
+        index += len;
+    }
+
+    // 語末の「っ」は対応する子音がない
+    if let Some(sokuon) = geminate {
+        mapping.unknown.push(UnknownSymbol {
+            index: offsets[sokuon],
+            symbol: SOKUON.to_string(),
+        });
+    }
+
+    mapping
+}
+
+// 促音は後続の子音を重ねる（破擦音は閉鎖部分のみ: っち → t tɕ）
+fn geminate_consonant(next: &Phoneme, db: &PhonemeDatabase) -> Option<Phoneme> {
+    match next.category {
+        PhonemeCategory::Consonant => Some(next.clone()),
+        PhonemeCategory::Affricate => {
+            let closure = next.components.first()?;
+            db.find_by_ipa(&closure.ipa).cloned()
+        }
+        _ => None,
+    }
+}
+
+/// Render a phoneme sequence as hiragana.
+///
+/// Consonants that do not form a mora are shown as IPA, so an unpronounceable
+/// reversal such as `[a k]` reads "あk".
+pub fn to_kana(phonemes: &[Phoneme]) -> String {
+    let mut kana = String::new();
+    let mut index = 0;
+
+    while index < phonemes.len() {
+        let phoneme = &phonemes[index];
+
+        // 語中の同じ子音の連続は促音
+        if let Some(next) = phonemes.get(index + 1) {
+            let closure = next.components.first().map(|c| c.ipa.as_str());
+            let doubled = next.ipa == phoneme.ipa || closure == Some(phoneme.ipa.as_str());
+            if doubled && !phoneme.category.is_vowel() && phoneme.ipa != "ɴ" && index > 0 {
+                kana.push(SOKUON);
+                index += 1;
+                continue;
+            }
+        }
+
+        // 長母音は短母音のかなに長音符を付ける
+        let (base, long): (Vec<&str>, bool) = (1..=3)
+            .rev()
+            .filter(|len| index + len <= phonemes.len())
+            .map(|len| {
+                let mora = &phonemes[index..index + len];
+                let long = mora.last().unwrap().category == PhonemeCategory::LongVowel;
+                let base: Vec<&str> = mora
+                    .iter()
+                    .map(|p| match p.category {
+                        PhonemeCategory::LongVowel => p
+                            .components
+                            .first()
+                            .map(|c| c.ipa.as_str())
+                            .unwrap_or_else(|| p.ipa.trim_end_matches(IPA_LENGTH_MARK)),
+                        _ => p.ipa.as_str(),
+                    })
+                    .collect();
+                (base, long)
+            })
+            .find(|(base, _)| kana_for(base).is_some())
+            .unwrap_or_else(|| (vec![phoneme.ipa.as_str()], false));
+
+        match kana_for(&base) {
+            Some(spelling) => {
+                kana.push_str(spelling);
+                if long {
+                    kana.push(CHOONPU);
+                }
+            }
+            None => kana.push_str(&phoneme.ipa),
+        }
+        index += base.len();
+    }
+
+    kana
+}
+
+fn kana_for(ipa: &[&str]) -> Option<&'static str> {
+    KANA.iter()
+        .find(|(_, spelling)| *spelling == ipa)
+        .map(|(kana, _)| *kana)
+}
+
+/// Convert romaji in `text` into hiragana, leaving other characters as-is.
+pub fn romaji_to_kana(text: &str) -> String {
+    romaji_to_kana_indexed(text)
+        .into_iter()
+        .map(|(c, _)| c)
+        .collect()
+}
+
+// かなと、その文字を生んだ入力の文字位置の組
+fn romaji_to_kana_indexed(text: &str) -> Vec<(char, usize)> {
+    let (chars, offsets): (Vec<char>, Vec<usize>) = text
+        .chars()
+        .enumerate()
+        .flat_map(|(offset, c)| c.to_lowercase().map(move |c| (c, offset)))
+        .flat_map(|(c, offset)| match MACRONS.iter().find(|(m, _)| *m == c) {
+            Some((_, vowel)) => vec![(*vowel, offset), ('-', offset)],
+            None => vec![(c, offset)],
+        })
+        .unzip();
+    let mut kana = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        let next = chars.get(index + 1).copied();
+        let offset = offsets[index];
+
+        if c == '-' {
+            kana.push((CHOONPU, offset));
+            index += 1;
+            continue;
+        }
+
+        if !c.is_ascii_alphabetic() {
+            // n' の区切り記号は読み捨てる
+            if !(c == '\'' && kana.last().is_some_and(|(k, _)| *k == MORAIC_NASAL)) {
+                kana.push((c, offset));
+            }
+            index += 1;
+            continue;
+        }
+
+        // 撥音: n の後に母音・y が続かない場合（nn は1つの「ん」）、b/p/m の前の m
+        if c == 'n' && !next.is_some_and(|n| is_vowel(n) || n == 'y') {
+            kana.push((MORAIC_NASAL, offset));
+            let doubled = next == Some('n')
+                && !chars
+                    .get(index + 2)
+                    .is_some_and(|&n| is_vowel(n) || n == 'y');
+            index += if doubled { 2 } else { 1 };
+            continue;
+        }
+        if c == 'm' && matches!(next, Some('b' | 'p' | 'm')) {
+            kana.push((MORAIC_NASAL, offset));
+            index += 1;
+            continue;
+        }
+
+        // 促音: 同じ子音の連続と tch
+        if !is_vowel(c) && (next == Some(c) || (c == 't' && next == Some('c'))) {
+            kana.push((SOKUON, offset));
+            index += 1;
+            continue;
+        }
+
+        let matched = (1..=3).rev().find_map(|len| {
+            let end = index + len;
+            if end > chars.len() {
+                return None;
+            }
+            let spelling: String = chars[index..end].iter().collect();
+            ROMAJI
+                .iter()
+                .find(|(romaji, _)| *romaji == spelling)
+                .map(|(_, kana)| (len, *kana))
+        });
+
+        match matched {
+            Some((len, spelling)) => {
+                kana.extend(spelling.chars().map(|k| (k, offset)));
+                index += len;
+            }
+            None => {
+                kana.push((c, offset));
+                index += 1;
+            }
+        }
+    }
+
+    kana
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'i' | 'u' | 'e' | 'o')
+}
+
+// カタカナ（ァ〜ヶ）をひらがなに寄せる
+fn to_hiragana(c: char) -> char {
+    match c {
+        'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+fn is_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation() || matches!(c, '、' | '。' | '・' | '「' | '」' | '！' | '？')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::synth::Language;
+
+    fn db() -> PhonemeDatabase {
+        PhonemeDatabase::new().for_language(Language::Japanese)
+    }
+
+    fn ipa(text: &str) -> Vec<String> {
+        let mapping = to_phonemes(text, &db());
+        assert!(mapping.unknown.is_empty(), "{:?}", mapping.unknown);
+        mapping.phonemes.into_iter().map(|p| p.ipa).collect()
+    }
+
+    #[test]
+    fn test_hiragana_katakana_and_romaji_agree() {
+        let expected = vec!["k", "o", "ɴ", "n", "i", "tɕ", "i", "w", "a"];
+        assert_eq!(ipa("こんにちわ"), expected);
+        assert_eq!(ipa("コンニチワ"), expected);
+        assert_eq!(ipa("konnichiwa"), expected);
+        assert_eq!(ipa("Kon'nichiwa"), expected);
+    }
+
+    #[test]
+    fn test_small_kana_and_sokuon() {
+        assert_eq!(ipa("きょう"), vec!["k", "j", "o", "ɯ"]);
+        assert_eq!(ipa("きって"), vec!["k", "i", "t", "t", "e"]);
+        assert_eq!(ipa("kitte"), vec!["k", "i", "t", "t", "e"]);
+        // 破擦音の前の促音は閉鎖音
+        assert_eq!(ipa("matcha"), vec!["m", "a", "t", "tɕ", "a"]);
+    }
+
+    #[test]
+    fn test_moraic_nasal() {
+        assert_eq!(ipa("shimbun"), vec!["ɕ", "i", "ɴ", "b", "ɯ", "ɴ"]);
+        assert_eq!(ipa("kan'i"), vec!["k", "a", "ɴ", "i"]);
+        assert_eq!(ipa("kani"), vec!["k", "a", "n", "i"]);
+    }
+
+    #[test]
+    fn test_long_vowels() {
+        assert_eq!(ipa("ラーメン"), vec!["r", "aː", "m", "e", "ɴ"]);
+        assert_eq!(ipa("tōkyō"), vec!["t", "oː", "k", "j", "oː"]);
+        assert_eq!(ipa("ra-men"), vec!["r", "aː", "m", "e", "ɴ"]);
+    }
+
+    #[test]
+    fn test_unknown_characters_are_reported() {
+        let mapping = to_phonemes("か漢っ", &db());
+        assert_eq!(mapping.phonemes.len(), 2);
+        let unknown: Vec<_> = mapping
+            .unknown
+            .iter()
+            .map(|u| (u.index, u.symbol.as_str()))
+            .collect();
+        assert_eq!(unknown, vec![(1, "漢"), (2, "っ")]);
+
+        // 英語のインベントリには ɯ がない
+        let english = PhonemeDatabase::new().for_language(Language::English);
+        assert_eq!(to_phonemes("う", &english).unknown[0].symbol, "う");
+    }
+
+    #[test]
+    fn test_unknown_romaji_points_into_typed_text() {
+        // "kya" は1文字の「きゃ」になるが、位置は入力の文字で数える
+        let mapping = to_phonemes("kyaqū漢", &db());
+        let unknown: Vec<_> = mapping
+            .unknown
+            .iter()
+            .map(|u| (u.index, u.symbol.as_str()))
+            .collect();
+        assert_eq!(unknown, vec![(3, "q"), (5, "漢")]);
+
+        // 語末の促音は重ねた子音の1文字目を指す
+        let mapping = to_phonemes("Kakk", &db());
+        let unknown: Vec<_> = mapping
+            .unknown
+            .iter()
+            .map(|u| (u.index, u.symbol.as_str()))
+            .collect();
+        assert_eq!(unknown, vec![(3, "k"), (2, "っ")]);
+    }
+
+    #[test]
+    fn test_to_kana_round_trip() {
+        for text in ["こんにちわ", "きょう", "きって", "らーめん", "しゃしん"] {
+            let mapping = to_phonemes(text, &db());
+            assert_eq!(to_kana(&mapping.phonemes), text);
+        }
+    }
+
+    #[test]
+    fn test_to_kana_long_vowel_without_components() {
+        // ユーザーのインベントリでは長母音に components がないことがある
+        let mut phonemes = to_phonemes("らーめん", &db()).phonemes;
+        phonemes[1].components.clear();
+        assert_eq!(to_kana(&phonemes), "らーめん");
+    }
+
+    #[test]
+    fn test_to_kana_shows_stray_consonants_as_ipa() {
+        // [k a t a] の逆順 [a t a k]
+        let mapping = to_phonemes("かた", &db());
+        let reversed: Vec<Phoneme> = mapping.phonemes.into_iter().rev().collect();
+        assert_eq!(to_kana(&reversed), "あたk");
+    }
+}
@@ -1,9 +1,18 @@
 pub mod error;
 pub mod audio;
+pub mod segment;
 pub mod phoneme;
 pub mod converter;
+pub mod reversal;
+pub mod phonotactics;
+pub mod kana;
+pub mod text;
+pub mod project;
+pub mod config;
 pub mod synth;
 pub mod playback;
+pub mod capture;
 pub mod tui;
+pub mod cli;
 
 pub use error::{PhonemeReverserError, Result};
@@ -1,13 +1,52 @@
 use anyhow::{Context, Result};
+use clap::Parser;
 use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use phoneme_reverser::cli::{self, BackendArgs, Cli, Command};
+use phoneme_reverser::playback::AudioPlayer;
 use phoneme_reverser::tui::{app::App, event::handle_events, ui::render};
+use phoneme_reverser::PhonemeReverserError;
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match cli.command.unwrap_or(Command::Tui) {
+        Command::Tui => run_tui(&cli.backend),
+        command => cli::run(command, &cli.backend).map_err(anyhow::Error::from),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            // PhonemeReverserError 由来なら variant ごとの終了コードを返す
+            match e.downcast_ref::<PhonemeReverserError>() {
+                Some(err) => ExitCode::from(err.exit_code()),
+                None => ExitCode::FAILURE,
+            }
+        }
+    }
+}
+
+fn run_tui(backend_args: &BackendArgs) -> Result<()> {
+    let phoneme_db = backend_args
+        .database()
+        .context("Failed to load phoneme inventory")?;
+    let converter = backend_args
+        .converter(&phoneme_db)
+        .context("Failed to initialize converter")?;
+    let synthesizer = backend_args
+        .synthesizer()
+        .context("Failed to initialize synthesizer")?;
+    let config = backend_args
+        .load_config()
+        .context("Failed to load config")?;
 
-fn main() -> Result<()> {
     // パニック時のクリーンアップ設定
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
@@ -25,7 +64,10 @@ fn main() -> Result<()> {
     let mut terminal = Terminal::new(backend).context("Failed to create terminal")?;
 
     // アプリケーションの初期化
-    let mut app = App::new().context("Failed to initialize app")?;
+    let mut app = App::with_backends(phoneme_db, converter, synthesizer)
+        .context("Failed to initialize app")?
+        .with_audio_sink(backend_args.audio_sink(AudioPlayer::new()?))
+        .with_config(config, backend_args.config_path());
 
     // メインループ
     let result = run_app(&mut terminal, &mut app);
@@ -44,6 +86,9 @@ fn run_app<B: ratatui::backend::Backend>(
     app: &mut App,
 ) -> Result<()> {
     loop {
+        // 再生状態の更新
+        app.tick();
+
         // 描画
         terminal.draw(|f| render(f, app))?;
 
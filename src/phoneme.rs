@@ -1,7 +1,39 @@
 use crate::error::{PhonemeReverserError, Result};
+use crate::tui::phoneme_db::{Phoneme, PhonemeDatabase};
 use pyo3::prelude::*;
 use std::path::Path;
 
+// allosaurus が出力する異音・表記ゆれを PhonemeDatabase の IPA に寄せる対応表
+// （データベースに完全一致する記号がない場合のみ使う）
+pub(crate) const IPA_ALIASES: &[(&str, &str)] = &[
+    ("ɾ", "r"),
+    ("ɹ", "r"),
+    ("ɡ", "g"),
+    ("ɯ", "u"),
+    ("ʊ", "u"),
+    ("ɪ", "i"),
+    ("ɛ", "e"),
+    ("æ", "a"),
+    ("ɐ", "ə"),
+    ("ʌ", "ə"),
+    ("ɒ", "ɔ"),
+    ("ɕ", "ʃ"),
+    ("ʑ", "ʒ"),
+    ("ɸ", "f"),
+    ("β", "b"),
+    ("ç", "h"),
+    ("x", "h"),
+    ("ɴ", "n"),
+    ("ɲ", "n"),
+    // espeak-ng の英語IPA出力に現れる記号
+    ("ɚ", "ɜ"),
+    ("ᵻ", "ɪ"),
+    ("ɫ", "l"),
+];
+
+// 音素の同一性に影響しない補助記号（長音・有気・口蓋化など）
+const IGNORED_MODIFIERS: &[char] = &['ː', 'ˑ', 'ʰ', 'ʲ', 'ʷ', '̃', '̥', '̚'];
+
 pub struct PhonemeRecognizer {
     // PyO3のPython GILとモデルを保持
     model: Py<PyAny>,
@@ -21,7 +53,7 @@ impl PhonemeRecognizer {
 
             // Get current working directory and construct venv path
             let cwd = std::env::current_dir()
-                .map_err(|e| PhonemeReverserError::Io(e))?;
+                .map_err(PhonemeReverserError::Io)?;
             let venv_site_packages = cwd.join(".venv/lib/python3.13/site-packages");
 
             if venv_site_packages.exists() {
@@ -103,11 +135,148 @@ impl PhonemeRecognizer {
     }
 }
 
+/// A recognized symbol that has no counterpart in `PhonemeDatabase`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownSymbol {
+    /// Index into the input (recognizer output or characters of typed text).
+    pub index: usize,
+    pub symbol: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PhonemeMapping {
+    pub phonemes: Vec<Phoneme>,
+    pub unknown: Vec<UnknownSymbol>,
+}
+
+/// Map recognizer output onto `PhonemeDatabase` entries.
+///
+/// Each symbol is tried as-is, then via `IPA_ALIASES`, then with modifiers
+/// stripped, and finally split into its individual characters (e.g. `tʃ`).
+/// Symbols that still don't resolve are reported in `unknown`.
+pub fn map_to_database(symbols: &[String], db: &PhonemeDatabase) -> PhonemeMapping {
+    let mut mapping = PhonemeMapping::default();
+
+    for (index, symbol) in symbols.iter().enumerate() {
+        match resolve_symbol(symbol, db) {
+            Some(phonemes) => mapping.phonemes.extend(phonemes),
+            None => mapping.unknown.push(UnknownSymbol {
+                index,
+                symbol: symbol.clone(),
+            }),
+        }
+    }
+
+    mapping
+}
+
+fn resolve_symbol(symbol: &str, db: &PhonemeDatabase) -> Option<Vec<Phoneme>> {
+    if let Some(phoneme) = lookup(symbol, db) {
+        return Some(vec![phoneme]);
+    }
+
+    // 長母音は長音記号を除いた母音の長音として扱う
+    if let Some(long) = symbol
+        .strip_suffix('ː')
+        .and_then(|base| lookup(base, db))
+        .and_then(|base| db.lengthen(&base))
+    {
+        return Some(vec![long]);
+    }
+
+    let stripped: String = symbol.chars().filter(|c| !IGNORED_MODIFIERS.contains(c)).collect();
+    if stripped.is_empty() {
+        return None;
+    }
+    if let Some(phoneme) = lookup(&stripped, db) {
+        return Some(vec![phoneme]);
+    }
+
+    // 破擦音などの多文字記号は1文字ずつ分解して対応付ける
+    stripped
+        .chars()
+        .map(|c| lookup(c.encode_utf8(&mut [0; 4]), db))
+        .collect()
+}
+
+fn lookup(symbol: &str, db: &PhonemeDatabase) -> Option<Phoneme> {
+    db.find_by_ipa(symbol)
+        .or_else(|| {
+            IPA_ALIASES
+                .iter()
+                .find(|(alias, _)| *alias == symbol)
+                .and_then(|(_, ipa)| db.find_by_ipa(ipa))
+        })
+        .cloned()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::synth::Language;
     use std::env;
 
+    fn symbols(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn ipa_of(mapping: &PhonemeMapping) -> Vec<&str> {
+        mapping.phonemes.iter().map(|p| p.ipa.as_str()).collect()
+    }
+
+    #[test]
+    fn test_map_exact_symbols() {
+        let db = PhonemeDatabase::new();
+        let mapping = map_to_database(&symbols(&["k", "a", "ʃ", "i"]), &db);
+        assert_eq!(ipa_of(&mapping), vec!["k", "a", "ʃ", "i"]);
+        assert!(mapping.unknown.is_empty());
+    }
+
+    #[test]
+    fn test_map_aliases_and_modifiers() {
+        let db = PhonemeDatabase::new().for_language(Language::English);
+        let mapping = map_to_database(&symbols(&["ɾ", "ɯ", "kʰ", "aː", "ɡ"]), &db);
+        assert_eq!(ipa_of(&mapping), vec!["r", "u", "k", "aː", "g"]);
+        assert!(mapping.unknown.is_empty());
+
+        // 日本語のインベントリには ɯ がそのまま存在する
+        let db = PhonemeDatabase::new().for_language(Language::Japanese);
+        let mapping = map_to_database(&symbols(&["ɯ", "ɕ"]), &db);
+        assert_eq!(ipa_of(&mapping), vec!["ɯ", "ɕ"]);
+    }
+
+    #[test]
+    fn test_map_splits_multi_character_symbols() {
+        let db = PhonemeDatabase::new().for_language(Language::Japanese);
+        let mapping = map_to_database(&symbols(&["tʃ", "a"]), &db);
+        assert_eq!(ipa_of(&mapping), vec!["t", "ʃ", "a"]);
+
+        // 破擦音を持つ言語では1音素として対応付ける
+        let db = PhonemeDatabase::new().for_language(Language::English);
+        let mapping = map_to_database(&symbols(&["tʃ", "aɪ"]), &db);
+        assert_eq!(ipa_of(&mapping), vec!["tʃ", "aɪ"]);
+    }
+
+    #[test]
+    fn test_map_flags_unknown_symbols() {
+        let db = PhonemeDatabase::new();
+        let mapping = map_to_database(&symbols(&["a", "ʘ", "k", "ǀ"]), &db);
+        assert_eq!(ipa_of(&mapping), vec!["a", "k"]);
+        assert_eq!(
+            mapping.unknown,
+            vec![
+                UnknownSymbol {
+                    index: 1,
+                    symbol: "ʘ".to_string()
+                },
+                UnknownSymbol {
+                    index: 3,
+                    symbol: "ǀ".to_string()
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_phoneme_recognizer_initialization() {
         // Check which Python is being used
@@ -139,7 +308,7 @@ mod tests {
         assert!(result.is_ok(), "Failed to recognize phonemes");
 
         let phonemes = result.unwrap();
-        assert!(phonemes.len() > 0, "No phonemes recognized");
+        assert!(!phonemes.is_empty(), "No phonemes recognized");
 
         // 音素が妥当な形式（通常1-4文字）かチェック
         for p in &phonemes {
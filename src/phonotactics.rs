@@ -0,0 +1,316 @@
+use crate::synth::Language;
+use crate::tui::phoneme_db::{Phoneme, PhonemeDatabase};
+use std::fmt;
+
+/// Kind of phonotactic violation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// Consonants that cannot follow each other (`kt` in Japanese).
+    Cluster,
+    /// A phoneme that cannot start a word (`ɴ` in Japanese, `ŋ` in English).
+    Onset,
+    /// A phoneme that cannot close a syllable or word (`k#` in Japanese).
+    Coda,
+}
+
+impl ViolationKind {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ViolationKind::Cluster => "illegal cluster",
+            ViolationKind::Onset => "illegal onset",
+            ViolationKind::Coda => "illegal coda",
+        }
+    }
+}
+
+/// An edit that removes a violation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Repair {
+    /// Insert an (epenthetic) phoneme before `index`.
+    Insert { index: usize, phoneme: Phoneme },
+    /// Replace the phoneme at `index`.
+    Replace { index: usize, phoneme: Phoneme },
+    /// Remove the phoneme at `index`.
+    Delete { index: usize },
+}
+
+impl Repair {
+    pub fn apply(&self, phonemes: &mut Vec<Phoneme>) {
+        match self {
+            Repair::Insert { index, phoneme } => {
+                phonemes.insert((*index).min(phonemes.len()), phoneme.clone())
+            }
+            Repair::Replace { index, phoneme } => {
+                if let Some(slot) = phonemes.get_mut(*index) {
+                    *slot = phoneme.clone();
+                }
+            }
+            Repair::Delete { index } => {
+                if *index < phonemes.len() {
+                    phonemes.remove(*index);
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Repair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Repair::Insert { phoneme, .. } => write!(f, "insert [{}]", phoneme.ipa),
+            Repair::Replace { phoneme, .. } => write!(f, "replace with [{}]", phoneme.ipa),
+            Repair::Delete { .. } => write!(f, "delete"),
+        }
+    }
+}
+
+/// A violation at `index` of the checked sequence.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    pub index: usize,
+    pub kind: ViolationKind,
+    /// Suggested fix, if the inventory has the phonemes it needs.
+    pub repair: Option<Repair>,
+}
+
+/// Check a sequence (treated as a single word) against the rules of
+/// `language`.
+///
+/// Repairs use phonemes from `db`, so pass the inventory of the same
+/// language. Indices refer to the unmodified sequence; after applying one
+/// repair, check again.
+pub fn validate(phonemes: &[Phoneme], language: Language, db: &PhonemeDatabase) -> Vec<Violation> {
+    match language {
+        Language::Japanese => validate_japanese(phonemes, db),
+        Language::English => validate_english(phonemes, db),
+    }
+}
+
+/// Apply the first available repair until none is left.
+pub fn repair_all(phonemes: &[Phoneme], language: Language, db: &PhonemeDatabase) -> Vec<Phoneme> {
+    let mut repaired = phonemes.to_vec();
+
+    // 1回の修正で違反が増えることはないが、念のため回数を制限する
+    for _ in 0..=phonemes.len() * 2 {
+        let repair = validate(&repaired, language, db)
+            .into_iter()
+            .find_map(|v| v.repair);
+        match repair {
+            Some(repair) => repair.apply(&mut repaired),
+            None => break,
+        }
+    }
+
+    repaired
+}
+
+// 日本語: 子音の後には母音が必要（撥音・促音・拗音の「C + j」を除く）
+fn validate_japanese(phonemes: &[Phoneme], db: &PhonemeDatabase) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    for (index, phoneme) in phonemes.iter().enumerate() {
+        if phoneme.category.is_vowel() {
+            continue;
+        }
+
+        if phoneme.ipa == "ɴ" {
+            if index == 0 {
+                violations.push(Violation {
+                    index,
+                    kind: ViolationKind::Onset,
+                    repair: Some(match db.find_by_ipa("n") {
+                        Some(n) => Repair::Replace {
+                            index,
+                            phoneme: n.clone(),
+                        },
+                        None => Repair::Delete { index },
+                    }),
+                });
+            }
+            continue;
+        }
+
+        let kind = match phonemes.get(index + 1) {
+            None => ViolationKind::Coda,
+            Some(next) if next.category.is_vowel() => continue,
+            Some(next) if next.ipa == "j" => continue,
+            // 促音（語中の同じ子音の連続）
+            Some(next) if next.ipa == phoneme.ipa && index > 0 => continue,
+            Some(_) => ViolationKind::Cluster,
+        };
+        violations.push(Violation {
+            index,
+            kind,
+            repair: japanese_epenthesis(phoneme, db).map(|vowel| Repair::Insert {
+                index: index + 1,
+                phoneme: vowel,
+            }),
+        });
+    }
+
+    violations
+}
+
+// 外来語と同じく t/d の後は「o」、それ以外は「u」を挿入する
+fn japanese_epenthesis(consonant: &Phoneme, db: &PhonemeDatabase) -> Option<Phoneme> {
+    let candidates: &[&str] = match consonant.ipa.as_str() {
+        "t" | "d" => &["o"],
+        _ => &["ɯ", "u"],
+    };
+    candidates
+        .iter()
+        .find_map(|ipa| db.find_by_ipa(ipa))
+        .cloned()
+}
+
+// 英語: 語頭の ŋ、母音の前以外の h、4つ以上の子音連続を禁止する
+fn validate_english(phonemes: &[Phoneme], db: &PhonemeDatabase) -> Vec<Violation> {
+    const MAX_CLUSTER: usize = 3;
+
+    let schwa = db.find_by_ipa("ə").cloned();
+    let mut violations = Vec::new();
+    let mut run = 0;
+
+    for (index, phoneme) in phonemes.iter().enumerate() {
+        if phoneme.category.is_vowel() {
+            run = 0;
+            continue;
+        }
+        run += 1;
+
+        let next_is_vowel = phonemes
+            .get(index + 1)
+            .is_some_and(|next| next.category.is_vowel());
+
+        let violation = if phoneme.ipa == "ŋ" && index == 0 {
+            Some((
+                ViolationKind::Onset,
+                db.find_by_ipa("n").map(|n| Repair::Replace {
+                    index,
+                    phoneme: n.clone(),
+                }),
+            ))
+        } else if phoneme.ipa == "h" && !next_is_vowel {
+            Some((ViolationKind::Coda, Some(Repair::Delete { index })))
+        } else if run > MAX_CLUSTER {
+            Some((
+                ViolationKind::Cluster,
+                schwa.clone().map(|vowel| Repair::Insert {
+                    index,
+                    phoneme: vowel,
+                }),
+            ))
+        } else {
+            None
+        };
+
+        if let Some((kind, repair)) = violation {
+            // 挿入で子音連続が切れる
+            if kind == ViolationKind::Cluster {
+                run = 1;
+            }
+            violations.push(Violation {
+                index,
+                kind,
+                repair,
+            });
+        }
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(language: Language, ipa: &[&str]) -> (Vec<Phoneme>, Vec<Violation>, PhonemeDatabase) {
+        let db = PhonemeDatabase::new().for_language(language);
+        let phonemes: Vec<Phoneme> = ipa
+            .iter()
+            .map(|s| db.find_by_ipa(s).unwrap().clone())
+            .collect();
+        let violations = validate(&phonemes, language, &db);
+        (phonemes, violations, db)
+    }
+
+    fn ipa_string(phonemes: &[Phoneme]) -> String {
+        phonemes.iter().map(|p| p.ipa.as_str()).collect()
+    }
+
+    #[test]
+    fn test_japanese_accepts_cv_and_moraic_nasal() {
+        let (_, violations, _) = check(Language::Japanese, &["k", "a", "ɴ", "t", "a"]);
+        assert!(violations.is_empty());
+
+        // 促音と拗音
+        let (_, violations, _) = check(Language::Japanese, &["a", "k", "k", "j", "o"]);
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_japanese_reports_cluster_and_coda() {
+        let (phonemes, violations, db) = check(Language::Japanese, &["k", "t", "a", "k"]);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].index, 0);
+        assert_eq!(violations[0].kind, ViolationKind::Cluster);
+        assert_eq!(violations[1].index, 3);
+        assert_eq!(violations[1].kind, ViolationKind::Coda);
+
+        assert_eq!(
+            ipa_string(&repair_all(&phonemes, Language::Japanese, &db)),
+            "kɯtakɯ"
+        );
+    }
+
+    #[test]
+    fn test_japanese_epenthesis_after_t_uses_o() {
+        let (phonemes, violations, db) = check(Language::Japanese, &["a", "t"]);
+        assert_eq!(
+            violations[0].repair.as_ref().unwrap().to_string(),
+            "insert [o]"
+        );
+        assert_eq!(
+            ipa_string(&repair_all(&phonemes, Language::Japanese, &db)),
+            "ato"
+        );
+    }
+
+    #[test]
+    fn test_japanese_initial_moraic_nasal() {
+        let (phonemes, violations, db) = check(Language::Japanese, &["ɴ", "a"]);
+        assert_eq!(violations[0].kind, ViolationKind::Onset);
+        assert_eq!(
+            ipa_string(&repair_all(&phonemes, Language::Japanese, &db)),
+            "na"
+        );
+    }
+
+    #[test]
+    fn test_english_rules() {
+        let (_, violations, _) = check(Language::English, &["s", "t", "r", "ɪ", "ŋ", "k", "s"]);
+        assert!(violations.is_empty());
+
+        let (phonemes, violations, db) =
+            check(Language::English, &["ŋ", "ɪ", "h", "k", "s", "t", "r", "ə"]);
+        let kinds: Vec<_> = violations.iter().map(|v| (v.index, v.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (0, ViolationKind::Onset),
+                (2, ViolationKind::Coda),
+                (5, ViolationKind::Cluster),
+            ]
+        );
+        assert_eq!(
+            ipa_string(&repair_all(&phonemes, Language::English, &db)),
+            "nɪkstərə"
+        );
+    }
+
+    #[test]
+    fn test_affricate_counts_as_one_consonant() {
+        let (_, violations, _) = check(Language::Japanese, &["ts", "ɯ"]);
+        assert!(violations.is_empty());
+    }
+}
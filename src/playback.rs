@@ -1,145 +1,639 @@
-use crate::audio::AudioData;
+use crate::audio::{convert, AudioData, ResampleQuality};
 use crate::error::{PhonemeReverserError, Result};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SampleFormat, SizedSample, SupportedStreamConfigRange};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
+// 完了通知後もデバイス側のバッファに残っている分を鳴らし切るための待ち時間
+const DRAIN_MARGIN: Duration = Duration::from_millis(150);
+
+// 音声デバイスを使わないシンクが1回に進める時間
+const SIMULATED_BLOCK: Duration = Duration::from_millis(10);
+
+// コールバックで書き込めるサンプル形式（先頭ほど優先）
+const SAMPLE_FORMATS: [SampleFormat; 3] = [SampleFormat::F32, SampleFormat::I16, SampleFormat::U16];
+
+/// Format of the output stream chosen for a clip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputFormat {
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub sample_format: SampleFormat,
+}
+
+/// An output device, identified by its host (audio API) and name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputDevice {
+    pub host: String,
+    pub name: String,
+}
+
+impl fmt::Display for OutputDevice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.host)
+    }
+}
+
+/// A range of stream formats an output device accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SupportedFormat {
+    pub channels: u16,
+    pub min_sample_rate: u32,
+    pub max_sample_rate: u32,
+    pub sample_format: SampleFormat,
+}
+
+impl From<&SupportedStreamConfigRange> for SupportedFormat {
+    fn from(range: &SupportedStreamConfigRange) -> Self {
+        Self {
+            channels: range.channels(),
+            min_sample_rate: range.min_sample_rate().0,
+            max_sample_rate: range.max_sample_rate().0,
+            sample_format: range.sample_format(),
+        }
+    }
+}
+
+impl fmt::Display for SupportedFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ch, ", self.channels)?;
+        if self.min_sample_rate == self.max_sample_rate {
+            write!(f, "{} Hz", self.min_sample_rate)?;
+        } else {
+            write!(f, "{}-{} Hz", self.min_sample_rate, self.max_sample_rate)?;
+        }
+        write!(f, ", {}", self.sample_format)
+    }
+}
+
+/// An output device found by [`output_devices`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceInfo {
+    pub device: OutputDevice,
+    /// Whether this is the default output device of its host.
+    pub is_default: bool,
+    pub formats: Vec<SupportedFormat>,
+}
+
+/// List the output devices of every available host.
+///
+/// Hosts and devices that cannot be queried (e.g. a JACK server that is not
+/// running) are skipped.
+pub fn output_devices() -> Vec<DeviceInfo> {
+    let mut devices = Vec::new();
+    for id in cpal::available_hosts() {
+        let Ok(host) = cpal::host_from_id(id) else {
+            continue;
+        };
+        let default_name = host.default_output_device().and_then(|d| d.name().ok());
+        let Ok(outputs) = host.output_devices() else {
+            continue;
+        };
+        for device in outputs {
+            let Ok(name) = device.name() else {
+                continue;
+            };
+            let formats = device
+                .supported_output_configs()
+                .map(|configs| configs.map(|range| SupportedFormat::from(&range)).collect())
+                .unwrap_or_default();
+            devices.push(DeviceInfo {
+                is_default: default_name.as_deref() == Some(name.as_str()),
+                device: OutputDevice {
+                    host: id.name().to_string(),
+                    name,
+                },
+                formats,
+            });
+        }
+    }
+    devices
+}
+
+/// Where played audio goes.
+///
+/// [`AudioPlayer`] plays on a sound card; [`NullSink`] and [`WavSink`] only
+/// simulate real-time playback, so the playback path also works (and can be
+/// tested) on machines without one.
+pub trait AudioSink {
+    /// Short sink name shown in the UI.
+    fn name(&self) -> &'static str;
+
+    /// Start playing `audio` and return immediately.
+    fn play(&self, audio: &AudioData) -> Result<PlaybackHandle>;
+
+    /// The selected output device; `None` for the system default or for
+    /// sinks without devices.
+    fn device(&self) -> Option<&OutputDevice> {
+        None
+    }
+
+    /// Select the output device; ignored by sinks without devices.
+    fn set_device(&mut self, _device: Option<OutputDevice>) {}
+}
+
+/// Sink that plays on a cpal output device.
 pub struct AudioPlayer {
-    // プレイヤーは状態を持たない（シンプルな設計）
+    // 再生ごとの状態は PlaybackHandle が持つ。プレイヤーは出力先と変換の品質のみ
+    quality: ResampleQuality,
+    device: Option<OutputDevice>,
 }
 
 impl AudioPlayer {
     pub fn new() -> Result<Self> {
-        Ok(Self {})
+        Ok(Self {
+            quality: ResampleQuality::default(),
+            device: None,
+        })
     }
 
-    pub fn play(&self, audio: &AudioData) -> Result<()> {
-        eprintln!("[PLAYBACK] Starting playback...");
-        eprintln!("[PLAYBACK] Audio info: sample_rate={}, channels={}, samples={}",
-                  audio.sample_rate, audio.channels, audio.samples.len());
+    /// Resampling quality used when the device does not support the clip's rate.
+    pub fn with_quality(mut self, quality: ResampleQuality) -> Self {
+        self.quality = quality;
+        self
+    }
+
+    /// Play on `device` instead of the default output device.
+    pub fn with_device(mut self, device: Option<OutputDevice>) -> Self {
+        self.device = device;
+        self
+    }
 
-        // 1. デフォルトホストとデバイスを取得
-        let host = cpal::default_host();
+    fn open_device(&self) -> Result<(cpal::Device, String)> {
+        let Some(selected) = &self.device else {
+            let device = cpal::default_host().default_output_device().ok_or_else(|| {
+                PhonemeReverserError::AudioPlayback("No output device available".to_string())
+            })?;
+            let name = device.name().unwrap_or_else(|_| "default".to_string());
+            return Ok((device, name));
+        };
+
+        let host = cpal::available_hosts()
+            .into_iter()
+            .find(|id| id.name() == selected.host)
+            .and_then(|id| cpal::host_from_id(id).ok())
+            .ok_or_else(|| {
+                let message = format!("Audio host '{}' is not available", selected.host);
+                PhonemeReverserError::AudioPlayback(message)
+            })?;
         let device = host
-            .default_output_device()
-            .ok_or_else(|| PhonemeReverserError::AudioPlayback("No output device available".to_string()))?;
-
-        eprintln!("[PLAYBACK] Output device: {:?}", device.name());
-
-        // 2. オーディオデータのサンプルレートに合わせた設定を使用
-        let mut config: cpal::StreamConfig = device
-            .default_output_config()
-            .map_err(|e| PhonemeReverserError::AudioPlayback(format!("Failed to get default config: {}", e)))?
-            .into();
-
-        // サンプルレートを音声データに合わせる
-        config.sample_rate = cpal::SampleRate(audio.sample_rate);
-        config.channels = audio.channels;
-
-        eprintln!("[PLAYBACK] Config: sample_rate={}, channels={}", config.sample_rate.0, config.channels);
-
-        // 3. サンプルデータをクローン
-        let samples = audio.samples.clone();
-        let channels = audio.channels;
-        let total_samples = samples.len();
-
-        // サンプルインデックスを共有（Mutexで保護）
-        let sample_idx = Arc::new(Mutex::new(0usize));
-        let sample_idx_clone = sample_idx.clone();
-
-        // 4. 出力ストリームのコールバック
-        let stream = device
-                    .build_output_stream(
-                        &config,
-                        move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
-                            let mut idx = sample_idx_clone.lock().unwrap();
-                            for frame in data.chunks_mut(channels as usize) {
-                                if *idx < samples.len() {
-                                    for sample in frame.iter_mut() {
-                                        *sample = samples[*idx];
-                                        *idx += 1;
-                                        if *idx >= samples.len() {
-                                            break;
-                                        }
-                                    }
-                                } else {
-                                    // 再生完了: 無音を出力
-                                    for sample in frame.iter_mut() {
-                                        *sample = 0.0;
-                                    }
-                                }
-                            }
-                        },
-                        |err| eprintln!("Stream error: {}", err),
-                        None,
-                    )
-                    .map_err(|e| PhonemeReverserError::AudioPlayback(format!("Failed to build stream: {}", e)))?;
-
-        // 5. ストリームを再生
-        eprintln!("[PLAYBACK] Starting stream...");
+            .output_devices()
+            .map_err(|e| {
+                PhonemeReverserError::AudioPlayback(format!("Failed to list devices: {}", e))
+            })?
+            .find(|device| device.name().is_ok_and(|name| name == selected.name))
+            .ok_or_else(|| device_unavailable(&selected.name))?;
+        Ok((device, selected.name.clone()))
+    }
+}
+
+impl AudioSink for AudioPlayer {
+    fn name(&self) -> &'static str {
+        "cpal"
+    }
+
+    /// Start playback on the selected (or default) output device and return
+    /// immediately.
+    ///
+    /// The stream uses the supported device format closest to the clip, and
+    /// the clip is resampled and remixed to it when they differ. Playback
+    /// continues until the returned handle reports completion, is stopped,
+    /// or is dropped.
+    fn play(&self, audio: &AudioData) -> Result<PlaybackHandle> {
+        // 1. 選択中（未選択ならデフォルト）のデバイスを取得
+        let (device, device_name) = self.open_device()?;
+
+        // 2. デバイスが対応する形式から音声に最も近いものを選ぶ
+        let format = negotiate_format(&device, &device_name, audio)?;
+        let config = cpal::StreamConfig {
+            channels: format.channels,
+            sample_rate: cpal::SampleRate(format.sample_rate),
+            buffer_size: cpal::BufferSize::Default,
+        };
+        let audio = convert(audio, format.sample_rate, format.channels, self.quality);
+
+        // 3. コールバックと共有する再生状態
+        let (done_tx, done_rx) = mpsc::channel();
+        let state = Arc::new(PlaybackState::new(audio.samples, done_tx));
+
+        // 4. 出力ストリームを構築して再生開始
+        let stream = match format.sample_format {
+            SampleFormat::I16 => build_stream::<i16>(&device, &device_name, &config, state.clone()),
+            SampleFormat::U16 => build_stream::<u16>(&device, &device_name, &config, state.clone()),
+            _ => build_stream::<f32>(&device, &device_name, &config, state.clone()),
+        }
+        .map_err(|e| match e {
+            cpal::BuildStreamError::DeviceNotAvailable => device_unavailable(&device_name),
+            e => PhonemeReverserError::AudioPlayback(format!("Failed to build stream: {}", e)),
+        })?;
+
         stream
             .play()
             .map_err(|e| PhonemeReverserError::AudioPlayback(format!("Failed to play stream: {}", e)))?;
 
-        // 6. 再生完了まで待機
-        let duration_secs = total_samples as f32 / (audio.sample_rate * channels as u32) as f32;
-        eprintln!("[PLAYBACK] Playing for {:.2} seconds...", duration_secs);
-        std::thread::sleep(std::time::Duration::from_secs_f32(duration_secs + 0.5));
+        Ok(PlaybackHandle {
+            _stream: Some(stream),
+            clock: Mutex::new(None),
+            state,
+            done_rx,
+            sample_rate: audio.sample_rate,
+            channels: audio.channels,
+        })
+    }
+
+    fn device(&self) -> Option<&OutputDevice> {
+        self.device.as_ref()
+    }
+
+    fn set_device(&mut self, device: Option<OutputDevice>) {
+        self.device = device;
+    }
+}
+
+/// Sink that discards audio while advancing in real time.
+#[derive(Debug, Default)]
+pub struct NullSink;
+
+impl AudioSink for NullSink {
+    fn name(&self) -> &'static str {
+        "null"
+    }
+
+    fn play(&self, audio: &AudioData) -> Result<PlaybackHandle> {
+        Ok(simulate(audio, |_| Ok(())))
+    }
+}
+
+/// Sink that advances in real time like [`NullSink`] and writes what was
+/// played to a WAV file when playback ends.
+///
+/// Each playback replaces the file; a stopped playback keeps only the part
+/// that was played.
+#[derive(Debug)]
+pub struct WavSink {
+    path: PathBuf,
+}
+
+impl WavSink {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl AudioSink for WavSink {
+    fn name(&self) -> &'static str {
+        "wav"
+    }
+
+    fn play(&self, audio: &AudioData) -> Result<PlaybackHandle> {
+        let path = self.path.clone();
+        let (sample_rate, channels) = (audio.sample_rate, audio.channels);
+        Ok(simulate(audio, move |played| {
+            let played = AudioData {
+                samples: played.to_vec(),
+                sample_rate,
+                channels,
+            };
+            crate::audio::write_wav(&path, &played)
+        }))
+    }
+}
+
+/// Play `audio` on a thread that consumes one block per [`SIMULATED_BLOCK`]
+/// and passes the played samples to `finish` before exiting.
+fn simulate<F>(audio: &AudioData, finish: F) -> PlaybackHandle
+where
+    F: FnOnce(&[f32]) -> Result<()> + Send + 'static,
+{
+    let (done_tx, done_rx) = mpsc::channel();
+    let state = Arc::new(PlaybackState::new(audio.samples.clone(), done_tx));
+
+    let frames = (SIMULATED_BLOCK.as_secs_f64() * f64::from(audio.sample_rate)).ceil() as usize;
+    let block_len = frames.max(1) * usize::from(audio.channels.max(1));
+    let clock_state = state.clone();
+    let clock = std::thread::spawn(move || {
+        // 処理の遅れが積み重ならないよう、開始時刻からの経過で待つ
+        let start = Instant::now();
+        let mut block = vec![0.0f32; block_len];
+        let mut blocks = 0u32;
+        while !clock_state.finished.load(Ordering::Acquire) {
+            clock_state.fill(&mut block);
+            blocks += 1;
+            let next = start + SIMULATED_BLOCK * blocks;
+            std::thread::sleep(next.saturating_duration_since(Instant::now()));
+        }
+
+        let played = clock_state.position.load(Ordering::Acquire);
+        if let Err(e) = finish(&clock_state.samples[..played]) {
+            clock_state.set_error(e.to_string());
+        }
+    });
 
-        eprintln!("[PLAYBACK] Playback complete!");
-        Ok(())
+    PlaybackHandle {
+        _stream: None,
+        clock: Mutex::new(Some(clock)),
+        state,
+        done_rx,
+        sample_rate: audio.sample_rate,
+        channels: audio.channels,
     }
 }
 
+fn device_unavailable(name: &str) -> PhonemeReverserError {
+    PhonemeReverserError::AudioPlayback(format!("Output device '{}' is not available", name))
+}
+
+fn negotiate_format(
+    device: &cpal::Device,
+    device_name: &str,
+    audio: &AudioData,
+) -> Result<OutputFormat> {
+    // 対応形式を列挙できないデバイスは既定の設定で鳴らす
+    let supported: Vec<SupportedStreamConfigRange> = device
+        .supported_output_configs()
+        .map(|configs| configs.collect())
+        .unwrap_or_default();
+    if let Some(format) = choose_format(&supported, audio.sample_rate, audio.channels) {
+        return Ok(format);
+    }
+
+    let default = device.default_output_config().map_err(|e| match e {
+        cpal::DefaultStreamConfigError::DeviceNotAvailable => device_unavailable(device_name),
+        e => PhonemeReverserError::AudioPlayback(format!("Failed to get default config: {}", e)),
+    })?;
+    if !SAMPLE_FORMATS.contains(&default.sample_format()) {
+        return Err(PhonemeReverserError::AudioPlayback(format!(
+            "Unsupported sample format: {}",
+            default.sample_format()
+        )));
+    }
+    Ok(OutputFormat {
+        channels: default.channels(),
+        sample_rate: default.sample_rate().0,
+        sample_format: default.sample_format(),
+    })
+}
+
+/// Pick the supported config closest to `sample_rate` and `channels`.
+///
+/// Matching the channel count comes first (more channels are preferred to
+/// fewer, since upmixing loses nothing), then the nearest sample rate, then
+/// the sample format in the order of [`SAMPLE_FORMATS`].
+pub fn choose_format(
+    supported: &[SupportedStreamConfigRange],
+    sample_rate: u32,
+    channels: u16,
+) -> Option<OutputFormat> {
+    supported
+        .iter()
+        .filter_map(|range| {
+            let format_rank = SAMPLE_FORMATS.iter().position(|f| *f == range.sample_format())?;
+            let rate = sample_rate.clamp(range.min_sample_rate().0, range.max_sample_rate().0);
+            let channel_rank = match range.channels().cmp(&channels) {
+                std::cmp::Ordering::Equal => 0,
+                std::cmp::Ordering::Greater => u32::from(range.channels() - channels),
+                // チャンネルが足りない形式は、多い形式がすべて無いときだけ使う
+                std::cmp::Ordering::Less => {
+                    u32::from(u16::MAX) + u32::from(channels - range.channels())
+                }
+            };
+            let format = OutputFormat {
+                channels: range.channels(),
+                sample_rate: rate,
+                sample_format: range.sample_format(),
+            };
+            Some(((channel_rank, rate.abs_diff(sample_rate), format_rank), format))
+        })
+        .min_by_key(|(rank, _)| *rank)
+        .map(|(_, format)| format)
+}
+
+fn build_stream<T>(
+    device: &cpal::Device,
+    device_name: &str,
+    config: &cpal::StreamConfig,
+    state: Arc<PlaybackState>,
+) -> std::result::Result<cpal::Stream, cpal::BuildStreamError>
+where
+    T: SizedSample + FromSample<f32>,
+{
+    let error_state = state.clone();
+    let device_name = device_name.to_string();
+    device.build_output_stream(
+        config,
+        move |data: &mut [T], _: &cpal::OutputCallbackInfo| state.fill(data),
+        // 再生中にデバイスが外されたときはデバイス名を添えて報告する
+        move |err| {
+            let message = match err {
+                cpal::StreamError::DeviceNotAvailable => {
+                    format!("Output device '{}' was disconnected", device_name)
+                }
+                err => err.to_string(),
+            };
+            error_state.set_error(message)
+        },
+        None,
+    )
+}
+
 impl Default for AudioPlayer {
     fn default() -> Self {
         Self::new().unwrap()
     }
 }
 
+/// State shared between a `PlaybackHandle` and the stream callback.
+struct PlaybackState {
+    samples: Vec<f32>,
+    // 次に出力するサンプルのインデックス（インターリーブ済み）
+    position: AtomicUsize,
+    paused: AtomicBool,
+    finished: AtomicBool,
+    error: Mutex<Option<String>>,
+    done_tx: Mutex<Sender<()>>,
+}
+
+impl PlaybackState {
+    fn new(samples: Vec<f32>, done_tx: Sender<()>) -> Self {
+        Self {
+            samples,
+            position: AtomicUsize::new(0),
+            paused: AtomicBool::new(false),
+            finished: AtomicBool::new(false),
+            error: Mutex::new(None),
+            done_tx: Mutex::new(done_tx),
+        }
+    }
+
+    /// Stream callback body: copy the next block of samples into `data`.
+    fn fill<T: SizedSample + FromSample<f32>>(&self, data: &mut [T]) {
+        let silence = T::from_sample(0.0f32);
+        if self.paused.load(Ordering::Acquire) || self.finished.load(Ordering::Acquire) {
+            data.fill(silence);
+            return;
+        }
+
+        let start = self.position.load(Ordering::Acquire);
+        let end = (start + data.len()).min(self.samples.len());
+        let written = end - start;
+
+        for (out, sample) in data.iter_mut().zip(&self.samples[start..end]) {
+            *out = T::from_sample(*sample);
+        }
+        // 再生完了: 残りは無音を出力
+        data[written..].fill(silence);
+        self.position.store(end, Ordering::Release);
+
+        if end >= self.samples.len() {
+            self.finish();
+        }
+    }
+
+    fn finish(&self) {
+        if !self.finished.swap(true, Ordering::AcqRel) {
+            let _ = self.done_tx.lock().unwrap().send(());
+        }
+    }
+
+    fn set_error(&self, message: String) {
+        *self.error.lock().unwrap() = Some(message);
+        self.finish();
+    }
+}
+
+/// Handle to a running playback.
+///
+/// Dropping the handle stops the stream.
+pub struct PlaybackHandle {
+    // cpal のストリーム、またはデバイスを使わないシンクの再生スレッド
+    _stream: Option<cpal::Stream>,
+    clock: Mutex<Option<JoinHandle<()>>>,
+    state: Arc<PlaybackState>,
+    done_rx: Receiver<()>,
+    sample_rate: u32,
+    channels: u16,
+}
+
+impl PlaybackHandle {
+    pub fn stop(&self) {
+        self.state.finish();
+    }
+
+    pub fn pause(&self) {
+        self.state.paused.store(true, Ordering::Release);
+    }
+
+    pub fn resume(&self) {
+        self.state.paused.store(false, Ordering::Release);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.paused.load(Ordering::Acquire)
+    }
+
+    /// Whether playback reached the end, was stopped, or failed.
+    pub fn is_finished(&self) -> bool {
+        self.state.finished.load(Ordering::Acquire)
+    }
+
+    /// Stream error reported by the device, if any.
+    pub fn error(&self) -> Option<String> {
+        self.state.error.lock().unwrap().clone()
+    }
+
+    pub fn position(&self) -> Duration {
+        self.samples_to_duration(self.state.position.load(Ordering::Acquire))
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.samples_to_duration(self.state.samples.len())
+    }
+
+    /// Playback progress in `0.0..=1.0`.
+    pub fn progress(&self) -> f64 {
+        let total = self.state.samples.len();
+        if total == 0 {
+            return 1.0;
+        }
+        self.state.position.load(Ordering::Acquire) as f64 / total as f64
+    }
+
+    /// Block until playback completes.
+    pub fn wait(&self) -> Result<()> {
+        let _ = self.done_rx.recv();
+        // 再生スレッドは終了処理（ファイルへの書き出し）まで待つ
+        match self.clock.lock().unwrap().take() {
+            Some(clock) => {
+                let _ = clock.join();
+            }
+            None => std::thread::sleep(DRAIN_MARGIN),
+        }
+
+        match self.error() {
+            Some(message) => Err(PhonemeReverserError::AudioPlayback(format!(
+                "Stream error: {}",
+                message
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    fn samples_to_duration(&self, samples: usize) -> Duration {
+        let per_second = self.sample_rate as f64 * self.channels.max(1) as f64;
+        Duration::from_secs_f64(samples as f64 / per_second)
+    }
+}
+
+impl Drop for PlaybackHandle {
+    fn drop(&mut self) {
+        self.stop();
+        if let Some(clock) = self.clock.get_mut().unwrap().take() {
+            let _ = clock.join();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn test_state(len: usize) -> (PlaybackState, Receiver<()>) {
+        let (tx, rx) = mpsc::channel();
+        let samples = (0..len).map(|i| i as f32 + 1.0).collect();
+        (PlaybackState::new(samples, tx), rx)
+    }
+
     #[test]
     fn test_audio_player_creation() {
         let player = AudioPlayer::new();
         assert!(player.is_ok(), "Failed to create AudioPlayer");
     }
 
-    #[test]
-    fn test_play_simple_audio() {
-        let player = AudioPlayer::new().unwrap();
-
-        // 440Hz サイン波（ラ音）を1秒生成
-        let sample_rate = 44100;
-        let duration = 1.0;
-        let frequency = 440.0;
-        let samples: Vec<f32> = (0..(sample_rate as f32 * duration) as usize)
+    // 440Hz サイン波（ラ音）
+    fn sine(sample_rate: u32, duration: f32) -> AudioData {
+        let samples = (0..(sample_rate as f32 * duration) as usize)
             .map(|i| {
                 let t = i as f32 / sample_rate as f32;
-                (2.0 * std::f32::consts::PI * frequency * t).sin() * 0.5
+                (2.0 * std::f32::consts::PI * 440.0 * t).sin() * 0.5
             })
             .collect();
-
-        let audio = AudioData {
+        AudioData {
             samples,
             sample_rate,
             channels: 1,
-        };
-
-        let result = player.play(&audio);
-        assert!(result.is_ok(), "Failed to play audio: {:?}", result.err());
+        }
     }
 
-    #[test]
-    fn test_play_stereo_audio() {
-        let player = AudioPlayer::new().unwrap();
-
-        // ステレオ音声: 左チャンネル440Hz、右チャンネル880Hz
-        let sample_rate = 44100;
-        let duration = 0.5;
+    // ステレオ音声: 左チャンネル440Hz、右チャンネル880Hz
+    fn stereo_sine(sample_rate: u32, duration: f32) -> AudioData {
         let mut samples = Vec::new();
-
         for i in 0..(sample_rate as f32 * duration) as usize {
             let t = i as f32 / sample_rate as f32;
             let left = (2.0 * std::f32::consts::PI * 440.0 * t).sin() * 0.3;
@@ -147,14 +641,265 @@ mod tests {
             samples.push(left);
             samples.push(right);
         }
-
-        let audio = AudioData {
+        AudioData {
             samples,
             sample_rate,
             channels: 2,
-        };
+        }
+    }
 
-        let result = player.play(&audio);
+    #[test]
+    fn test_play_simple_audio() {
+        let audio = sine(44100, 0.25);
+        let result = NullSink.play(&audio);
+        assert!(result.is_ok(), "Failed to play audio: {:?}", result.err());
+
+        let handle = result.unwrap();
+        assert_eq!(handle.duration(), Duration::from_millis(250));
+        assert!(handle.wait().is_ok());
+        assert!(handle.is_finished());
+        assert_eq!(handle.position(), handle.duration());
+    }
+
+    #[test]
+    fn test_play_stereo_audio() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stereo.wav");
+        let audio = stereo_sine(44100, 0.1);
+
+        let result = WavSink::new(&path).play(&audio);
         assert!(result.is_ok(), "Failed to play stereo audio: {:?}", result.err());
+        assert!(result.unwrap().wait().is_ok());
+
+        // 左右のチャンネルが入れ替わらずに書き出される（16bitの量子化誤差のみ）
+        let played = crate::audio::read_wav(&path).unwrap();
+        assert_eq!((played.sample_rate, played.channels), (44100, 2));
+        assert_eq!(played.samples.len(), audio.samples.len());
+        for (played, expected) in played.samples.iter().zip(&audio.samples) {
+            assert!((played - expected).abs() < 1e-3, "{} != {}", played, expected);
+        }
+    }
+
+    // サウンドカードのある環境で `cargo test -- --ignored` で実行する
+    #[test]
+    #[ignore = "requires an output device"]
+    fn test_play_on_sound_card() {
+        let player = AudioPlayer::new().unwrap();
+        for audio in [sine(44100, 0.5), stereo_sine(44100, 0.5)] {
+            let result = player.play(&audio);
+            assert!(result.is_ok(), "Failed to play audio: {:?}", result.err());
+            let handle = result.unwrap();
+            assert_eq!(handle.duration(), Duration::from_millis(500));
+            assert!(handle.wait().is_ok());
+            assert!(handle.is_finished());
+        }
+    }
+
+    #[test]
+    fn test_fill_advances_position_and_completes() {
+        let (state, rx) = test_state(6);
+        let mut buffer = [0.0; 4];
+
+        state.fill(&mut buffer);
+        assert_eq!(buffer, [1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(state.position.load(Ordering::Acquire), 4);
+        assert!(!state.finished.load(Ordering::Acquire));
+
+        state.fill(&mut buffer);
+        assert_eq!(buffer, [5.0, 6.0, 0.0, 0.0]);
+        assert!(state.finished.load(Ordering::Acquire));
+        assert!(rx.try_recv().is_ok(), "Completion should be signalled");
+
+        // 完了後は無音のみ
+        state.fill(&mut buffer);
+        assert_eq!(buffer, [0.0; 4]);
+        assert!(rx.try_recv().is_err(), "Completion should be signalled once");
+    }
+
+    #[test]
+    fn test_fill_outputs_silence_while_paused() {
+        let (state, _rx) = test_state(8);
+        let mut buffer = [0.0; 4];
+
+        state.paused.store(true, Ordering::Release);
+        state.fill(&mut buffer);
+        assert_eq!(buffer, [0.0; 4]);
+        assert_eq!(state.position.load(Ordering::Acquire), 0);
+
+        state.paused.store(false, Ordering::Release);
+        state.fill(&mut buffer);
+        assert_eq!(buffer, [1.0, 2.0, 3.0, 4.0]);
+    }
+
+    fn range(channels: u16, min: u32, max: u32, fmt: SampleFormat) -> SupportedStreamConfigRange {
+        SupportedStreamConfigRange::new(
+            channels,
+            cpal::SampleRate(min),
+            cpal::SampleRate(max),
+            cpal::SupportedBufferSize::Unknown,
+            fmt,
+        )
+    }
+
+    #[test]
+    fn test_choose_format_prefers_exact_match() {
+        let supported = [
+            range(2, 44100, 48000, SampleFormat::F32),
+            range(1, 8000, 48000, SampleFormat::I16),
+            range(1, 8000, 48000, SampleFormat::F32),
+        ];
+        assert_eq!(
+            choose_format(&supported, 22050, 1),
+            Some(OutputFormat {
+                channels: 1,
+                sample_rate: 22050,
+                sample_format: SampleFormat::F32,
+            })
+        );
+    }
+
+    #[test]
+    fn test_choose_format_falls_back_to_closest() {
+        // 22050Hz モノラルに対応しない一般的なデバイス
+        let supported = [
+            range(2, 44100, 48000, SampleFormat::I16),
+            range(6, 22050, 22050, SampleFormat::F32),
+        ];
+        let format = choose_format(&supported, 22050, 1).unwrap();
+        assert_eq!((format.channels, format.sample_rate), (2, 44100));
+
+        // チャンネル数が足りない形式は最後の候補
+        let mono_only = [range(1, 48000, 48000, SampleFormat::F32)];
+        let format = choose_format(&mono_only, 44100, 2).unwrap();
+        assert_eq!((format.channels, format.sample_rate), (1, 48000));
+
+        // 書き込めないサンプル形式は選ばない
+        assert_eq!(choose_format(&[range(2, 48000, 48000, SampleFormat::I32)], 48000, 2), None);
+        assert_eq!(choose_format(&[], 48000, 2), None);
+    }
+
+    #[test]
+    fn test_null_sink_plays_in_real_time() {
+        let audio = AudioData::silence(8000, 2, Duration::from_millis(60));
+        let start = Instant::now();
+        let handle = NullSink.play(&audio).unwrap();
+        assert_eq!(handle.duration(), Duration::from_millis(60));
+        assert!(!handle.is_finished());
+
+        handle.wait().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert!(handle.is_finished());
+        assert_eq!(handle.progress(), 1.0);
+    }
+
+    #[test]
+    fn test_null_sink_pause_holds_position() {
+        let audio = AudioData::silence(8000, 1, Duration::from_secs(10));
+        let handle = NullSink.play(&audio).unwrap();
+        handle.pause();
+        std::thread::sleep(Duration::from_millis(30));
+        let position = handle.position();
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(handle.position(), position);
+
+        handle.resume();
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(handle.position() > position);
+        handle.stop();
+        handle.wait().unwrap();
+    }
+
+    #[test]
+    fn test_wav_sink_records_played_audio() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("played.wav");
+        let audio = AudioData {
+            samples: (0..800).map(|i| ((i % 100) as f32 / 100.0) - 0.5).collect(),
+            sample_rate: 16000,
+            channels: 2,
+        };
+
+        let sink = WavSink::new(&path);
+        assert_eq!(sink.name(), "wav");
+        sink.play(&audio).unwrap().wait().unwrap();
+        let played = crate::audio::read_wav(&path).unwrap();
+        assert_eq!((played.sample_rate, played.channels), (16000, 2));
+        assert_eq!(played.samples.len(), audio.samples.len());
+
+        // 途中で止めると再生した分だけが残る
+        let long = AudioData::silence(16000, 1, Duration::from_secs(10));
+        let handle = sink.play(&long).unwrap();
+        std::thread::sleep(Duration::from_millis(30));
+        handle.stop();
+        handle.wait().unwrap();
+        let played = crate::audio::read_wav(&path).unwrap();
+        assert!(!played.samples.is_empty());
+        assert!(played.samples.len() < long.samples.len());
+    }
+
+    #[test]
+    fn test_wav_sink_reports_write_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let sink = WavSink::new(dir.path().join("missing").join("played.wav"));
+        let audio = AudioData::silence(8000, 1, Duration::from_millis(10));
+        assert!(matches!(
+            sink.play(&audio).unwrap().wait(),
+            Err(PhonemeReverserError::AudioPlayback(_))
+        ));
+    }
+
+    #[test]
+    fn test_supported_format_display() {
+        let format = SupportedFormat::from(&range(2, 44100, 48000, SampleFormat::F32));
+        assert_eq!(format.to_string(), "2 ch, 44100-48000 Hz, f32");
+        let format = SupportedFormat::from(&range(1, 22050, 22050, SampleFormat::I16));
+        assert_eq!(format.to_string(), "1 ch, 22050 Hz, i16");
+    }
+
+    #[test]
+    fn test_missing_device_names_the_device() {
+        let player = AudioPlayer::new().unwrap().with_device(Some(OutputDevice {
+            host: "No Such Host".to_string(),
+            name: "Missing".to_string(),
+        }));
+        let audio = AudioData::silence(22050, 1, Duration::from_millis(10));
+        match player.play(&audio) {
+            Err(PhonemeReverserError::AudioPlayback(message)) => {
+                assert!(message.contains("No Such Host"), "{}", message)
+            }
+            _ => panic!("Expected a playback error"),
+        }
+
+        // ホストはあってもデバイスが無い場合
+        let host = cpal::default_host().id().name().to_string();
+        let player = player.with_device(Some(OutputDevice {
+            host,
+            name: "Unplugged USB Headset".to_string(),
+        }));
+        match player.play(&audio) {
+            Err(PhonemeReverserError::AudioPlayback(message)) => {
+                assert!(message.contains("Unplugged USB Headset"), "{}", message)
+            }
+            _ => panic!("Expected a playback error"),
+        }
+    }
+
+    #[test]
+    fn test_fill_converts_sample_format() {
+        let (tx, _rx) = mpsc::channel();
+        let state = PlaybackState::new(vec![0.0, 1.0, -1.0], tx);
+        let mut buffer = [1i16; 4];
+        state.fill(&mut buffer);
+        assert_eq!(buffer, [0, i16::MAX, i16::MIN, 0]);
+    }
+
+    #[test]
+    fn test_stream_error_finishes_playback() {
+        let (state, rx) = test_state(8);
+        state.set_error("device unplugged".to_string());
+
+        assert!(state.finished.load(Ordering::Acquire));
+        assert!(rx.try_recv().is_ok());
+        assert_eq!(state.error.lock().unwrap().as_deref(), Some("device unplugged"));
     }
 }
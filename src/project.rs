@@ -0,0 +1,266 @@
+use crate::error::{PhonemeReverserError, Result};
+use crate::reversal::{DiphthongPolicy, ReversalStrategy};
+use crate::synth::{Language, SynthConfig};
+use crate::tui::phoneme_db::Phoneme;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+/// Schema version written by this build.
+pub const CURRENT_VERSION: u64 = 2;
+
+// MIGRATIONS[n] は version n+1 のファイルを version n+2 に書き換える
+const MIGRATIONS: &[fn(&mut Value)] = &[add_voice_settings];
+
+/// A project file: named phoneme sequences with their synthesis settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Project {
+    pub version: u64,
+    pub sequences: Vec<Sequence>,
+}
+
+/// One sequence of a project, stored as IPA so that files stay readable and
+/// independent of the key layout.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sequence {
+    pub name: String,
+    pub phonemes: Vec<String>,
+    pub language: Language,
+    pub speed: u32,
+    pub pitch: u32,
+    pub amplitude: u32,
+    pub word_gap: u32,
+    pub variant: Option<String>,
+    #[serde(default)]
+    pub strategy: ReversalStrategy,
+    #[serde(default)]
+    pub diphthongs: DiphthongPolicy,
+}
+
+impl Default for Project {
+    fn default() -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            sequences: Vec::new(),
+        }
+    }
+}
+
+impl Project {
+    /// Read a project file, migrating older schema versions.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path).map_err(|e| project_error(path, e))?;
+        Self::from_json(&text).map_err(|e| project_error(path, e))
+    }
+
+    /// Write the project as pretty-printed JSON with the current version.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.to_json()?).map_err(|e| project_error(path, e))
+    }
+
+    pub fn from_json(text: &str) -> Result<Self> {
+        let mut value: Value = serde_json::from_str(text).map_err(json_error)?;
+        let version = value
+            .get("version")
+            .and_then(Value::as_u64)
+            .filter(|&v| v > 0)
+            .ok_or_else(|| {
+                PhonemeReverserError::Project("missing or invalid \"version\"".to_string())
+            })?;
+        if version > CURRENT_VERSION {
+            return Err(PhonemeReverserError::Project(format!(
+                "version {} is newer than supported version {}",
+                version, CURRENT_VERSION
+            )));
+        }
+
+        for migrate in &MIGRATIONS[(version - 1) as usize..] {
+            migrate(&mut value);
+        }
+        value["version"] = CURRENT_VERSION.into();
+
+        let project: Project = serde_json::from_value(value).map_err(json_error)?;
+        for sequence in &project.sequences {
+            sequence.synth_config().validate().map_err(|e| {
+                PhonemeReverserError::Project(format!("sequence '{}': {}", sequence.name, e))
+            })?;
+        }
+        Ok(project)
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        let project = Project {
+            version: CURRENT_VERSION,
+            ..self.clone()
+        };
+        serde_json::to_string_pretty(&project).map_err(json_error)
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Sequence> {
+        self.sequences.iter().find(|s| s.name == name)
+    }
+
+    /// Replace the sequence with the same name, or append it.
+    pub fn upsert(&mut self, sequence: Sequence) {
+        match self.sequences.iter_mut().find(|s| s.name == sequence.name) {
+            Some(slot) => *slot = sequence,
+            None => self.sequences.push(sequence),
+        }
+    }
+}
+
+impl Sequence {
+    pub fn new(name: impl Into<String>, phonemes: &[Phoneme], config: &SynthConfig) -> Self {
+        Self {
+            name: name.into(),
+            phonemes: phonemes.iter().map(|p| p.ipa.clone()).collect(),
+            language: config.language,
+            speed: config.speed,
+            pitch: config.pitch,
+            amplitude: config.amplitude,
+            word_gap: config.word_gap,
+            variant: config.variant.clone(),
+            strategy: ReversalStrategy::default(),
+            diphthongs: DiphthongPolicy::default(),
+        }
+    }
+
+    pub fn synth_config(&self) -> SynthConfig {
+        SynthConfig {
+            language: self.language,
+            speed: self.speed,
+            pitch: self.pitch,
+            amplitude: self.amplitude,
+            word_gap: self.word_gap,
+            variant: self.variant.clone(),
+        }
+    }
+}
+
+// v1 → v2: 音量・単語間の間隔・声のバリエーションを追加（既定値で補う）
+fn add_voice_settings(project: &mut Value) {
+    let defaults = SynthConfig::default();
+    if let Some(sequences) = project["sequences"].as_array_mut() {
+        for sequence in sequences {
+            sequence["amplitude"] = defaults.amplitude.into();
+            sequence["word_gap"] = defaults.word_gap.into();
+            sequence["variant"] = Value::Null;
+        }
+    }
+}
+
+fn json_error(e: serde_json::Error) -> PhonemeReverserError {
+    PhonemeReverserError::Project(e.to_string())
+}
+
+fn project_error(path: &Path, e: impl std::fmt::Display) -> PhonemeReverserError {
+    PhonemeReverserError::Project(format!("{}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::phoneme_db::PhonemeDatabase;
+
+    fn sample() -> Project {
+        let db = PhonemeDatabase::new();
+        let phonemes: Vec<Phoneme> = ["k", "a", "m", "i"]
+            .iter()
+            .map(|ipa| db.find_by_ipa(ipa).unwrap().clone())
+            .collect();
+        let mut sequence = Sequence::new("kami", &phonemes, &SynthConfig::default());
+        sequence.strategy = ReversalStrategy::CvSwap;
+
+        Project {
+            version: CURRENT_VERSION,
+            sequences: vec![sequence],
+        }
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let project = sample();
+        let json = project.to_json().unwrap();
+        assert!(json.contains("\"cv-swap\""));
+        assert!(json.contains("\"ja\""));
+        assert_eq!(Project::from_json(&json).unwrap(), project);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("project.json");
+        sample().save(&path).unwrap();
+        assert_eq!(Project::load(&path).unwrap(), sample());
+    }
+
+    #[test]
+    fn test_rejects_unknown_versions() {
+        let newer = format!(
+            "{{\"version\": {}, \"sequences\": []}}",
+            CURRENT_VERSION + 1
+        );
+        assert!(matches!(
+            Project::from_json(&newer),
+            Err(PhonemeReverserError::Project(_))
+        ));
+        assert!(Project::from_json("{\"sequences\": []}").is_err());
+    }
+
+    #[test]
+    fn test_missing_reversal_settings_use_defaults() {
+        let json = r#"{"version": 2, "sequences": [
+            {"name": "a", "phonemes": ["a"], "language": "en", "speed": 120, "pitch": 40,
+             "amplitude": 80, "word_gap": 0, "variant": "f3"}
+        ]}"#;
+        let sequence = &Project::from_json(json).unwrap().sequences[0];
+        assert_eq!(sequence.language, Language::English);
+        assert_eq!(sequence.strategy, ReversalStrategy::Segment);
+        assert_eq!(sequence.synth_config().speed, 120);
+        assert_eq!(sequence.synth_config().voice_name(), "en-us+f3");
+    }
+
+    #[test]
+    fn test_migrates_version_1() {
+        let json = r#"{"version": 1, "sequences": [
+            {"name": "a", "phonemes": ["a"], "language": "ja", "speed": 120, "pitch": 40,
+             "strategy": "mora"}
+        ]}"#;
+        let project = Project::from_json(json).unwrap();
+        assert_eq!(project.version, CURRENT_VERSION);
+
+        let config = project.sequences[0].synth_config();
+        assert_eq!(config.speed, 120);
+        assert_eq!(config.amplitude, SynthConfig::default().amplitude);
+        assert_eq!(config.variant, None);
+        assert_eq!(project.sequences[0].strategy, ReversalStrategy::Mora);
+    }
+
+    #[test]
+    fn test_rejects_out_of_range_settings() {
+        let json = r#"{"version": 2, "sequences": [
+            {"name": "loud", "phonemes": [], "language": "ja", "speed": 175, "pitch": 50,
+             "amplitude": 500, "word_gap": 0, "variant": null}
+        ]}"#;
+        let err = Project::from_json(json).unwrap_err();
+        assert!(err.to_string().contains("loud"), "{}", err);
+    }
+
+    #[test]
+    fn test_every_old_version_has_a_migration() {
+        assert_eq!(MIGRATIONS.len() as u64, CURRENT_VERSION - 1);
+    }
+
+    #[test]
+    fn test_upsert_replaces_by_name() {
+        let mut project = sample();
+        let mut sequence = project.sequences[0].clone();
+        sequence.pitch = 80;
+        project.upsert(sequence);
+        assert_eq!(project.sequences.len(), 1);
+        assert_eq!(project.find("kami").unwrap().pitch, 80);
+
+        project.upsert(Sequence::new("other", &[], &SynthConfig::default()));
+        assert_eq!(project.sequences.len(), 2);
+    }
+}
@@ -0,0 +1,548 @@
+use crate::audio::{concatenate, reverse_samples, AudioData};
+use crate::error::Result;
+use crate::segment::SegmentedAudio;
+use crate::tui::phoneme_db::{Phoneme, PhonemeCategory};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Pause between the parts of [`ReversalMode::Demo`].
+pub const DEMO_GAP: Duration = Duration::from_millis(500);
+
+/// How diphthongs are treated when a sequence is reversed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DiphthongPolicy {
+    /// Keep the diphthong as one unit (`aɪ` stays `aɪ`).
+    #[default]
+    Atomic,
+    /// Reverse the glide as well (`aɪ` becomes `ɪa`).
+    Flip,
+}
+
+impl DiphthongPolicy {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            DiphthongPolicy::Atomic => "Atomic",
+            DiphthongPolicy::Flip => "Flip",
+        }
+    }
+
+    pub fn toggled(&self) -> Self {
+        match self {
+            DiphthongPolicy::Atomic => DiphthongPolicy::Flip,
+            DiphthongPolicy::Flip => DiphthongPolicy::Atomic,
+        }
+    }
+}
+
+/// How the reversed audio is produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReversalMode {
+    /// Synthesize the reversed phoneme sequence.
+    #[default]
+    Phoneme,
+    /// Synthesize the original sequence and play the samples backwards.
+    Waveform,
+    /// Synthesize each original phoneme on its own and join the segments
+    /// in reverse order, so that coarticulation does not change them.
+    Concatenative,
+    /// Original → waveform-reversed → phoneme-reversed, one after another.
+    Demo,
+}
+
+impl ReversalMode {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ReversalMode::Phoneme => "Phoneme-reversed",
+            ReversalMode::Waveform => "Waveform-reversed",
+            ReversalMode::Concatenative => "Concatenative",
+            ReversalMode::Demo => "Demo",
+        }
+    }
+
+    /// The other side of an A/B comparison against the phoneme-reversed
+    /// audio.
+    pub fn ab_toggled(&self) -> Self {
+        match self {
+            ReversalMode::Phoneme => ReversalMode::Waveform,
+            _ => ReversalMode::Phoneme,
+        }
+    }
+
+    /// Render the audio for this mode; `render` synthesizes an IPA sequence.
+    ///
+    /// Only [`Concatenative`](Self::Concatenative) knows the phoneme
+    /// boundaries: it renders each phoneme of `reversed` on its own and joins
+    /// them in that order, overlapping by `crossfade`.
+    pub fn render<F>(
+        &self,
+        original: &[String],
+        reversed: &[String],
+        crossfade: Duration,
+        mut render: F,
+    ) -> Result<SegmentedAudio>
+    where
+        F: FnMut(&[String]) -> Result<AudioData>,
+    {
+        let audio = match self {
+            ReversalMode::Phoneme => render(reversed)?,
+            ReversalMode::Waveform => reverse_samples(&render(original)?),
+            ReversalMode::Concatenative => {
+                return SegmentedAudio::synthesize_each(reversed, crossfade, render);
+            }
+            ReversalMode::Demo => {
+                let original = render(original)?;
+                let waveform = reverse_samples(&original);
+                concatenate(&[original, waveform, render(reversed)?], DEMO_GAP)?
+            }
+        };
+        Ok(audio.into())
+    }
+}
+
+/// Unit that is reversed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReversalStrategy {
+    /// Reverse individual phonemes (ka → ak).
+    #[default]
+    Segment,
+    /// Reverse morae, keeping each mora intact (ka-mi → mi-ka).
+    Mora,
+    /// Reverse syllables, keeping onset and coda intact (kan-ta → ta-kan).
+    Syllable,
+    /// Keep syllable order but reverse inside each syllable (ka-mi → ak-im).
+    CvSwap,
+}
+
+impl ReversalStrategy {
+    pub const ALL: [ReversalStrategy; 4] = [
+        ReversalStrategy::Segment,
+        ReversalStrategy::Mora,
+        ReversalStrategy::Syllable,
+        ReversalStrategy::CvSwap,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ReversalStrategy::Segment => "Segment",
+            ReversalStrategy::Mora => "Mora",
+            ReversalStrategy::Syllable => "Syllable",
+            ReversalStrategy::CvSwap => "CV-swap",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|s| s == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// A syllable split into onset, nucleus and coda.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Syllable {
+    pub onset: Vec<Phoneme>,
+    pub nucleus: Vec<Phoneme>,
+    pub coda: Vec<Phoneme>,
+}
+
+impl Syllable {
+    pub fn phonemes(&self) -> impl Iterator<Item = &Phoneme> {
+        self.onset.iter().chain(&self.nucleus).chain(&self.coda)
+    }
+}
+
+/// Split a sequence into syllables using `PhonemeCategory`.
+///
+/// Every vowel, diphthong or long vowel is a nucleus. Of the consonants
+/// between two nuclei, the first closes the previous syllable when there are
+/// two or more, and the rest open the next one (kanta → kan.ta). A consonant
+/// followed by a glide (`j`, `w`) is kept together as the onset, so only the
+/// consonants before it close the previous syllable (tokjo → to.kjo,
+/// kaɴkjo → kaɴ.kjo). A sequence without vowels is a single syllable.
+pub fn syllabify(phonemes: &[Phoneme]) -> Vec<Syllable> {
+    let mut syllables: Vec<Syllable> = Vec::new();
+    let mut consonants: Vec<Phoneme> = Vec::new();
+
+    for phoneme in phonemes {
+        if !phoneme.category.is_vowel() {
+            consonants.push(phoneme.clone());
+            continue;
+        }
+
+        // 前の音節がある場合、子音連続の先頭（子音＋半母音の前の子音すべて）を
+        // その音節の尾子音にする
+        if let Some(previous) = syllables.last_mut() {
+            let coda_len = match consonants.len() {
+                len if len >= 2 && is_glide(&consonants[len - 1]) => len - 2,
+                len if len >= 2 => 1,
+                _ => 0,
+            };
+            previous.coda.extend(consonants.drain(..coda_len));
+        }
+        syllables.push(Syllable {
+            onset: std::mem::take(&mut consonants),
+            nucleus: vec![phoneme.clone()],
+            coda: Vec::new(),
+        });
+    }
+
+    // 末尾の子音は最後の音節の尾子音
+    match syllables.last_mut() {
+        Some(last) => last.coda.extend(consonants),
+        None if !consonants.is_empty() => syllables.push(Syllable {
+            onset: consonants,
+            ..Syllable::default()
+        }),
+        None => {}
+    }
+
+    syllables
+}
+
+// 子音と組んで頭子音になる半母音（拗音の j、合拗音の w）
+fn is_glide(phoneme: &Phoneme) -> bool {
+    phoneme.category == PhonemeCategory::Consonant && matches!(phoneme.ipa.as_str(), "j" | "w")
+}
+
+/// Split a sequence into morae: (onset) + nucleus, with every coda consonant
+/// (e.g. the moraic nasal) forming a mora of its own. Long vowels stay in
+/// their mora.
+pub fn morae(phonemes: &[Phoneme]) -> Vec<Vec<Phoneme>> {
+    let mut morae = Vec::new();
+
+    for syllable in syllabify(phonemes) {
+        let head: Vec<Phoneme> = syllable
+            .onset
+            .iter()
+            .chain(&syllable.nucleus)
+            .cloned()
+            .collect();
+        if !head.is_empty() {
+            morae.push(head);
+        }
+        morae.extend(syllable.coda.into_iter().map(|c| vec![c]));
+    }
+
+    morae
+}
+
+/// Reverse a sequence with the given strategy.
+///
+/// Affricates and long vowels always stay intact. Diphthongs follow `policy`
+/// wherever segment order inside a syllable is reversed (`Segment` and
+/// `CvSwap`); `Mora` and `Syllable` keep their units as they are.
+pub fn reverse(
+    phonemes: &[Phoneme],
+    strategy: ReversalStrategy,
+    policy: DiphthongPolicy,
+) -> Vec<Phoneme> {
+    match strategy {
+        ReversalStrategy::Segment => reverse_phonemes(phonemes, policy),
+        ReversalStrategy::Mora => morae(phonemes).into_iter().rev().flatten().collect(),
+        ReversalStrategy::Syllable => syllabify(phonemes)
+            .into_iter()
+            .rev()
+            .flat_map(|s| s.phonemes().cloned().collect::<Vec<_>>())
+            .collect(),
+        ReversalStrategy::CvSwap => syllabify(phonemes)
+            .into_iter()
+            .flat_map(|s| reverse_phonemes(&s.phonemes().cloned().collect::<Vec<_>>(), policy))
+            .collect(),
+    }
+}
+
+/// Reverse a phoneme sequence segment by segment.
+///
+/// Affricates and long vowels always stay intact; diphthongs follow `policy`.
+pub fn reverse_phonemes(phonemes: &[Phoneme], policy: DiphthongPolicy) -> Vec<Phoneme> {
+    phonemes
+        .iter()
+        .rev()
+        .map(|phoneme| match policy {
+            DiphthongPolicy::Flip if phoneme.category == PhonemeCategory::Diphthong => {
+                flip_diphthong(phoneme)
+            }
+            _ => phoneme.clone(),
+        })
+        .collect()
+}
+
+/// Reverse the components of a diphthong (`aɪ` → `ɪa`).
+pub fn flip_diphthong(phoneme: &Phoneme) -> Phoneme {
+    if phoneme.components.len() < 2 {
+        return phoneme.clone();
+    }
+
+    let components: Vec<_> = phoneme.components.iter().rev().cloned().collect();
+    Phoneme {
+        ipa: components.iter().map(|c| c.ipa.as_str()).collect(),
+        espeak: components.iter().map(|c| c.espeak.as_str()).collect(),
+        description_ja: format!("{}（反転）", phoneme.description_ja),
+        key: phoneme.key,
+        category: phoneme.category,
+        components,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::phoneme_db::PhonemeDatabase;
+
+    fn sequence(db: &PhonemeDatabase, ipa: &[&str]) -> Vec<Phoneme> {
+        ipa.iter()
+            .map(|s| db.find_by_ipa(s).unwrap().clone())
+            .collect()
+    }
+
+    fn ipa_of(phonemes: &[Phoneme]) -> Vec<&str> {
+        phonemes.iter().map(|p| p.ipa.as_str()).collect()
+    }
+
+    fn japanese(ipa: &[&str]) -> Vec<Phoneme> {
+        sequence(
+            &PhonemeDatabase::new().for_language(crate::synth::Language::Japanese),
+            ipa,
+        )
+    }
+
+    fn reversed(ipa: &[&str], strategy: ReversalStrategy) -> String {
+        reverse(&japanese(ipa), strategy, DiphthongPolicy::Atomic)
+            .iter()
+            .map(|p| p.ipa.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_syllabify() {
+        let syllables = syllabify(&japanese(&["k", "a", "ɴ", "t", "a"]));
+        assert_eq!(syllables.len(), 2);
+        assert_eq!(ipa_of(&syllables[0].onset), vec!["k"]);
+        assert_eq!(ipa_of(&syllables[0].coda), vec!["ɴ"]);
+        assert_eq!(ipa_of(&syllables[1].onset), vec!["t"]);
+
+        // 母音の連続はそれぞれ別の音節、子音1つは後ろの音節の頭子音
+        let syllables = syllabify(&japanese(&["a", "o", "k", "i"]));
+        assert_eq!(syllables.len(), 3);
+        assert!(syllables[1].coda.is_empty());
+        assert_eq!(ipa_of(&syllables[2].onset), vec!["k"]);
+
+        // 語中の拗音は頭子音のまとまり、促音・撥音だけが前の音節の尾子音
+        let syllables = syllabify(&japanese(&["t", "o", "k", "j", "o"]));
+        assert_eq!(syllables.len(), 2);
+        assert!(syllables[0].coda.is_empty());
+        assert_eq!(ipa_of(&syllables[1].onset), vec!["k", "j"]);
+        let syllables = syllabify(&japanese(&["k", "a", "ɴ", "k", "j", "o"]));
+        assert_eq!(ipa_of(&syllables[0].coda), vec!["ɴ"]);
+        assert_eq!(ipa_of(&syllables[1].onset), vec!["k", "j"]);
+
+        let syllables = syllabify(&japanese(&["s", "t"]));
+        assert_eq!(syllables.len(), 1);
+        assert!(syllables[0].nucleus.is_empty());
+        assert!(syllabify(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_morae_split_moraic_nasal() {
+        let morae = morae(&japanese(&["k", "a", "ɴ", "t", "a"]));
+        let morae: Vec<String> = morae
+            .iter()
+            .map(|m| m.iter().map(|p| p.ipa.as_str()).collect())
+            .collect();
+        assert_eq!(morae, vec!["ka", "ɴ", "ta"]);
+
+        let morae = super::morae(&japanese(&["t", "o", "k", "j", "o", "t", "t", "o"]));
+        let morae: Vec<String> = morae
+            .iter()
+            .map(|m| m.iter().map(|p| p.ipa.as_str()).collect())
+            .collect();
+        assert_eq!(morae, vec!["to", "kjo", "t", "to"]);
+    }
+
+    #[test]
+    fn test_strategies() {
+        let kami = ["k", "a", "m", "i"];
+        assert_eq!(reversed(&kami, ReversalStrategy::Segment), "imak");
+        assert_eq!(reversed(&kami, ReversalStrategy::Mora), "mika");
+        assert_eq!(reversed(&kami, ReversalStrategy::Syllable), "mika");
+        assert_eq!(reversed(&kami, ReversalStrategy::CvSwap), "akim");
+
+        let kanta = ["k", "a", "ɴ", "t", "a"];
+        assert_eq!(reversed(&kanta, ReversalStrategy::Mora), "taɴka");
+        assert_eq!(reversed(&kanta, ReversalStrategy::Syllable), "takaɴ");
+        assert_eq!(reversed(&kanta, ReversalStrategy::CvSwap), "ɴakat");
+
+        // 語中の拗音（ときょ）は分かれない
+        let tokjo = ["t", "o", "k", "j", "o"];
+        assert_eq!(reversed(&tokjo, ReversalStrategy::Mora), "kjoto");
+        assert_eq!(reversed(&tokjo, ReversalStrategy::Syllable), "kjoto");
+    }
+
+    #[test]
+    fn test_strategy_cycle() {
+        let mut strategy = ReversalStrategy::default();
+        for expected in [
+            ReversalStrategy::Mora,
+            ReversalStrategy::Syllable,
+            ReversalStrategy::CvSwap,
+            ReversalStrategy::Segment,
+        ] {
+            strategy = strategy.next();
+            assert_eq!(strategy, expected);
+        }
+    }
+
+    #[test]
+    fn test_atomic_keeps_diphthongs() {
+        let db = PhonemeDatabase::new();
+        let reversed =
+            reverse_phonemes(&sequence(&db, &["m", "aɪ", "tʃ"]), DiphthongPolicy::Atomic);
+        assert_eq!(ipa_of(&reversed), vec!["tʃ", "aɪ", "m"]);
+    }
+
+    #[test]
+    fn test_flip_reverses_diphthong_components() {
+        let db = PhonemeDatabase::new();
+        let reversed = reverse_phonemes(&sequence(&db, &["m", "aɪ", "tʃ"]), DiphthongPolicy::Flip);
+        assert_eq!(ipa_of(&reversed), vec!["tʃ", "ɪa", "m"]);
+        assert_eq!(reversed[1].espeak, "Ia");
+        assert_eq!(reversed[1].category, PhonemeCategory::Diphthong);
+    }
+
+    #[test]
+    fn test_flip_keeps_affricates_and_long_vowels() {
+        let db = PhonemeDatabase::new();
+        let long_a = db.lengthen(db.find_by_ipa("a").unwrap()).unwrap();
+        let phonemes = vec![db.find_by_ipa("ts").unwrap().clone(), long_a];
+
+        let reversed = reverse_phonemes(&phonemes, DiphthongPolicy::Flip);
+        assert_eq!(ipa_of(&reversed), vec!["aː", "ts"]);
+    }
+
+    #[test]
+    fn test_flip_twice_restores_diphthong() {
+        let db = PhonemeDatabase::new();
+        let diphthong = db.find_by_ipa("oʊ").unwrap();
+        let restored = flip_diphthong(&flip_diphthong(diphthong));
+        assert_eq!(restored.ipa, diphthong.ipa);
+        assert_eq!(restored.espeak, diphthong.espeak);
+    }
+
+    // 音素列の長さだけのサンプルを返す（1音素 = 値 i の1サンプル）
+    fn fake_render(ipa: &[String]) -> Result<AudioData> {
+        Ok(AudioData {
+            samples: (0..ipa.len())
+                .map(|i| i as f32 + if ipa[0] == "k" { 0.5 } else { 0.0 })
+                .collect(),
+            sample_rate: 10,
+            channels: 1,
+        })
+    }
+
+    #[test]
+    fn test_reversal_modes() {
+        let original = vec!["k".to_string(), "a".to_string()];
+        let reversed = vec!["a".to_string(), "k".to_string()];
+        let render = |mode: ReversalMode| {
+            mode.render(&original, &reversed, Duration::ZERO, fake_render)
+                .unwrap()
+                .audio
+        };
+
+        assert_eq!(render(ReversalMode::Phoneme).samples, vec![0.0, 1.0]);
+        assert_eq!(render(ReversalMode::Waveform).samples, vec![1.5, 0.5]);
+
+        // 元の音声 → 波形の反転 → 音素の逆順（間に DEMO_GAP の無音）
+        let demo = render(ReversalMode::Demo).samples;
+        assert_eq!(demo.len(), 6 + 2 * 5);
+        assert_eq!(&demo[..2], &[0.5, 1.5]);
+        assert_eq!(&demo[7..9], &[1.5, 0.5]);
+        assert_eq!(&demo[14..], &[0.0, 1.0]);
+
+        assert_eq!(ReversalMode::Phoneme.ab_toggled(), ReversalMode::Waveform);
+        assert_eq!(ReversalMode::Waveform.ab_toggled(), ReversalMode::Phoneme);
+    }
+
+    #[test]
+    fn test_concatenative_reverses_segments() {
+        let original = vec!["k".to_string(), "a".to_string(), "i".to_string()];
+        let reversed: Vec<String> = original.iter().rev().cloned().collect();
+        // 音素ごとに異なる長さ・値のサンプルを返す
+        let render = |ipa: &[String]| {
+            let (value, frames) = match ipa[0].as_str() {
+                "k" => (0.1, 2),
+                "a" => (0.2, 3),
+                _ => (0.3, 4),
+            };
+            Ok(AudioData {
+                samples: vec![value; frames],
+                sample_rate: 1000,
+                channels: 1,
+            })
+        };
+
+        let segmented = ReversalMode::Concatenative
+            .render(&original, &reversed, Duration::ZERO, render)
+            .unwrap();
+        let phonemes: Vec<&str> = segmented
+            .segments
+            .iter()
+            .map(|s| s.phoneme.as_str())
+            .collect();
+        assert_eq!(phonemes, vec!["i", "a", "k"]);
+        assert_eq!(
+            segmented.audio.samples,
+            vec![0.3, 0.3, 0.3, 0.3, 0.2, 0.2, 0.2, 0.1, 0.1]
+        );
+
+        let faded = ReversalMode::Concatenative
+            .render(&original, &reversed, Duration::from_millis(1), render)
+            .unwrap();
+        assert_eq!(faded.audio.samples.len(), 9 - 2);
+    }
+
+    #[test]
+    fn test_concatenative_follows_strategy() {
+        let phonemes = japanese(&["k", "a", "m", "i"]);
+        let original: Vec<String> = phonemes.iter().map(|p| p.ipa.clone()).collect();
+        let concat = |strategy: ReversalStrategy| {
+            let reversed: Vec<String> = reverse(&phonemes, strategy, DiphthongPolicy::Atomic)
+                .into_iter()
+                .map(|p| p.ipa)
+                .collect();
+            ReversalMode::Concatenative
+                .render(&original, &reversed, Duration::ZERO, fake_render)
+                .unwrap()
+                .segments
+                .into_iter()
+                .map(|s| s.phoneme)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(concat(ReversalStrategy::Segment), vec!["i", "m", "a", "k"]);
+        assert_eq!(concat(ReversalStrategy::Mora), vec!["m", "i", "k", "a"]);
+    }
+
+    #[test]
+    fn test_concatenative_uses_flipped_diphthongs() {
+        let db = PhonemeDatabase::new().for_language(crate::synth::Language::English);
+        let phonemes = sequence(&db, &["m", "aɪ"]);
+        let original: Vec<String> = phonemes.iter().map(|p| p.ipa.clone()).collect();
+        let reversed: Vec<String> =
+            reverse(&phonemes, ReversalStrategy::Segment, DiphthongPolicy::Flip)
+                .into_iter()
+                .map(|p| p.ipa)
+                .collect();
+
+        let segmented = ReversalMode::Concatenative
+            .render(&original, &reversed, Duration::ZERO, fake_render)
+            .unwrap();
+        let phonemes: Vec<&str> = segmented
+            .segments
+            .iter()
+            .map(|s| s.phoneme.as_str())
+            .collect();
+        assert_eq!(phonemes, vec!["ɪa", "m"]);
+    }
+}
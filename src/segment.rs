@@ -0,0 +1,275 @@
+use crate::audio::{trim_silence, AudioData};
+use crate::error::{PhonemeReverserError, Result};
+use std::time::Duration;
+
+/// Default overlap between concatenated segments.
+pub const DEFAULT_CROSSFADE: Duration = Duration::from_millis(10);
+
+/// Longest crossfade accepted by the TUI and the CLI.
+pub const MAX_CROSSFADE: Duration = Duration::from_millis(100);
+
+// 単独で合成した音素の前後の無音を切り詰めるしきい値
+const SILENCE_THRESHOLD: f32 = 0.01;
+
+/// Where one phoneme lies in an [`AudioData`], in frames.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub start: usize,
+    pub end: usize,
+    pub phoneme: String,
+}
+
+impl Segment {
+    pub fn contains(&self, frame: usize) -> bool {
+        (self.start..self.end).contains(&frame)
+    }
+}
+
+/// Audio with the boundaries of the phonemes it contains.
+///
+/// Audio rendered as a whole (e.g. one espeak call) has no segments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentedAudio {
+    pub audio: AudioData,
+    pub segments: Vec<Segment>,
+}
+
+impl From<AudioData> for SegmentedAudio {
+    fn from(audio: AudioData) -> Self {
+        Self {
+            audio,
+            segments: Vec::new(),
+        }
+    }
+}
+
+impl SegmentedAudio {
+    /// Render each phoneme on its own with `render`, trim the silence around
+    /// it and join the clips in order, crossfading by `crossfade`.
+    pub fn synthesize_each<F>(ipa: &[String], crossfade: Duration, mut render: F) -> Result<Self>
+    where
+        F: FnMut(&[String]) -> Result<AudioData>,
+    {
+        let mut builder = AudioBuilder::new().with_crossfade(crossfade);
+        for phoneme in ipa {
+            let clip = render(std::slice::from_ref(phoneme))?;
+            builder.push(phoneme, &trim_silence(&clip, SILENCE_THRESHOLD))?;
+        }
+        builder.build()
+    }
+
+    /// Samples of segment `index`.
+    pub fn slice(&self, index: usize) -> AudioData {
+        let channels = usize::from(self.audio.channels.max(1));
+        let segment = &self.segments[index];
+        AudioData {
+            samples: self.audio.samples[segment.start * channels..segment.end * channels].to_vec(),
+            sample_rate: self.audio.sample_rate,
+            channels: self.audio.channels,
+        }
+    }
+
+    /// Re-join the segments in reverse order, crossfading by `crossfade`.
+    pub fn reversed(&self, crossfade: Duration) -> Result<Self> {
+        let mut builder = AudioBuilder::new().with_crossfade(crossfade);
+        for index in (0..self.segments.len()).rev() {
+            builder.push(&self.segments[index].phoneme, &self.slice(index))?;
+        }
+        builder.build()
+    }
+}
+
+/// Index of the segment playing at `position` of audio at `sample_rate`.
+pub fn segment_at(segments: &[Segment], sample_rate: u32, position: Duration) -> Option<usize> {
+    let frame = (position.as_secs_f64() * f64::from(sample_rate)) as usize;
+    // クロスフェードで重なる部分は後の音素を優先する
+    segments.iter().rposition(|s| s.contains(frame))
+}
+
+/// Concatenates clips and records where each one starts and ends.
+///
+/// Consecutive clips overlap by the crossfade (shortened to fit short clips)
+/// with a linear fade; the segment of a clip includes the overlap.
+#[derive(Debug, Default)]
+pub struct AudioBuilder {
+    audio: Option<AudioData>,
+    segments: Vec<Segment>,
+    crossfade: Duration,
+}
+
+impl AudioBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_crossfade(mut self, crossfade: Duration) -> Self {
+        self.crossfade = crossfade;
+        self
+    }
+
+    /// Append `clip` as the audio of `phoneme`.
+    pub fn push(&mut self, phoneme: &str, clip: &AudioData) -> Result<()> {
+        let audio = self.audio.get_or_insert_with(|| AudioData {
+            samples: Vec::new(),
+            sample_rate: clip.sample_rate,
+            channels: clip.channels,
+        });
+        if (audio.sample_rate, audio.channels) != (clip.sample_rate, clip.channels) {
+            return Err(PhonemeReverserError::Synthesis(format!(
+                "Cannot join {} Hz / {} ch audio with {} Hz / {} ch",
+                clip.sample_rate, clip.channels, audio.sample_rate, audio.channels
+            )));
+        }
+
+        let channels = usize::from(audio.channels.max(1));
+        let frames = audio.samples.len() / channels;
+        let clip_frames = clip.samples.len() / channels;
+        let fade_frames = (self.crossfade.as_secs_f64() * f64::from(audio.sample_rate)) as usize;
+        let fade = fade_frames.min(frames).min(clip_frames);
+
+        let start = frames - fade;
+        for frame in 0..fade {
+            let gain = (frame + 1) as f32 / (fade + 1) as f32;
+            for channel in 0..channels {
+                let old = &mut audio.samples[(start + frame) * channels + channel];
+                let new = clip.samples[frame * channels + channel];
+                *old = *old * (1.0 - gain) + new * gain;
+            }
+        }
+        audio
+            .samples
+            .extend_from_slice(&clip.samples[fade * channels..]);
+
+        self.segments.push(Segment {
+            start,
+            end: start + clip_frames,
+            phoneme: phoneme.to_string(),
+        });
+        Ok(())
+    }
+
+    pub fn build(self) -> Result<SegmentedAudio> {
+        let audio = self
+            .audio
+            .ok_or_else(|| PhonemeReverserError::Synthesis("No segments to join".to_string()))?;
+        Ok(SegmentedAudio {
+            audio,
+            segments: self.segments,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clip(value: f32, frames: usize) -> AudioData {
+        AudioData {
+            samples: vec![value; frames],
+            sample_rate: 1000,
+            channels: 1,
+        }
+    }
+
+    #[test]
+    fn test_builder_records_boundaries() {
+        let mut builder = AudioBuilder::new();
+        builder.push("k", &clip(0.1, 3)).unwrap();
+        builder.push("a", &clip(0.2, 5)).unwrap();
+        let built = builder.build().unwrap();
+
+        assert_eq!(built.audio.samples.len(), 8);
+        assert_eq!(
+            built.segments,
+            vec![
+                Segment {
+                    start: 0,
+                    end: 3,
+                    phoneme: "k".to_string()
+                },
+                Segment {
+                    start: 3,
+                    end: 8,
+                    phoneme: "a".to_string()
+                },
+            ]
+        );
+        assert_eq!(built.slice(1), clip(0.2, 5));
+    }
+
+    #[test]
+    fn test_crossfade_overlaps_segments() {
+        let mut builder = AudioBuilder::new().with_crossfade(Duration::from_millis(3));
+        builder.push("a", &clip(1.0, 5)).unwrap();
+        builder.push("i", &clip(0.0, 5)).unwrap();
+        let built = builder.build().unwrap();
+
+        // 3フレーム重なり、線形に切り替わる
+        assert_eq!(built.audio.samples.len(), 7);
+        assert_eq!(built.segments[1].start, 2);
+        assert_eq!(built.audio.samples[..2], [1.0, 1.0]);
+        assert_eq!(built.audio.samples[2..5], [0.75, 0.5, 0.25]);
+        assert_eq!(built.segments[1].end, built.audio.samples.len());
+    }
+
+    #[test]
+    fn test_crossfade_is_limited_by_short_clips() {
+        let mut builder = AudioBuilder::new().with_crossfade(Duration::from_millis(50));
+        builder.push("a", &clip(1.0, 4)).unwrap();
+        builder.push("t", &clip(0.0, 2)).unwrap();
+        let built = builder.build().unwrap();
+        assert_eq!(built.audio.samples.len(), 4);
+        assert_eq!(built.segments[1].start, 2);
+    }
+
+    #[test]
+    fn test_reversed_keeps_stereo_frames() {
+        let stereo = |left: f32, right: f32, frames: usize| AudioData {
+            samples: [left, right].repeat(frames),
+            sample_rate: 1000,
+            channels: 2,
+        };
+        let mut builder = AudioBuilder::new();
+        builder.push("k", &stereo(0.1, -0.1, 2)).unwrap();
+        builder.push("a", &stereo(0.2, -0.2, 3)).unwrap();
+
+        let reversed = builder.build().unwrap().reversed(Duration::ZERO).unwrap();
+        let phonemes: Vec<&str> = reversed
+            .segments
+            .iter()
+            .map(|s| s.phoneme.as_str())
+            .collect();
+        assert_eq!(phonemes, vec!["a", "k"]);
+        // 音素の中身は反転しない
+        assert_eq!(reversed.slice(0), stereo(0.2, -0.2, 3));
+        assert_eq!(reversed.slice(1), stereo(0.1, -0.1, 2));
+    }
+
+    #[test]
+    fn test_synthesize_each_trims_and_finds_segments() {
+        let ipa = vec!["k".to_string(), "a".to_string()];
+        let padded = |ipa: &[String]| {
+            let frames = if ipa[0] == "k" { 2 } else { 4 };
+            let mut samples = vec![0.0; 3];
+            samples.extend(vec![0.5; frames]);
+            samples.extend(vec![0.0; 3]);
+            Ok(AudioData {
+                samples,
+                sample_rate: 1000,
+                channels: 1,
+            })
+        };
+
+        let segmented = SegmentedAudio::synthesize_each(&ipa, Duration::ZERO, padded).unwrap();
+        assert_eq!(segmented.audio.samples, vec![0.5; 6]);
+        let at = |ms| segment_at(&segmented.segments, 1000, Duration::from_millis(ms));
+        assert_eq!(at(1), Some(0));
+        assert_eq!(at(4), Some(1));
+        assert_eq!(at(9), None);
+    }
+
+    #[test]
+    fn test_empty_builder_is_an_error() {
+        assert!(AudioBuilder::new().build().is_err());
+    }
+}
@@ -0,0 +1,411 @@
+use super::{SynthConfig, Synthesizer};
+use crate::audio::{read_wav, write_wav_float, AudioData};
+use crate::error::Result;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Hit and miss counters of a [`CachedSynthesizer`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Served from memory.
+    pub hits: u64,
+    /// Served from the disk tier (and promoted to memory).
+    pub disk_hits: u64,
+    /// Rendered by the wrapped backend.
+    pub misses: u64,
+    /// Dropped from memory to stay within capacity.
+    pub evictions: u64,
+    /// Entries currently held in memory.
+    pub entries: usize,
+}
+
+impl CacheStats {
+    /// Share of requests that did not reach the backend.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.disk_hits + self.misses;
+        if total == 0 {
+            return 0.0;
+        }
+        (self.hits + self.disk_hits) as f64 / total as f64
+    }
+}
+
+/// Content address of a rendering: the backend, the eSpeak phonemes and
+/// every `SynthConfig` field.
+///
+/// Entries are looked up by the hash of the canonical string, and the string
+/// itself is compared on every hit so that a hash collision is a miss rather
+/// than audio for other phonemes or settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheKey {
+    hash: u64,
+    canonical: String,
+}
+
+impl CacheKey {
+    pub fn new(backend: &str, espeak_phonemes: &str, config: &SynthConfig) -> Self {
+        let canonical = format!(
+            "{}\0{}\0{}\0{}\0{}\0{}\0{}\0{}",
+            backend,
+            espeak_phonemes,
+            config.language.to_espeak_code(),
+            config.speed,
+            config.pitch,
+            config.amplitude,
+            config.word_gap,
+            config.variant.as_deref().unwrap_or("")
+        );
+        Self {
+            hash: fnv1a(canonical.as_bytes()),
+            canonical,
+        }
+    }
+
+    fn file_name(&self) -> String {
+        format!("{:016x}.wav", self.hash)
+    }
+
+    // 音声の隣に置く正規化文字列のファイル（ディスクから読むときに照合する）
+    fn key_file_name(&self) -> String {
+        format!("{:016x}.key", self.hash)
+    }
+}
+
+// FNV-1a (64bit)。ディスク上のファイル名に使うので Rust のバージョンに依存しないハッシュにする
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[derive(Default)]
+struct Lru {
+    // ハッシュ → (正規化文字列, 音声)。衝突した別のキーは上書きする
+    entries: HashMap<u64, (String, AudioData)>,
+    // 先頭が最も古く使われたもの
+    order: VecDeque<u64>,
+    stats: CacheStats,
+}
+
+impl Lru {
+    fn get(&mut self, key: &CacheKey) -> Option<AudioData> {
+        let (canonical, audio) = self.entries.get(&key.hash)?;
+        if *canonical != key.canonical {
+            return None;
+        }
+        let audio = audio.clone();
+        self.touch(key.hash);
+        Some(audio)
+    }
+
+    fn insert(&mut self, key: CacheKey, audio: AudioData, capacity: usize) {
+        let hash = key.hash;
+        if self.entries.insert(hash, (key.canonical, audio)).is_some() {
+            self.touch(hash);
+            return;
+        }
+        self.order.push_back(hash);
+        while self.order.len() > capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+                self.stats.evictions += 1;
+            }
+        }
+    }
+
+    fn touch(&mut self, hash: u64) {
+        if let Some(position) = self.order.iter().position(|h| *h == hash) {
+            self.order.remove(position);
+        }
+        self.order.push_back(hash);
+    }
+}
+
+/// Wraps a backend and reuses renderings of the same phonemes and settings.
+///
+/// Results are kept in an in-memory LRU of `capacity` entries and, with
+/// [`with_disk_cache`](Self::with_disk_cache), as float WAV files named by
+/// the hash of their [`CacheKey`] (with the key itself in a `.key` file next
+/// to them) so that later runs can reuse them too. Errors are not cached.
+pub struct CachedSynthesizer {
+    inner: Box<dyn Synthesizer>,
+    capacity: usize,
+    disk_dir: Option<PathBuf>,
+    memory: Mutex<Lru>,
+}
+
+impl CachedSynthesizer {
+    pub const DEFAULT_CAPACITY: usize = 64;
+
+    pub fn new(inner: Box<dyn Synthesizer>, capacity: usize) -> Self {
+        Self {
+            inner,
+            capacity: capacity.max(1),
+            disk_dir: None,
+            memory: Mutex::new(Lru::default()),
+        }
+    }
+
+    /// Also store renderings in `dir` (created if missing).
+    pub fn with_disk_cache(mut self, dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        self.disk_dir = Some(dir.to_path_buf());
+        Ok(self)
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let memory = self.lock();
+        CacheStats {
+            entries: memory.entries.len(),
+            ..memory.stats
+        }
+    }
+
+    /// Drop the in-memory entries (the disk tier is kept).
+    pub fn clear(&self) {
+        let mut memory = self.lock();
+        memory.entries.clear();
+        memory.order.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Lru> {
+        self.memory.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // 壊れたファイルや書き込み失敗は合成し直せばよいので無視する。
+    // キーのファイルが無い・一致しない（ハッシュの衝突）ときも読み込まない
+    fn read_disk(&self, key: &CacheKey) -> Option<AudioData> {
+        let dir = self.disk_dir.as_ref()?;
+        let canonical = std::fs::read_to_string(dir.join(key.key_file_name())).ok()?;
+        if canonical != key.canonical {
+            return None;
+        }
+        read_wav(&dir.join(key.file_name())).ok()
+    }
+
+    fn write_disk(&self, key: &CacheKey, audio: &AudioData) {
+        if let Some(dir) = &self.disk_dir {
+            if write_wav_float(&dir.join(key.file_name()), audio).is_ok() {
+                let _ = std::fs::write(dir.join(key.key_file_name()), &key.canonical);
+            }
+        }
+    }
+}
+
+impl Synthesizer for CachedSynthesizer {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn cache_id(&self) -> String {
+        self.inner.cache_id()
+    }
+
+    fn synthesize(&self, espeak_phonemes: &str, config: &SynthConfig) -> Result<AudioData> {
+        let key = CacheKey::new(&self.inner.cache_id(), espeak_phonemes, config);
+
+        {
+            let mut memory = self.lock();
+            if let Some(audio) = memory.get(&key) {
+                memory.stats.hits += 1;
+                return Ok(audio);
+            }
+        }
+
+        if let Some(audio) = self.read_disk(&key) {
+            let mut memory = self.lock();
+            memory.stats.disk_hits += 1;
+            memory.insert(key, audio.clone(), self.capacity);
+            return Ok(audio);
+        }
+
+        // 合成中はロックを持たない（他のスレッドからの参照を妨げない）
+        let audio = self.inner.synthesize(espeak_phonemes, config)?;
+        self.write_disk(&key, &audio);
+
+        let mut memory = self.lock();
+        memory.stats.misses += 1;
+        memory.insert(key, audio.clone(), self.capacity);
+        Ok(audio)
+    }
+
+    fn cache_stats(&self) -> Option<CacheStats> {
+        Some(self.stats())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::synth::TestSynthesizer;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Counts how often the wrapped backend actually renders.
+    struct Counting(Arc<AtomicUsize>);
+
+    impl Synthesizer for Counting {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        fn synthesize(&self, espeak_phonemes: &str, config: &SynthConfig) -> Result<AudioData> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            TestSynthesizer.synthesize(espeak_phonemes, config)
+        }
+    }
+
+    fn counting(capacity: usize) -> (CachedSynthesizer, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let synth = CachedSynthesizer::new(Box::new(Counting(calls.clone())), capacity);
+        (synth, calls)
+    }
+
+    #[test]
+    fn test_repeated_requests_hit_memory() {
+        let (synth, calls) = counting(4);
+        let config = SynthConfig::default();
+
+        let first = synth.synthesize("aka", &config).unwrap();
+        let second = synth.synthesize("aka", &config).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let stats = synth.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+        assert_eq!(stats.hit_rate(), 0.5);
+    }
+
+    #[test]
+    fn test_key_covers_every_setting() {
+        let base = SynthConfig::default();
+        let key = |config: &SynthConfig| CacheKey::new("test", "aka", config);
+        let variants = [
+            SynthConfig {
+                speed: 200,
+                ..base.clone()
+            },
+            SynthConfig {
+                pitch: 60,
+                ..base.clone()
+            },
+            SynthConfig {
+                amplitude: 90,
+                ..base.clone()
+            },
+            SynthConfig {
+                word_gap: 1,
+                ..base.clone()
+            },
+            SynthConfig {
+                variant: Some("f1".to_string()),
+                ..base.clone()
+            },
+        ];
+        for config in &variants {
+            assert_ne!(key(config), key(&base), "{:?}", config);
+        }
+        assert_ne!(
+            CacheKey::new("formant@16000", "aka", &base),
+            CacheKey::new("formant@22050", "aka", &base)
+        );
+        assert_eq!(key(&base), key(&base.clone()));
+    }
+
+    #[test]
+    fn test_least_recently_used_entry_is_evicted() {
+        let (synth, calls) = counting(2);
+        let config = SynthConfig::default();
+
+        synth.synthesize("a", &config).unwrap();
+        synth.synthesize("i", &config).unwrap();
+        // a を使ったので次に追い出されるのは i
+        synth.synthesize("a", &config).unwrap();
+        synth.synthesize("u", &config).unwrap();
+        assert_eq!(synth.stats().evictions, 1);
+
+        synth.synthesize("a", &config).unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        synth.synthesize("i", &config).unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_disk_tier_survives_new_instances() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SynthConfig::default();
+
+        let (synth, _) = counting(4);
+        let synth = synth.with_disk_cache(dir.path()).unwrap();
+        let rendered = synth.synthesize("aka", &config).unwrap();
+
+        let (synth, calls) = counting(4);
+        let synth = synth.with_disk_cache(dir.path()).unwrap();
+        assert_eq!(synth.synthesize("aka", &config).unwrap(), rendered);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(synth.stats().disk_hits, 1);
+
+        // メモリに昇格している
+        synth.synthesize("aka", &config).unwrap();
+        assert_eq!(synth.stats().hits, 1);
+    }
+
+    #[test]
+    fn test_hash_collisions_are_misses() {
+        let key = CacheKey::new("test", "aka", &SynthConfig::default());
+        let colliding = CacheKey {
+            hash: key.hash,
+            canonical: "test\0aki".to_string(),
+        };
+        let audio = TestSynthesizer
+            .synthesize("aka", &SynthConfig::default())
+            .unwrap();
+
+        let mut lru = Lru::default();
+        lru.insert(key.clone(), audio.clone(), 4);
+        assert_eq!(lru.get(&key), Some(audio));
+        assert_eq!(lru.get(&colliding), None);
+    }
+
+    #[test]
+    fn test_disk_entries_are_checked_against_the_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SynthConfig::default();
+        let key = CacheKey::new("counting", "aka", &config);
+
+        let (synth, _) = counting(4);
+        let synth = synth.with_disk_cache(dir.path()).unwrap();
+        synth.synthesize("aka", &config).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(key.key_file_name())).unwrap(),
+            key.canonical
+        );
+
+        // 同じハッシュの別のキーが書かれていれば（衝突）ディスクのファイルは使わない
+        std::fs::write(dir.path().join(key.key_file_name()), "counting\0aki").unwrap();
+        let (synth, calls) = counting(4);
+        let synth = synth.with_disk_cache(dir.path()).unwrap();
+        synth.synthesize("aka", &config).unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(synth.stats().disk_hits, 0);
+
+        // キーのファイルが無い音声も使わない
+        std::fs::remove_file(dir.path().join(key.key_file_name())).unwrap();
+        let (synth, calls) = counting(4);
+        let synth = synth.with_disk_cache(dir.path()).unwrap();
+        synth.synthesize("aka", &config).unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_errors_are_not_cached() {
+        let synth =
+            CachedSynthesizer::new(Box::new(crate::synth::FormantSynthesizer::default()), 4);
+        let config = SynthConfig::default();
+        assert!(synth.synthesize("a%a", &config).is_err());
+        assert!(synth.synthesize("a%a", &config).is_err());
+        assert_eq!(synth.stats().misses, 0);
+        assert_eq!(synth.stats().entries, 0);
+    }
+}
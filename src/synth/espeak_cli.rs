@@ -0,0 +1,216 @@
+use super::{SynthConfig, Synthesizer};
+use super::Language;
+use crate::audio::{read_wav, AudioData};
+use crate::error::{PhonemeReverserError, Result};
+use std::ffi::OsStr;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::NamedTempFile;
+
+/// Runs the `espeak-ng` executable and reads back the WAV it writes.
+#[derive(Debug, Default)]
+pub struct EspeakCli;
+
+impl Synthesizer for EspeakCli {
+    fn name(&self) -> &'static str {
+        "espeak-ng"
+    }
+
+    fn synthesize(&self, espeak_phonemes: &str, config: &SynthConfig) -> Result<AudioData> {
+        let temp_file = NamedTempFile::new()?;
+        synthesize_phonemes(espeak_phonemes, temp_file.path(), config)?;
+        read_wav(temp_file.path())
+    }
+}
+
+pub fn synthesize_phonemes(
+    espeak_phonemes: &str,
+    output_path: &Path,
+    config: &SynthConfig,
+) -> Result<()> {
+    // Build espeak-ng command
+    // espeak-ng -v <lang>[+variant] -s <speed> ... "[[phonemes]]" -w output.wav
+    let phoneme_input = format!("[[{}]]", espeak_phonemes);
+    let voice_args = voice_args(config);
+
+    let mut args: Vec<&OsStr> = voice_args.iter().map(OsStr::new).collect();
+    args.extend([
+        OsStr::new(&phoneme_input),
+        OsStr::new("-w"),
+        output_path.as_os_str(),
+    ]);
+    run_espeak(&args)?;
+
+    // Verify output file was created
+    if !output_path.exists() {
+        return Err(PhonemeReverserError::Synthesis(
+            "espeak-ng did not create output file".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+// 声と合成パラメータの引数（-v, -s, -p, -a, -g）
+fn voice_args(config: &SynthConfig) -> Vec<String> {
+    vec![
+        "-v".to_string(),
+        config.voice_name(),
+        "-s".to_string(),
+        config.speed.to_string(),
+        "-p".to_string(),
+        config.pitch.to_string(),
+        "-a".to_string(),
+        config.amplitude.to_string(),
+        "-g".to_string(),
+        config.word_gap.to_string(),
+    ]
+}
+
+/// Convert text into IPA with espeak-ng's own phonemizer.
+///
+/// Runs `espeak-ng -q -x --ipa`; the output keeps espeak's stress marks and
+/// word spacing, one line per clause.
+pub fn phonemize(text: &str, language: Language) -> Result<String> {
+    let output = run_espeak(&[
+        OsStr::new("-q"),
+        OsStr::new("-x"),
+        OsStr::new("--ipa"),
+        OsStr::new("-v"),
+        OsStr::new(language.to_espeak_code()),
+        OsStr::new(text),
+    ])?;
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// espeak-ng を実行し、起動失敗・異常終了をエラーにする
+fn run_espeak(args: &[&OsStr]) -> Result<Output> {
+    let output = Command::new("espeak-ng")
+        .args(args)
+        .output()
+        .map_err(|e| PhonemeReverserError::Synthesis(format!(
+            "Failed to execute espeak-ng: {}. Is espeak-ng installed?",
+            e
+        )))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(PhonemeReverserError::Synthesis(format!(
+            "espeak-ng failed with exit code {:?}: {}",
+            output.status.code(),
+            stderr
+        )));
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::synth::Language;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_synthesize_simple_phonemes() {
+        // Test basic espeak phoneme synthesis
+        let espeak_phonemes = "h @ l oU";  // "hello" in espeak notation
+        let temp_dir = tempdir().unwrap();
+        let temp_path = temp_dir.path().join("synth_test.wav");
+
+        let result = synthesize_phonemes(espeak_phonemes, &temp_path, &SynthConfig::default());
+
+        if let Err(e) = &result {
+            eprintln!("Synthesis error: {:?}", e);
+        }
+
+        assert!(result.is_ok(), "Failed to synthesize phonemes");
+        assert!(temp_path.exists(), "Output WAV file was not created");
+
+        // Verify the generated WAV is valid
+        let audio = read_wav(&temp_path).unwrap();
+        assert!(!audio.samples.is_empty(), "Generated WAV has no samples");
+
+        println!("Synthesized {} samples", audio.samples.len());
+    }
+
+    #[test]
+    fn test_synthesize_with_japanese() {
+        // Test Japanese synthesis
+        let espeak_phonemes = "a i u e o";  // Japanese vowels
+        let temp_dir = tempdir().unwrap();
+        let temp_path = temp_dir.path().join("synth_ja_test.wav");
+
+        let config = SynthConfig {
+            language: Language::Japanese,
+            ..SynthConfig::default()
+        };
+
+        let result = synthesize_phonemes(espeak_phonemes, &temp_path, &config);
+
+        if let Err(e) = &result {
+            eprintln!("Synthesis error: {:?}", e);
+        }
+
+        assert!(result.is_ok(), "Failed to synthesize Japanese phonemes");
+        assert!(temp_path.exists(), "Output WAV file was not created");
+
+        let audio = read_wav(&temp_path).unwrap();
+        assert!(!audio.samples.is_empty(), "Generated WAV has no samples");
+    }
+
+    #[test]
+    fn test_synthesize_with_english() {
+        // Test English synthesis
+        let espeak_phonemes = "h @ l oU";
+        let temp_dir = tempdir().unwrap();
+        let temp_path = temp_dir.path().join("synth_en_test.wav");
+
+        let config = SynthConfig {
+            language: Language::English,
+            ..SynthConfig::default()
+        };
+
+        let result = synthesize_phonemes(espeak_phonemes, &temp_path, &config);
+
+        if let Err(e) = &result {
+            eprintln!("Synthesis error: {:?}", e);
+        }
+
+        assert!(result.is_ok(), "Failed to synthesize English phonemes");
+        assert!(temp_path.exists(), "Output WAV file was not created");
+
+        let audio = read_wav(&temp_path).unwrap();
+        assert!(!audio.samples.is_empty(), "Generated WAV has no samples");
+    }
+
+    #[test]
+    fn test_voice_args_pass_every_setting() {
+        let config = SynthConfig {
+            language: Language::English,
+            speed: 120,
+            pitch: 70,
+            amplitude: 150,
+            word_gap: 5,
+            variant: Some("f3".to_string()),
+        };
+        assert_eq!(
+            voice_args(&config),
+            vec!["-v", "en-us+f3", "-s", "120", "-p", "70", "-a", "150", "-g", "5"]
+        );
+    }
+
+    #[test]
+    fn test_phonemize_english() {
+        let ipa = phonemize("hello", Language::English).unwrap();
+        assert!(ipa.contains('h'), "Unexpected IPA: {}", ipa);
+    }
+
+    #[test]
+    fn test_espeak_cli_synthesizer() {
+        let audio = EspeakCli.synthesize("a i u e o", &SynthConfig::default()).unwrap();
+        assert!(!audio.samples.is_empty());
+        assert_eq!(audio.channels, 1);
+    }
+}
@@ -0,0 +1,182 @@
+use super::{SynthConfig, Synthesizer};
+use crate::audio::AudioData;
+use crate::error::{PhonemeReverserError, Result};
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, c_short, c_uint, c_void, CString};
+use std::sync::{Mutex, OnceLock};
+
+// speak_lib.h の定数
+const AUDIO_OUTPUT_SYNCHRONOUS: c_int = 2;
+const ESPEAK_INITIALIZE_DONT_EXIT: c_int = 0x8000;
+const ESPEAK_CHARS_UTF8: c_uint = 1;
+const ESPEAK_PHONEMES: c_uint = 0x100;
+const POS_CHARACTER: c_int = 1;
+const ESPEAK_RATE: c_int = 1;
+const ESPEAK_VOLUME: c_int = 2;
+const ESPEAK_PITCH: c_int = 3;
+const ESPEAK_WORDGAP: c_int = 7;
+const EE_OK: c_int = 0;
+
+type SynthCallback =
+    unsafe extern "C" fn(wav: *mut c_short, numsamples: c_int, events: *mut c_void) -> c_int;
+
+#[link(name = "espeak-ng")]
+extern "C" {
+    fn espeak_Initialize(
+        output: c_int,
+        buflength: c_int,
+        path: *const c_char,
+        options: c_int,
+    ) -> c_int;
+    fn espeak_SetSynthCallback(callback: SynthCallback);
+    fn espeak_SetVoiceByName(name: *const c_char) -> c_int;
+    fn espeak_SetParameter(parameter: c_int, value: c_int, relative: c_int) -> c_int;
+    fn espeak_Synth(
+        text: *const c_void,
+        size: usize,
+        position: c_uint,
+        position_type: c_int,
+        end_position: c_uint,
+        flags: c_uint,
+        unique_identifier: *mut c_uint,
+        user_data: *mut c_void,
+    ) -> c_int;
+    fn espeak_Synchronize() -> c_int;
+}
+
+// libespeak-ng はプロセス内で単一の状態を持つため、初期化は一度だけ行い合成呼び出しは直列化する
+static SAMPLE_RATE: OnceLock<std::result::Result<u32, String>> = OnceLock::new();
+static SYNTH_LOCK: Mutex<()> = Mutex::new(());
+
+thread_local! {
+    // 同期モードのコールバックは espeak_Synth を呼んだスレッド上で実行される
+    static BUFFER: RefCell<Vec<i16>> = const { RefCell::new(Vec::new()) };
+}
+
+unsafe extern "C" fn collect_samples(
+    wav: *mut c_short,
+    numsamples: c_int,
+    _events: *mut c_void,
+) -> c_int {
+    if !wav.is_null() && numsamples > 0 {
+        let chunk = std::slice::from_raw_parts(wav, numsamples as usize);
+        BUFFER.with(|buffer| buffer.borrow_mut().extend_from_slice(chunk));
+    }
+    0
+}
+
+fn initialize() -> Result<u32> {
+    SAMPLE_RATE
+        .get_or_init(|| {
+            let rate = unsafe {
+                espeak_Initialize(
+                    AUDIO_OUTPUT_SYNCHRONOUS,
+                    0,
+                    std::ptr::null(),
+                    ESPEAK_INITIALIZE_DONT_EXIT,
+                )
+            };
+            if rate <= 0 {
+                return Err(format!("espeak_Initialize failed with {}", rate));
+            }
+            unsafe { espeak_SetSynthCallback(collect_samples) };
+            Ok(rate as u32)
+        })
+        .clone()
+        .map_err(PhonemeReverserError::Synthesis)
+}
+
+/// Links against libespeak-ng and renders in-process.
+#[derive(Debug)]
+pub struct EspeakLib {
+    sample_rate: u32,
+}
+
+impl EspeakLib {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            sample_rate: initialize()?,
+        })
+    }
+}
+
+impl Synthesizer for EspeakLib {
+    fn name(&self) -> &'static str {
+        "libespeak-ng"
+    }
+
+    fn synthesize(&self, espeak_phonemes: &str, config: &SynthConfig) -> Result<AudioData> {
+        let _guard = SYNTH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        let voice = CString::new(config.voice_name())
+            .map_err(|e| PhonemeReverserError::Synthesis(format!("Invalid voice name: {}", e)))?;
+        let text = CString::new(format!("[[{}]]", espeak_phonemes)).map_err(|e| {
+            PhonemeReverserError::Synthesis(format!("Invalid phoneme string: {}", e))
+        })?;
+
+        BUFFER.with(|buffer| buffer.borrow_mut().clear());
+
+        let status = unsafe {
+            if espeak_SetVoiceByName(voice.as_ptr()) != EE_OK {
+                return Err(PhonemeReverserError::Synthesis(format!(
+                    "libespeak-ng has no voice '{}'",
+                    config.voice_name()
+                )));
+            }
+            espeak_SetParameter(ESPEAK_RATE, config.speed as c_int, 0);
+            espeak_SetParameter(ESPEAK_PITCH, config.pitch as c_int, 0);
+            espeak_SetParameter(ESPEAK_VOLUME, config.amplitude as c_int, 0);
+            espeak_SetParameter(ESPEAK_WORDGAP, config.word_gap as c_int, 0);
+
+            let bytes = text.as_bytes_with_nul();
+            let status = espeak_Synth(
+                bytes.as_ptr() as *const c_void,
+                bytes.len(),
+                0,
+                POS_CHARACTER,
+                0,
+                ESPEAK_CHARS_UTF8 | ESPEAK_PHONEMES,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            );
+            espeak_Synchronize();
+            status
+        };
+
+        if status != EE_OK {
+            return Err(PhonemeReverserError::Synthesis(format!(
+                "espeak_Synth failed with {}",
+                status
+            )));
+        }
+
+        let samples = BUFFER.with(|buffer| {
+            buffer
+                .borrow_mut()
+                .drain(..)
+                .map(|s| s as f32 / i16::MAX as f32)
+                .collect()
+        });
+
+        Ok(AudioData {
+            samples,
+            sample_rate: self.sample_rate,
+            channels: 1,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_espeak_lib_synthesize() {
+        let synth = EspeakLib::new().unwrap();
+        let audio = synth
+            .synthesize("a i u e o", &SynthConfig::default())
+            .unwrap();
+        assert!(!audio.samples.is_empty());
+        assert_eq!(audio.channels, 1);
+    }
+}
@@ -0,0 +1,780 @@
+use super::{SynthConfig, Synthesizer};
+use crate::audio::AudioData;
+use crate::error::{PhonemeReverserError, Result};
+use std::f32::consts::PI;
+
+// 声道フィルタの帯域幅 (Hz)
+const FORMANT_BANDWIDTHS: [f32; 3] = [60.0, 90.0, 150.0];
+// 先行音素のフォルマントから目標値へ移行する区間の割合
+const TRANSITION: f32 = 0.35;
+// 音源振幅の平滑化時定数（クリック防止）
+const GAIN_SMOOTHING_SECS: f32 = 0.004;
+// フィルタ係数の更新間隔（サンプル）
+const COEFF_UPDATE_INTERVAL: usize = 32;
+const PAUSE_MS: f32 = 60.0;
+const LENGTH_MARK_FACTOR: f32 = 1.8;
+
+#[derive(Debug, Clone, Copy)]
+enum Manner {
+    Vowel,
+    /// Glides from `formants` to the given targets.
+    Diphthong([f32; 3]),
+    Nasal,
+    Approximant,
+    /// Glottal frication shaped by the vocal tract (h).
+    Aspirate,
+    Fricative {
+        center: f32,
+        gain: f32,
+    },
+    Plosive {
+        burst: f32,
+    },
+    Affricate {
+        center: f32,
+    },
+    Silence,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PhonemeSpec {
+    espeak: &'static str,
+    manner: Manner,
+    voiced: bool,
+    /// F1-F3 targets; for consonants these are the transition loci.
+    formants: [f32; 3],
+    duration_ms: f32,
+}
+
+const fn spec(
+    espeak: &'static str,
+    manner: Manner,
+    voiced: bool,
+    formants: [f32; 3],
+    duration_ms: f32,
+) -> PhonemeSpec {
+    PhonemeSpec {
+        espeak,
+        manner,
+        voiced,
+        formants,
+        duration_ms,
+    }
+}
+
+use Manner::*;
+
+#[rustfmt::skip]
+const PHONEMES: &[PhonemeSpec] = &[
+    // 母音
+    spec("a", Vowel, true, [800.0, 1300.0, 2600.0], 120.0),
+    spec("i", Vowel, true, [300.0, 2300.0, 3000.0], 110.0),
+    spec("u", Vowel, true, [350.0, 1300.0, 2400.0], 110.0),
+    spec("e", Vowel, true, [480.0, 2000.0, 2700.0], 120.0),
+    spec("o", Vowel, true, [500.0, 900.0, 2500.0], 120.0),
+    spec("@", Vowel, true, [500.0, 1500.0, 2500.0], 80.0),
+    spec("A", Vowel, true, [750.0, 1100.0, 2550.0], 130.0),
+    spec("O", Vowel, true, [570.0, 850.0, 2450.0], 130.0),
+    spec("I", Vowel, true, [400.0, 2000.0, 2550.0], 90.0),
+    spec("U", Vowel, true, [440.0, 1000.0, 2250.0], 90.0),
+    spec("E", Vowel, true, [580.0, 1800.0, 2550.0], 110.0),
+    spec("V", Vowel, true, [640.0, 1200.0, 2400.0], 100.0),
+    spec("0", Vowel, true, [600.0, 900.0, 2500.0], 110.0),
+    spec("3", Vowel, true, [500.0, 1400.0, 1700.0], 130.0),
+    spec("a#", Vowel, true, [650.0, 1400.0, 2500.0], 90.0),
+    spec("u-", Vowel, true, [350.0, 1400.0, 2400.0], 100.0),
+    // 二重母音
+    spec("aI", Diphthong([400.0, 2000.0, 2550.0]), true, [800.0, 1300.0, 2600.0], 200.0),
+    spec("eI", Diphthong([400.0, 2000.0, 2550.0]), true, [480.0, 2000.0, 2700.0], 190.0),
+    spec("oU", Diphthong([440.0, 1000.0, 2250.0]), true, [500.0, 900.0, 2500.0], 190.0),
+    spec("aU", Diphthong([440.0, 1000.0, 2250.0]), true, [800.0, 1300.0, 2600.0], 200.0),
+    spec("OI", Diphthong([400.0, 2000.0, 2550.0]), true, [570.0, 850.0, 2450.0], 210.0),
+    // 鼻音
+    spec("m", Nasal, true, [250.0, 1100.0, 2300.0], 70.0),
+    spec("n", Nasal, true, [250.0, 1600.0, 2600.0], 70.0),
+    spec("N", Nasal, true, [250.0, 2000.0, 2700.0], 80.0),
+    spec("n^", Nasal, true, [250.0, 2100.0, 2800.0], 75.0),
+    // 接近音・流音
+    spec("l", Approximant, true, [360.0, 1300.0, 2700.0], 60.0),
+    spec("r", Approximant, true, [420.0, 1300.0, 1600.0], 60.0),
+    spec("*", Approximant, true, [400.0, 1500.0, 2500.0], 30.0),
+    spec("w", Approximant, true, [300.0, 700.0, 2300.0], 60.0),
+    spec("j", Approximant, true, [280.0, 2200.0, 3000.0], 60.0),
+    // 摩擦音
+    spec("h", Aspirate, false, [500.0, 1500.0, 2500.0], 80.0),
+    spec("s", Fricative { center: 5500.0, gain: 0.6 }, false, [400.0, 1700.0, 2600.0], 110.0),
+    spec("z", Fricative { center: 5500.0, gain: 0.4 }, true, [400.0, 1700.0, 2600.0], 90.0),
+    spec("S", Fricative { center: 3000.0, gain: 0.7 }, false, [400.0, 1900.0, 2500.0], 110.0),
+    spec("Z", Fricative { center: 3000.0, gain: 0.5 }, true, [400.0, 1900.0, 2500.0], 90.0),
+    spec("S;", Fricative { center: 3500.0, gain: 0.7 }, false, [350.0, 2100.0, 2800.0], 110.0),
+    spec("Z;", Fricative { center: 3500.0, gain: 0.5 }, true, [350.0, 2100.0, 2800.0], 90.0),
+    spec("f", Fricative { center: 6000.0, gain: 0.25 }, false, [400.0, 1100.0, 2300.0], 100.0),
+    spec("v", Fricative { center: 6000.0, gain: 0.15 }, true, [400.0, 1100.0, 2300.0], 80.0),
+    spec("T", Fricative { center: 6500.0, gain: 0.2 }, false, [400.0, 1400.0, 2600.0], 100.0),
+    spec("D", Fricative { center: 6500.0, gain: 0.12 }, true, [400.0, 1400.0, 2600.0], 70.0),
+    spec("F", Fricative { center: 1500.0, gain: 0.25 }, false, [400.0, 900.0, 2300.0], 90.0),
+    spec("C", Fricative { center: 4000.0, gain: 0.5 }, false, [300.0, 2200.0, 3000.0], 100.0),
+    spec("x", Fricative { center: 1800.0, gain: 0.4 }, false, [400.0, 1500.0, 2400.0], 100.0),
+    // 破裂音
+    spec("p", Plosive { burst: 800.0 }, false, [400.0, 1000.0, 2300.0], 90.0),
+    spec("b", Plosive { burst: 800.0 }, true, [400.0, 1000.0, 2300.0], 80.0),
+    spec("t", Plosive { burst: 4000.0 }, false, [400.0, 1700.0, 2600.0], 90.0),
+    spec("d", Plosive { burst: 4000.0 }, true, [400.0, 1700.0, 2600.0], 80.0),
+    spec("k", Plosive { burst: 1800.0 }, false, [400.0, 1900.0, 2400.0], 95.0),
+    spec("g", Plosive { burst: 1800.0 }, true, [400.0, 1900.0, 2400.0], 85.0),
+    spec("?", Silence, false, [500.0, 1500.0, 2500.0], 50.0),
+    // 破擦音
+    spec("tS", Affricate { center: 3000.0 }, false, [400.0, 1900.0, 2500.0], 130.0),
+    spec("dZ", Affricate { center: 3000.0 }, true, [400.0, 1900.0, 2500.0], 120.0),
+    spec("ts", Affricate { center: 5500.0 }, false, [400.0, 1700.0, 2600.0], 130.0),
+    spec("dz", Affricate { center: 5500.0 }, true, [400.0, 1700.0, 2600.0], 120.0),
+    spec("tS;", Affricate { center: 3500.0 }, false, [350.0, 2100.0, 2800.0], 130.0),
+    spec("dZ;", Affricate { center: 3500.0 }, true, [350.0, 2100.0, 2800.0], 120.0),
+];
+
+/// Klatt-style cascade/parallel formant synthesizer.
+///
+/// Every eSpeak mnemonic produced by `PhonemeConverter` for the phoneme
+/// database has a formant target, duration and voicing entry here, so no
+/// external binary is needed. Output is fully deterministic.
+#[derive(Debug, Clone)]
+pub struct FormantSynthesizer {
+    sample_rate: u32,
+}
+
+impl FormantSynthesizer {
+    /// Same rate as espeak-ng's output.
+    pub const DEFAULT_SAMPLE_RATE: u32 = 22050;
+
+    /// Highest output rate accepted by the CLI.
+    pub const MAX_SAMPLE_RATE: u32 = 192_000;
+
+    pub fn new(sample_rate: u32) -> Self {
+        Self { sample_rate }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Whether the synthesizer has formant data for the given mnemonic.
+    pub fn supports(espeak: &str) -> bool {
+        PHONEMES.iter().any(|p| p.espeak == espeak)
+    }
+}
+
+impl Default for FormantSynthesizer {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SAMPLE_RATE)
+    }
+}
+
+impl Synthesizer for FormantSynthesizer {
+    fn name(&self) -> &'static str {
+        "formant"
+    }
+
+    fn cache_id(&self) -> String {
+        format!("formant@{}", self.sample_rate)
+    }
+
+    fn synthesize(&self, espeak_phonemes: &str, config: &SynthConfig) -> Result<AudioData> {
+        let segments = parse(espeak_phonemes, config.word_gap as f32 * 10.0)?;
+        let samples = Renderer::new(self.sample_rate, config).render(&segments);
+
+        Ok(AudioData {
+            samples,
+            sample_rate: self.sample_rate,
+            channels: 1,
+        })
+    }
+}
+
+struct Segment {
+    spec: PhonemeSpec,
+    duration_ms: f32,
+}
+
+// 空白（単語の区切り）には word_gap_ms の無音を入れる
+fn parse(espeak_phonemes: &str, word_gap_ms: f32) -> Result<Vec<Segment>> {
+    let max_len = PHONEMES.iter().map(|p| p.espeak.len()).max().unwrap_or(1);
+    let silence = spec("_", Silence, false, [500.0, 1500.0, 2500.0], PAUSE_MS);
+    let mut segments: Vec<Segment> = Vec::new();
+    let mut rest = espeak_phonemes;
+
+    while let Some(c) = rest.chars().next() {
+        match c {
+            // 強勢記号は無視する
+            '\'' | ',' => rest = &rest[1..],
+            ':' => {
+                if let Some(last) = segments.last_mut() {
+                    last.duration_ms *= LENGTH_MARK_FACTOR;
+                }
+                rest = &rest[1..];
+            }
+            '_' => {
+                segments.push(Segment {
+                    spec: silence,
+                    duration_ms: PAUSE_MS,
+                });
+                rest = &rest[1..];
+            }
+            c if c.is_whitespace() => {
+                let trimmed = rest.trim_start();
+                if word_gap_ms > 0.0 && !segments.is_empty() && !trimmed.is_empty() {
+                    segments.push(Segment {
+                        spec: silence,
+                        duration_ms: word_gap_ms,
+                    });
+                }
+                rest = trimmed;
+            }
+            _ => {
+                let matched = (1..=max_len.min(rest.len())).rev().find_map(|len| {
+                    let symbol = rest.get(..len)?;
+                    PHONEMES.iter().find(|p| p.espeak == symbol)
+                });
+                let spec = *matched.ok_or_else(|| {
+                    PhonemeReverserError::Synthesis(format!(
+                        "No formant data for eSpeak phoneme '{}' in '{}'",
+                        c, espeak_phonemes
+                    ))
+                })?;
+                rest = &rest[spec.espeak.len()..];
+                segments.push(Segment {
+                    spec,
+                    duration_ms: spec.duration_ms,
+                });
+            }
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Two-pole resonator with unity gain at DC (Klatt 1980).
+#[derive(Default)]
+struct Resonator {
+    a: f32,
+    b: f32,
+    c: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl Resonator {
+    fn set(&mut self, frequency: f32, bandwidth: f32, sample_rate: f32) {
+        let t = 1.0 / sample_rate;
+        self.c = -(-2.0 * PI * bandwidth * t).exp();
+        self.b = 2.0 * (-PI * bandwidth * t).exp() * (2.0 * PI * frequency * t).cos();
+        self.a = 1.0 - self.b - self.c;
+    }
+
+    fn process(&mut self, x: f32) -> f32 {
+        let y = self.a * x + self.b * self.y1 + self.c * self.y2;
+        self.y2 = self.y1;
+        self.y1 = y;
+        y
+    }
+}
+
+/// Constant-peak band-pass biquad used for the frication branch.
+#[derive(Default)]
+struct BandPass {
+    b0: f32,
+    a1: f32,
+    a2: f32,
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl BandPass {
+    fn set(&mut self, center: f32, sample_rate: f32) {
+        let center = center.min(sample_rate * 0.45);
+        let w0 = 2.0 * PI * center / sample_rate;
+        // Q ≈ 2.5（摩擦音のスペクトルは広い）
+        let alpha = w0.sin() / (2.0 * 2.5);
+        let a0 = 1.0 + alpha;
+        self.b0 = alpha / a0;
+        self.a1 = -2.0 * w0.cos() / a0;
+        self.a2 = (1.0 - alpha) / a0;
+    }
+
+    fn process(&mut self, x: f32) -> f32 {
+        let y = self.b0 * x - self.b0 * self.x2 - self.a1 * self.y1 - self.a2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = x;
+        self.y2 = self.y1;
+        self.y1 = y;
+        y
+    }
+}
+
+/// Per-sample source amplitudes (AV, AH, AF in Klatt's terms).
+#[derive(Clone, Copy, Default)]
+struct Gains {
+    voice: f32,
+    aspiration: f32,
+    frication: f32,
+}
+
+/// How a voice variant changes the default voice.
+#[derive(Debug, Clone, Copy)]
+struct VoiceProfile {
+    f0_scale: f32,
+    /// Scales all formant frequencies (shorter vocal tract > 1).
+    formant_scale: f32,
+    /// Share of the voicing replaced by aspiration noise.
+    breathiness: f32,
+}
+
+impl VoiceProfile {
+    const DEFAULT: VoiceProfile = VoiceProfile {
+        f0_scale: 1.0,
+        formant_scale: 1.0,
+        breathiness: 0.0,
+    };
+
+    // espeak-ng の m1-m7 / f1-f5 / croak / whisper を近似する
+    fn for_variant(variant: Option<&str>) -> Self {
+        let Some(variant) = variant else {
+            return Self::DEFAULT;
+        };
+        let number = |prefix: char| {
+            variant
+                .strip_prefix(prefix)
+                .and_then(|n| n.parse::<u8>().ok())
+                .map(f32::from)
+        };
+
+        if let Some(n) = number('m') {
+            VoiceProfile {
+                f0_scale: 0.85 + 0.05 * n,
+                formant_scale: 1.0 - 0.01 * n,
+                ..Self::DEFAULT
+            }
+        } else if let Some(n) = number('f') {
+            VoiceProfile {
+                f0_scale: 1.6 + 0.1 * n,
+                formant_scale: 1.15,
+                breathiness: 0.1,
+            }
+        } else {
+            match variant {
+                "croak" => VoiceProfile {
+                    f0_scale: 0.7,
+                    breathiness: 0.3,
+                    ..Self::DEFAULT
+                },
+                "whisper" => VoiceProfile {
+                    breathiness: 1.0,
+                    ..Self::DEFAULT
+                },
+                _ => Self::DEFAULT,
+            }
+        }
+    }
+}
+
+struct Renderer {
+    sample_rate: f32,
+    speed_factor: f32,
+    base_f0: f32,
+    gain: f32,
+    voice: VoiceProfile,
+    formants: [Resonator; 3],
+    frication: BandPass,
+    gains: Gains,
+    gain_coeff: f32,
+    glottal_phase: f32,
+    last_glottal: f32,
+    noise_state: u32,
+}
+
+impl Renderer {
+    fn new(sample_rate: u32, config: &SynthConfig) -> Self {
+        let sample_rate = sample_rate as f32;
+        let voice = VoiceProfile::for_variant(config.variant.as_deref());
+        Self {
+            sample_rate,
+            // speed / pitch / amplitude は espeak-ng と同じスケール（175 wpm、0-99、0-200）
+            speed_factor: 175.0 / config.speed.max(1) as f32,
+            base_f0: (60.0 + config.pitch as f32 * 1.2) * voice.f0_scale,
+            gain: config.amplitude as f32 / 100.0,
+            voice,
+            formants: Default::default(),
+            frication: BandPass::default(),
+            gains: Gains::default(),
+            gain_coeff: 1.0 - (-1.0 / (GAIN_SMOOTHING_SECS * sample_rate)).exp(),
+            glottal_phase: 0.0,
+            last_glottal: 0.0,
+            noise_state: 0x2545_f491,
+        }
+    }
+
+    fn render(mut self, segments: &[Segment]) -> Vec<f32> {
+        let lengths: Vec<usize> = segments
+            .iter()
+            .map(|s| (s.duration_ms * self.speed_factor / 1000.0 * self.sample_rate) as usize)
+            .collect();
+        let total: usize = lengths.iter().sum();
+
+        let mut samples = Vec::with_capacity(total);
+        let mut current = segments
+            .first()
+            .map_or([500.0, 1500.0, 2500.0], |s| s.spec.formants);
+
+        for (segment, &len) in segments.iter().zip(&lengths) {
+            let start = current;
+            for i in 0..len {
+                let t = i as f32 / len as f32;
+                let formants = formant_track(&segment.spec, start, t);
+                if i % COEFF_UPDATE_INTERVAL == 0 {
+                    self.update_filters(&segment.spec, formants);
+                }
+                current = formants;
+
+                // 全体にわたって緩やかにピッチを下げる（自然な下降調）
+                let progress = samples.len() as f32 / total.max(1) as f32;
+                let f0 = self.base_f0 * (1.1 - 0.2 * progress);
+                samples.push(self.next_sample(source_gains(&segment.spec, t), f0));
+            }
+        }
+
+        normalize(&mut samples);
+        for sample in &mut samples {
+            *sample = (*sample * self.gain).clamp(-1.0, 1.0);
+        }
+        samples
+    }
+
+    fn update_filters(&mut self, spec: &PhonemeSpec, formants: [f32; 3]) {
+        let nyquist = self.sample_rate * 0.45;
+        for (k, resonator) in self.formants.iter_mut().enumerate() {
+            let bandwidth = match spec.manner {
+                Nasal if k == 0 => 100.0,
+                _ => FORMANT_BANDWIDTHS[k],
+            };
+            let frequency = formants[k] * self.voice.formant_scale;
+            resonator.set(frequency.min(nyquist), bandwidth, self.sample_rate);
+        }
+
+        let center = match spec.manner {
+            Fricative { center, .. } | Affricate { center } => center,
+            Plosive { burst } => burst,
+            _ => 2500.0,
+        };
+        self.frication.set(center, self.sample_rate);
+    }
+
+    fn next_sample(&mut self, target: Gains, f0: f32) -> f32 {
+        let k = self.gain_coeff;
+        let breathiness = self.voice.breathiness;
+        let target_voice = target.voice * (1.0 - breathiness);
+        let target_aspiration = target.aspiration + target.voice * breathiness;
+        self.gains.voice += (target_voice - self.gains.voice) * k;
+        self.gains.aspiration += (target_aspiration - self.gains.aspiration) * k;
+        self.gains.frication += (target.frication - self.gains.frication) * k;
+
+        let noise = self.noise();
+        let glottal = self.glottal(f0);
+
+        let mut cascade = glottal * self.gains.voice + noise * self.gains.aspiration * 0.3;
+        for resonator in &mut self.formants {
+            cascade = resonator.process(cascade);
+        }
+        let frication = self.frication.process(noise) * self.gains.frication;
+
+        cascade + frication
+    }
+
+    /// Rosenberg-style glottal pulse followed by a first difference (lip radiation).
+    fn glottal(&mut self, f0: f32) -> f32 {
+        self.glottal_phase = (self.glottal_phase + f0 / self.sample_rate).fract();
+        let open_quotient = 0.6;
+        let pulse = if self.glottal_phase < open_quotient {
+            0.5 * (1.0 - (2.0 * PI * self.glottal_phase / open_quotient).cos())
+        } else {
+            0.0
+        };
+        let radiated = pulse - self.last_glottal;
+        self.last_glottal = pulse;
+        radiated * 4.0
+    }
+
+    /// xorshift32 so that the output is reproducible across runs.
+    fn noise(&mut self) -> f32 {
+        let mut x = self.noise_state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.noise_state = x;
+        (x as f32 / u32::MAX as f32) * 2.0 - 1.0
+    }
+}
+
+fn lerp3(from: [f32; 3], to: [f32; 3], t: f32) -> [f32; 3] {
+    let t = t.clamp(0.0, 1.0);
+    [
+        from[0] + (to[0] - from[0]) * t,
+        from[1] + (to[1] - from[1]) * t,
+        from[2] + (to[2] - from[2]) * t,
+    ]
+}
+
+fn formant_track(spec: &PhonemeSpec, start: [f32; 3], t: f32) -> [f32; 3] {
+    match spec.manner {
+        Diphthong(glide) if t > TRANSITION => {
+            lerp3(spec.formants, glide, (t - TRANSITION) / (1.0 - TRANSITION))
+        }
+        _ => lerp3(start, spec.formants, t / TRANSITION),
+    }
+}
+
+fn source_gains(spec: &PhonemeSpec, t: f32) -> Gains {
+    let voiced = if spec.voiced { 1.0 } else { 0.0 };
+    match spec.manner {
+        Vowel | Diphthong(_) => Gains {
+            voice: 1.0,
+            ..Gains::default()
+        },
+        Nasal => Gains {
+            voice: 0.6,
+            ..Gains::default()
+        },
+        Approximant => Gains {
+            voice: 0.8,
+            ..Gains::default()
+        },
+        Aspirate => Gains {
+            aspiration: 1.0,
+            ..Gains::default()
+        },
+        Fricative { gain, .. } => Gains {
+            voice: 0.4 * voiced,
+            frication: gain,
+            ..Gains::default()
+        },
+        // 閉鎖 → 破裂 → 帯気（無声）/ 声の立ち上がり（有声）
+        Plosive { .. } if t < 0.65 => Gains {
+            voice: 0.15 * voiced,
+            ..Gains::default()
+        },
+        Plosive { .. } if t < 0.75 => Gains {
+            frication: 1.0,
+            ..Gains::default()
+        },
+        Plosive { .. } => Gains {
+            voice: 0.8 * voiced,
+            aspiration: 0.5 * (1.0 - voiced),
+            ..Gains::default()
+        },
+        Affricate { .. } if t < 0.4 => Gains {
+            voice: 0.15 * voiced,
+            ..Gains::default()
+        },
+        Affricate { .. } => Gains {
+            voice: 0.3 * voiced,
+            frication: 0.7,
+            ..Gains::default()
+        },
+        Silence => Gains::default(),
+    }
+}
+
+fn normalize(samples: &mut [f32]) {
+    let peak = samples.iter().fold(0.0f32, |max, s| max.max(s.abs()));
+    if peak > 0.0 {
+        let scale = 0.8 / peak;
+        samples.iter_mut().for_each(|s| *s *= scale);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::phoneme_db::PhonemeDatabase;
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_renders_every_database_phoneme() {
+        let db = PhonemeDatabase::new();
+        let synth = FormantSynthesizer::default();
+        let config = SynthConfig::default();
+
+        for phoneme in db.get_vowels().iter().chain(db.get_consonants()) {
+            // 子音単独では無音区間が長いので母音で挟む
+            let input = format!("a{}a", phoneme.espeak);
+            let audio = synth.synthesize(&input, &config).unwrap_or_else(|e| {
+                panic!(
+                    "Failed to render /{}/ ({}): {}",
+                    phoneme.ipa, phoneme.espeak, e
+                )
+            });
+            assert!(!audio.samples.is_empty());
+            assert!(audio.samples.iter().all(|s| s.is_finite()));
+            assert!(rms(&audio.samples) > 0.01, "/{}/ is silent", phoneme.ipa);
+        }
+    }
+
+    #[test]
+    fn test_output_is_deterministic() {
+        let synth = FormantSynthesizer::default();
+        let config = SynthConfig::default();
+        let first = synth.synthesize("konnitSiwa", &config).unwrap();
+        let second = synth.synthesize("konnitSiwa", &config).unwrap();
+        assert_eq!(first.samples, second.samples);
+    }
+
+    #[test]
+    fn test_sample_rate_is_configurable() {
+        let config = SynthConfig::default();
+        let low = FormantSynthesizer::new(16000)
+            .synthesize("aka", &config)
+            .unwrap();
+        let high = FormantSynthesizer::new(48000)
+            .synthesize("aka", &config)
+            .unwrap();
+
+        assert_eq!(low.sample_rate, 16000);
+        assert_eq!(high.sample_rate, 48000);
+        let ratio = high.samples.len() as f32 / low.samples.len() as f32;
+        assert!(
+            (ratio - 3.0).abs() < 0.01,
+            "Duration should not depend on sample rate"
+        );
+    }
+
+    #[test]
+    fn test_length_mark_extends_duration() {
+        let synth = FormantSynthesizer::default();
+        let config = SynthConfig::default();
+        let short = synth.synthesize("a", &config).unwrap();
+        let long = synth.synthesize("a:", &config).unwrap();
+        assert!(long.samples.len() > short.samples.len());
+    }
+
+    #[test]
+    fn test_multi_character_mnemonics() {
+        let segments = parse("tSaIdZ;", 0.0).unwrap();
+        let names: Vec<&str> = segments.iter().map(|s| s.spec.espeak).collect();
+        assert_eq!(names, vec!["tS", "aI", "dZ;"]);
+    }
+
+    #[test]
+    fn test_unknown_mnemonic_fails() {
+        let result = FormantSynthesizer::default().synthesize("a%a", &SynthConfig::default());
+        assert!(matches!(result, Err(PhonemeReverserError::Synthesis(_))));
+    }
+
+    fn zero_crossing_rate(samples: &[f32]) -> f32 {
+        let crossings = samples
+            .windows(2)
+            .filter(|w| (w[0] < 0.0) != (w[1] < 0.0))
+            .count();
+        crossings as f32 / samples.len() as f32
+    }
+
+    fn peak(samples: &[f32]) -> f32 {
+        samples.iter().fold(0.0f32, |max, s| max.max(s.abs()))
+    }
+
+    #[test]
+    fn test_speed_changes_duration() {
+        let synth = FormantSynthesizer::default();
+        let normal = synth.synthesize("aka", &SynthConfig::default()).unwrap();
+        let fast = SynthConfig {
+            speed: 350,
+            ..SynthConfig::default()
+        };
+        let fast = synth.synthesize("aka", &fast).unwrap();
+        let ratio = normal.samples.len() as f32 / fast.samples.len() as f32;
+        assert!((ratio - 2.0).abs() < 0.01, "ratio {}", ratio);
+    }
+
+    #[test]
+    fn test_pitch_changes_waveform() {
+        let synth = FormantSynthesizer::default();
+        let normal = synth.synthesize("a", &SynthConfig::default()).unwrap();
+        let high = SynthConfig {
+            pitch: 99,
+            ..SynthConfig::default()
+        };
+        let high = synth.synthesize("a", &high).unwrap();
+        assert_eq!(high.samples.len(), normal.samples.len());
+        assert_ne!(high.samples, normal.samples);
+    }
+
+    #[test]
+    fn test_amplitude_scales_output() {
+        let synth = FormantSynthesizer::default();
+        let normal = synth.synthesize("aka", &SynthConfig::default()).unwrap();
+        let quiet = SynthConfig {
+            amplitude: 50,
+            ..SynthConfig::default()
+        };
+        let quiet = synth.synthesize("aka", &quiet).unwrap();
+        assert!((peak(&quiet.samples) * 2.0 - peak(&normal.samples)).abs() < 1e-4);
+
+        let loud = SynthConfig {
+            amplitude: 200,
+            ..SynthConfig::default()
+        };
+        let loud = synth.synthesize("aka", &loud).unwrap();
+        assert!(peak(&loud.samples) <= 1.0);
+        assert!(peak(&loud.samples) > peak(&normal.samples));
+    }
+
+    #[test]
+    fn test_word_gap_inserts_pause_between_words() {
+        let synth = FormantSynthesizer::default();
+        let joined = synth.synthesize("a a", &SynthConfig::default()).unwrap();
+        let gap = SynthConfig {
+            word_gap: 10,
+            ..SynthConfig::default()
+        };
+        let spaced = synth.synthesize("a a", &gap).unwrap();
+        // 100ms × 22050Hz
+        assert_eq!(spaced.samples.len(), joined.samples.len() + 2205);
+        assert_eq!(
+            synth.synthesize("aa", &gap).unwrap().samples.len(),
+            joined.samples.len()
+        );
+    }
+
+    #[test]
+    fn test_voice_variants_change_waveform() {
+        let synth = FormantSynthesizer::default();
+        let default = synth.synthesize("a", &SynthConfig::default()).unwrap();
+        let variant = |name: &str| {
+            let config = SynthConfig {
+                variant: Some(name.to_string()),
+                ..SynthConfig::default()
+            };
+            synth.synthesize("a", &config).unwrap().samples
+        };
+
+        assert_ne!(variant("f3"), default.samples);
+        assert_ne!(variant("m3"), default.samples);
+        assert_ne!(variant("f3"), variant("f4"));
+        // ささやき声は声帯振動の代わりに気息音で鳴る
+        let whisper = variant("whisper");
+        assert_ne!(whisper, default.samples);
+        assert!(rms(&whisper) > 0.01);
+    }
+
+    #[test]
+    fn test_voiceless_fricative_is_noisier_than_vowel() {
+        // 無声摩擦音はゼロ交差が多い（高域のノイズ）
+        let synth = FormantSynthesizer::default();
+        let config = SynthConfig::default();
+        let s = synth.synthesize("s", &config).unwrap();
+        let a = synth.synthesize("a", &config).unwrap();
+        assert!(zero_crossing_rate(&s.samples) > zero_crossing_rate(&a.samples) * 2.0);
+    }
+}
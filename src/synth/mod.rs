@@ -0,0 +1,309 @@
+mod cache;
+mod espeak_cli;
+#[cfg(feature = "espeak-ffi")]
+mod espeak_ffi;
+mod formant;
+mod test_backend;
+
+pub use cache::{CacheKey, CacheStats, CachedSynthesizer};
+pub use espeak_cli::{phonemize, synthesize_phonemes, EspeakCli};
+#[cfg(feature = "espeak-ffi")]
+pub use espeak_ffi::EspeakLib;
+pub use formant::FormantSynthesizer;
+pub use test_backend::TestSynthesizer;
+
+use crate::audio::AudioData;
+use crate::error::{PhonemeReverserError, Result};
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "ja")]
+    Japanese,
+    #[serde(rename = "en")]
+    English,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::Japanese, Language::English];
+
+    pub fn to_espeak_code(&self) -> &str {
+        match self {
+            Language::Japanese => "ja",
+            Language::English => "en-us",
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            Language::Japanese => "日本語",
+            Language::English => "English",
+        }
+    }
+}
+
+/// Voice variants of espeak-ng (`-v ja+f3`).
+pub const VOICE_VARIANTS: &[&str] = &[
+    "m1", "m2", "m3", "m4", "m5", "m6", "m7", "f1", "f2", "f3", "f4", "f5", "croak", "whisper",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SynthConfig {
+    pub language: Language,
+    /// Words per minute (espeak-ng `-s`).
+    pub speed: u32,
+    /// Base pitch, 0-99 (espeak-ng `-p`).
+    pub pitch: u32,
+    /// Volume, 0-200 (espeak-ng `-a`).
+    pub amplitude: u32,
+    /// Extra pause between words in units of 10 ms (espeak-ng `-g`).
+    pub word_gap: u32,
+    /// One of [`VOICE_VARIANTS`], appended to the voice name.
+    pub variant: Option<String>,
+}
+
+impl Default for SynthConfig {
+    fn default() -> Self {
+        Self {
+            language: Language::default(),
+            speed: 175,
+            pitch: 50,
+            amplitude: 100,
+            word_gap: 0,
+            variant: None,
+        }
+    }
+}
+
+/// A user-adjustable synthesis setting, in the order of the settings view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SynthParam {
+    Speed,
+    Pitch,
+    Amplitude,
+    WordGap,
+    Variant,
+}
+
+impl SynthParam {
+    pub const ALL: [SynthParam; 5] = [
+        SynthParam::Speed,
+        SynthParam::Pitch,
+        SynthParam::Amplitude,
+        SynthParam::WordGap,
+        SynthParam::Variant,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            SynthParam::Speed => "Speed (wpm)",
+            SynthParam::Pitch => "Pitch",
+            SynthParam::Amplitude => "Amplitude",
+            SynthParam::WordGap => "Word gap (10 ms)",
+            SynthParam::Variant => "Voice variant",
+        }
+    }
+
+    /// Accepted values; for `Variant` the index into [`VOICE_VARIANTS`] plus
+    /// one (0 is the default voice).
+    pub fn range(&self) -> RangeInclusive<u32> {
+        match self {
+            SynthParam::Speed => 80..=450,
+            SynthParam::Pitch => 0..=99,
+            SynthParam::Amplitude => 0..=200,
+            SynthParam::WordGap => 0..=100,
+            SynthParam::Variant => 0..=VOICE_VARIANTS.len() as u32,
+        }
+    }
+
+    /// Amount changed by one key press in the settings view.
+    pub fn step(&self) -> u32 {
+        match self {
+            SynthParam::Speed | SynthParam::Amplitude => 5,
+            SynthParam::Pitch | SynthParam::WordGap | SynthParam::Variant => 1,
+        }
+    }
+
+    /// Check that `value` is in range, for argument parsers.
+    pub fn check(&self, value: u32) -> Result<u32> {
+        let range = self.range();
+        if range.contains(&value) {
+            Ok(value)
+        } else {
+            Err(PhonemeReverserError::Synthesis(format!(
+                "{} must be between {} and {}, got {}",
+                self.display_name(),
+                range.start(),
+                range.end(),
+                value
+            )))
+        }
+    }
+}
+
+impl SynthConfig {
+    pub fn get(&self, param: SynthParam) -> u32 {
+        match param {
+            SynthParam::Speed => self.speed,
+            SynthParam::Pitch => self.pitch,
+            SynthParam::Amplitude => self.amplitude,
+            SynthParam::WordGap => self.word_gap,
+            SynthParam::Variant => self
+                .variant
+                .as_deref()
+                .and_then(|v| VOICE_VARIANTS.iter().position(|&name| name == v))
+                .map_or(0, |index| index as u32 + 1),
+        }
+    }
+
+    /// Set `param`, clamping the value into its range.
+    pub fn set(&mut self, param: SynthParam, value: u32) {
+        let range = param.range();
+        let value = value.clamp(*range.start(), *range.end());
+        match param {
+            SynthParam::Speed => self.speed = value,
+            SynthParam::Pitch => self.pitch = value,
+            SynthParam::Amplitude => self.amplitude = value,
+            SynthParam::WordGap => self.word_gap = value,
+            SynthParam::Variant => {
+                self.variant = value
+                    .checked_sub(1)
+                    .map(|index| VOICE_VARIANTS[index as usize].to_string())
+            }
+        }
+    }
+
+    /// Change `param` by `steps` steps, stopping at the ends of its range.
+    pub fn adjust(&mut self, param: SynthParam, steps: i32) {
+        let delta = steps.saturating_mul(param.step() as i32);
+        self.set(param, self.get(param).saturating_add_signed(delta));
+    }
+
+    /// Value of `param` as shown in the UI.
+    pub fn value_text(&self, param: SynthParam) -> String {
+        match param {
+            SynthParam::Variant => self
+                .variant
+                .clone()
+                .unwrap_or_else(|| "default".to_string()),
+            _ => self.get(param).to_string(),
+        }
+    }
+
+    /// Check every setting; configs built from files or other untrusted
+    /// input go through this before synthesis.
+    pub fn validate(&self) -> Result<()> {
+        for param in [
+            SynthParam::Speed,
+            SynthParam::Pitch,
+            SynthParam::Amplitude,
+            SynthParam::WordGap,
+        ] {
+            param.check(self.get(param))?;
+        }
+        match &self.variant {
+            Some(variant) if !VOICE_VARIANTS.contains(&variant.as_str()) => Err(
+                PhonemeReverserError::Synthesis(format!("Unknown voice variant '{}'", variant)),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// espeak-ng voice name including the variant, e.g. `ja+f3`.
+    pub fn voice_name(&self) -> String {
+        match &self.variant {
+            Some(variant) => format!("{}+{}", self.language.to_espeak_code(), variant),
+            None => self.language.to_espeak_code().to_string(),
+        }
+    }
+}
+
+/// A speech synthesis backend that renders eSpeak phoneme mnemonics.
+pub trait Synthesizer: Send {
+    /// Short backend name shown in the UI.
+    fn name(&self) -> &'static str;
+
+    /// Identifies the backend and every option that changes its output; part
+    /// of the [`CacheKey`].
+    fn cache_id(&self) -> String {
+        self.name().to_string()
+    }
+
+    fn synthesize(&self, espeak_phonemes: &str, config: &SynthConfig) -> Result<AudioData>;
+
+    /// Hit/miss counters when the backend is wrapped in a cache.
+    fn cache_stats(&self) -> Option<CacheStats> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_default_is_japanese() {
+        assert_eq!(Language::default(), Language::Japanese);
+    }
+
+    #[test]
+    fn test_language_to_espeak_code() {
+        assert_eq!(Language::Japanese.to_espeak_code(), "ja");
+        assert_eq!(Language::English.to_espeak_code(), "en-us");
+    }
+
+    #[test]
+    fn test_language_display_name() {
+        assert_eq!(Language::Japanese.display_name(), "日本語");
+        assert_eq!(Language::English.display_name(), "English");
+    }
+
+    #[test]
+    fn test_synth_config_default_is_japanese() {
+        let config = SynthConfig::default();
+        assert_eq!(config.language, Language::Japanese);
+    }
+
+    #[test]
+    fn test_adjust_clamps_to_range() {
+        let mut config = SynthConfig::default();
+        config.adjust(SynthParam::Pitch, 100);
+        assert_eq!(config.pitch, 99);
+        config.adjust(SynthParam::Speed, -100);
+        assert_eq!(config.speed, 80);
+        config.adjust(SynthParam::Amplitude, 2);
+        assert_eq!(config.amplitude, 110);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_variant_setting() {
+        let mut config = SynthConfig::default();
+        assert_eq!(config.value_text(SynthParam::Variant), "default");
+        assert_eq!(config.voice_name(), "ja");
+
+        config.adjust(SynthParam::Variant, 10);
+        assert_eq!(config.variant.as_deref(), Some("f3"));
+        assert_eq!(config.voice_name(), "ja+f3");
+
+        config.adjust(SynthParam::Variant, -20);
+        assert_eq!(config.variant, None);
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range_values() {
+        let config = SynthConfig {
+            amplitude: 300,
+            ..SynthConfig::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = SynthConfig {
+            variant: Some("robot".to_string()),
+            ..SynthConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
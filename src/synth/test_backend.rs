@@ -0,0 +1,159 @@
+use super::{SynthConfig, Synthesizer};
+use crate::audio::AudioData;
+use crate::error::Result;
+
+const SAMPLE_RATE: u32 = 22050;
+const SYMBOL_DURATION_SECS: f32 = 0.08;
+
+/// Deterministic backend that renders each eSpeak symbol as a short sine tone.
+///
+/// It needs no external binaries, so tests and CI jobs can exercise the whole
+/// synthesis path and compare the output sample-for-sample. Speed sets the tone
+/// length, pitch and the voice variant shift the tones, amplitude scales them
+/// and whitespace becomes a `word_gap` pause.
+#[derive(Debug, Default)]
+pub struct TestSynthesizer;
+
+impl Synthesizer for TestSynthesizer {
+    fn name(&self) -> &'static str {
+        "test"
+    }
+
+    fn synthesize(&self, espeak_phonemes: &str, config: &SynthConfig) -> Result<AudioData> {
+        // speed は espeak-ng と同じく words per minute（175が標準）
+        let duration = SYMBOL_DURATION_SECS * 175.0 / config.speed.max(1) as f32;
+        let samples_per_symbol = (duration * SAMPLE_RATE as f32) as usize;
+        // word_gap は 10ms 単位
+        let gap = config.word_gap as usize * SAMPLE_RATE as usize / 100;
+        let pitch_scale = (0.5 + config.pitch as f32 / 100.0) * variant_scale(config);
+        let gain = 0.5 * config.amplitude as f32 / 100.0;
+
+        let mut samples = Vec::new();
+        for symbol in espeak_phonemes.trim().chars() {
+            if symbol.is_whitespace() {
+                samples.resize(samples.len() + gap, 0.0);
+                continue;
+            }
+            let frequency = (200.0 + (symbol as u32 % 32) as f32 * 25.0) * pitch_scale;
+            samples.extend((0..samples_per_symbol).map(|i| {
+                let t = i as f32 / SAMPLE_RATE as f32;
+                (2.0 * std::f32::consts::PI * frequency * t).sin() * gain
+            }));
+        }
+
+        Ok(AudioData {
+            samples,
+            sample_rate: SAMPLE_RATE,
+            channels: 1,
+        })
+    }
+}
+
+// 声のバリエーションごとに決まった分だけ音を高くする（既定の声は 1.0）
+fn variant_scale(config: &SynthConfig) -> f32 {
+    let Some(variant) = &config.variant else {
+        return 1.0;
+    };
+    let hash = variant.bytes().fold(0u32, |hash, byte| {
+        hash.wrapping_mul(31).wrapping_add(u32::from(byte))
+    });
+    1.0 + (1 + hash % 16) as f32 * 0.02
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_is_deterministic() {
+        let config = SynthConfig::default();
+        let first = TestSynthesizer.synthesize("aka", &config).unwrap();
+        let second = TestSynthesizer.synthesize("aka", &config).unwrap();
+        assert_eq!(first.samples, second.samples);
+    }
+
+    #[test]
+    fn test_output_depends_on_phonemes() {
+        let config = SynthConfig::default();
+        let aka = TestSynthesizer.synthesize("aka", &config).unwrap();
+        let aki = TestSynthesizer.synthesize("aki", &config).unwrap();
+        assert_eq!(aka.samples.len(), aki.samples.len());
+        assert_ne!(aka.samples, aki.samples);
+    }
+
+    #[test]
+    fn test_length_scales_with_symbol_count() {
+        let config = SynthConfig::default();
+        let one = TestSynthesizer.synthesize("a", &config).unwrap();
+        let three = TestSynthesizer.synthesize("a k a", &config).unwrap();
+        assert_eq!(three.samples.len(), one.samples.len() * 3);
+        assert_eq!(three.sample_rate, SAMPLE_RATE);
+    }
+
+    fn base() -> AudioData {
+        TestSynthesizer
+            .synthesize("a a", &SynthConfig::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_speed_changes_duration() {
+        let fast = SynthConfig {
+            speed: 350,
+            ..SynthConfig::default()
+        };
+        let fast = TestSynthesizer.synthesize("a a", &fast).unwrap();
+        assert!(fast.samples.len() < base().samples.len());
+    }
+
+    #[test]
+    fn test_pitch_changes_waveform() {
+        let high = SynthConfig {
+            pitch: 80,
+            ..SynthConfig::default()
+        };
+        let high = TestSynthesizer.synthesize("a a", &high).unwrap();
+        assert_eq!(high.samples.len(), base().samples.len());
+        assert_ne!(high.samples, base().samples);
+    }
+
+    #[test]
+    fn test_amplitude_scales_output() {
+        let quiet = SynthConfig {
+            amplitude: 50,
+            ..SynthConfig::default()
+        };
+        let quiet = TestSynthesizer.synthesize("a a", &quiet).unwrap();
+        assert!((quiet.samples[10] - base().samples[10] * 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_word_gap_inserts_pause_between_words() {
+        let gap = SynthConfig {
+            word_gap: 10,
+            ..SynthConfig::default()
+        };
+        let gap = TestSynthesizer.synthesize("a a", &gap).unwrap();
+        assert_eq!(
+            gap.samples.len(),
+            base().samples.len() + SAMPLE_RATE as usize / 10
+        );
+    }
+
+    #[test]
+    fn test_voice_variants_change_waveform() {
+        let render = |variant: &str| {
+            let config = SynthConfig {
+                variant: Some(variant.to_string()),
+                ..SynthConfig::default()
+            };
+            TestSynthesizer.synthesize("a a", &config).unwrap()
+        };
+        let f3 = render("f3");
+        assert_eq!(f3.samples.len(), base().samples.len());
+        assert_ne!(f3.samples, base().samples);
+        assert_ne!(f3.samples, render("m1").samples);
+        // 同じバリエーションは同じ音声になる
+        assert_eq!(f3.samples, render("f3").samples);
+    }
+}
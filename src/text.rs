@@ -0,0 +1,98 @@
+use crate::error::Result;
+use crate::phoneme::{map_to_database, PhonemeMapping, IPA_ALIASES};
+use crate::synth::{phonemize, Language};
+use crate::tui::phoneme_db::PhonemeDatabase;
+
+// 音素の区切りに影響しない記号（強勢・連結・タイバー・音節境界）
+const SUPRASEGMENTALS: &[char] = &['ˈ', 'ˌ', '‿', '͡', '.', '-', '|'];
+
+/// Convert English text into phonemes of `db` through espeak-ng.
+///
+/// Symbols that have no counterpart in `db` are reported in `unknown` with
+/// their index among the segmented IPA symbols.
+pub fn english_to_phonemes(text: &str, db: &PhonemeDatabase) -> Result<PhonemeMapping> {
+    let ipa = phonemize(text, Language::English)?;
+    Ok(map_to_database(&segment_ipa(&ipa, db), db))
+}
+
+/// Split unsegmented IPA (e.g. `həlˈoʊ`) into symbols.
+///
+/// Matches the longest IPA string known to `db` (or an alias) at each
+/// position, so diphthongs and affricates stay whole; a following `ː` stays
+/// attached. Stress marks and word boundaries are dropped.
+pub fn segment_ipa(ipa: &str, db: &PhonemeDatabase) -> Vec<String> {
+    let mut candidates: Vec<&str> = db
+        .get_vowels()
+        .iter()
+        .chain(db.get_consonants())
+        .map(|p| p.ipa.as_str())
+        .chain(IPA_ALIASES.iter().map(|(alias, _)| *alias))
+        .collect();
+    // 長い記号から照合する
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.chars().count()));
+
+    let mut symbols = Vec::new();
+    for word in ipa.split_whitespace() {
+        let chars: Vec<char> = word
+            .chars()
+            .filter(|c| !SUPRASEGMENTALS.contains(c))
+            .collect();
+        let mut index = 0;
+
+        while index < chars.len() {
+            let len = candidates
+                .iter()
+                .map(|c| c.chars().collect::<Vec<_>>())
+                .find(|c| chars[index..].starts_with(c))
+                .map_or(1, |c| c.len());
+
+            let mut symbol: String = chars[index..index + len].iter().collect();
+            index += len;
+            if chars.get(index) == Some(&'ː') {
+                symbol.push('ː');
+                index += 1;
+            }
+            symbols.push(symbol);
+        }
+    }
+
+    symbols
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn english() -> PhonemeDatabase {
+        PhonemeDatabase::new().for_language(Language::English)
+    }
+
+    #[test]
+    fn test_segment_keeps_diphthongs_and_affricates() {
+        let db = english();
+        assert_eq!(segment_ipa("həlˈoʊ", &db), vec!["h", "ə", "l", "oʊ"]);
+        assert_eq!(segment_ipa("tʃˈɜːtʃ", &db), vec!["tʃ", "ɜː", "tʃ"]);
+        assert_eq!(segment_ipa("d͡ʒˈʌdʒ", &db), vec!["dʒ", "ʌ", "dʒ"]);
+    }
+
+    #[test]
+    fn test_segment_splits_words_and_keeps_unknown_symbols() {
+        let db = english();
+        assert_eq!(
+            segment_ipa("ðə wˈɜːld", &db),
+            vec!["ð", "ə", "w", "ɜː", "l", "d"]
+        );
+    }
+
+    #[test]
+    fn test_segmented_ipa_maps_onto_database() {
+        let db = english();
+        let mapping = map_to_database(&segment_ipa("ðɪs ɪz bˈɛɾɚ", &db), &db);
+
+        let ipa: Vec<&str> = mapping.phonemes.iter().map(|p| p.ipa.as_str()).collect();
+        assert_eq!(ipa, vec!["ɪ", "s", "ɪ", "z", "b", "ɛ", "r", "ɜ"]);
+        assert_eq!(mapping.unknown.len(), 1);
+        assert_eq!(mapping.unknown[0].symbol, "ð");
+        assert_eq!(mapping.unknown[0].index, 0);
+    }
+}
@@ -1,187 +1,1169 @@
+use crate::audio::{read_wav, write_wav, AudioData};
+use crate::capture::{AudioRecorder, RecordingHandle};
+use crate::config::Config;
 use crate::converter::PhonemeConverter;
-use crate::error::Result;
-use crate::playback::AudioPlayer;
-use crate::synth::{synthesize_phonemes, Language, SynthConfig};
+use crate::error::{PhonemeReverserError, Result};
+use crate::kana;
+use crate::phoneme::{map_to_database, PhonemeMapping, PhonemeRecognizer, UnknownSymbol};
+use crate::playback::{
+    output_devices, AudioPlayer, AudioSink, DeviceInfo, OutputDevice, PlaybackHandle,
+};
+use crate::project::{Project, Sequence};
+use crate::phonotactics::{validate, Repair, Violation};
+use crate::reversal::{reverse, DiphthongPolicy, ReversalMode, ReversalStrategy};
+use crate::synth::{
+    CacheStats, CachedSynthesizer, EspeakCli, Language, SynthConfig, SynthParam, Synthesizer,
+};
+use crate::segment::{segment_at, Segment, DEFAULT_CROSSFADE, MAX_CROSSFADE};
+use crate::tui::history::{Edit, History};
+use crate::tui::worker::{JobHandle, JobKind, JobOutput, Render, SynthWorker, WorkerEvent};
+use crate::text::english_to_phonemes;
 use crate::tui::phoneme_db::{Phoneme, PhonemeDatabase};
 use chrono::Local;
-use std::path::PathBuf;
-use tempfile::NamedTempFile;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum View {
     PhonemeSelection,
     Preview,
+    Settings,
+    Devices,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum PlaybackStatus {
     Idle,
+    // 合成ワーカーの順番待ち
+    Queued,
     Synthesizing,
     Playing,
+    Paused,
+    Done,
     Error(String),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputKind {
+    ImportAudio,
+    Kana,
+    EnglishText,
+    SaveProject,
+    OpenProject,
+}
+
+impl InputKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            InputKind::ImportAudio => "Import WAV",
+            InputKind::Kana => "Kana/Romaji",
+            InputKind::EnglishText => "English text",
+            InputKind::SaveProject => "Save project",
+            InputKind::OpenProject => "Open project",
+        }
+    }
+}
+
+/// 1行テキスト入力（ステータスバーに表示）
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputPrompt {
+    pub kind: InputKind,
+    pub buffer: String,
+}
+
 pub struct App {
     // UI State
     pub should_quit: bool,
     pub current_view: View,
+    pub input: Option<InputPrompt>,
+    pub message: Option<String>,
+    // 設定画面で選択中の項目（SynthParam::ALL の位置）と、閉じたときに戻る画面
+    pub settings_cursor: usize,
+    settings_return: View,
+    // 出力デバイス画面の一覧と選択中の行（0 はシステムの既定）、閉じたときに戻る画面
+    pub devices: Vec<DeviceInfo>,
+    pub device_cursor: usize,
+    devices_return: View,
 
     // Phoneme Data
     pub selected_phonemes: Vec<Phoneme>,
+    // 編集カーソル（挿入位置、0..=len）と範囲選択の起点
+    pub cursor: usize,
+    pub selection_anchor: Option<usize>,
+    pub replace_mode: bool,
+    clipboard: Vec<Phoneme>,
+    history: History,
+    // 現在の言語の音素とキー配列（inventory から切り出したもの）
     pub phoneme_db: PhonemeDatabase,
+    inventory: PhonemeDatabase,
+    pub diphthong_policy: DiphthongPolicy,
+    pub reversal_strategy: ReversalStrategy,
+    // A/B 比較で選択中の逆順方式（音素の逆順 / 波形の反転）
+    pub ab_mode: ReversalMode,
+    // 音素ごとに合成してつなぐときの重なり
+    pub crossfade: Duration,
+    // 逆順結果に適用した音素配列の修正と、その適用対象だった逆順結果
+    repairs: Vec<Repair>,
+    repair_base: Vec<Phoneme>,
+    pub unknown_symbols: Vec<UnknownSymbol>,
+
+    // Project（編集中のシーケンスは保存・切り替え時に project へ書き戻す）
+    project: Project,
+    sequence_index: usize,
+    pub project_path: Option<PathBuf>,
 
     // Audio State
     pub is_playing: bool,
     pub playback_status: PlaybackStatus,
+    // 再生中の音声の音素境界（音素ごとに合成したときのみ）
+    playing_segments: Vec<Segment>,
+    playing_sample_rate: u32,
 
-    // Backend services
-    converter: PhonemeConverter,
+    // Backend services（変換と合成はワーカースレッドで行う）
+    worker: SynthWorker,
+    // 投入済みで結果を待っているジョブ（投入順）
+    jobs: Vec<JobHandle>,
+    cache_stats: Option<CacheStats>,
     synth_config: SynthConfig,
-    player: AudioPlayer,
+    // 出力デバイスの選択は設定ファイルに保存する（パスが無ければ保存しない）
+    config: Config,
+    config_path: Option<PathBuf>,
+    player: Box<dyn AudioSink>,
+    playback: Option<PlaybackHandle>,
+    recorder: AudioRecorder,
+    recording: Option<RecordingHandle>,
+    // allosaurus のモデル読み込みは重いので初回の取り込み時に初期化する
+    recognizer: Option<PhonemeRecognizer>,
 }
 
 impl App {
     pub fn new() -> Result<Self> {
+        Self::with_backends(
+            PhonemeDatabase::new(),
+            PhonemeConverter::new()?,
+            Box::new(CachedSynthesizer::new(
+                Box::new(EspeakCli),
+                CachedSynthesizer::DEFAULT_CAPACITY,
+            )),
+        )
+    }
+
+    pub fn with_backends(
+        phoneme_db: PhonemeDatabase,
+        converter: PhonemeConverter,
+        synthesizer: Box<dyn Synthesizer>,
+    ) -> Result<Self> {
         Ok(Self {
             should_quit: false,
             current_view: View::PhonemeSelection,
+            input: None,
+            message: None,
+            settings_cursor: 0,
+            settings_return: View::PhonemeSelection,
+            devices: Vec::new(),
+            device_cursor: 0,
+            devices_return: View::PhonemeSelection,
             selected_phonemes: Vec::new(),
-            phoneme_db: PhonemeDatabase::new(),
+            cursor: 0,
+            selection_anchor: None,
+            replace_mode: false,
+            clipboard: Vec::new(),
+            history: History::default(),
+            phoneme_db: phoneme_db.for_language(Language::default()),
+            inventory: phoneme_db,
+            diphthong_policy: DiphthongPolicy::default(),
+            reversal_strategy: ReversalStrategy::default(),
+            ab_mode: ReversalMode::default(),
+            crossfade: DEFAULT_CROSSFADE,
+            repairs: Vec::new(),
+            repair_base: Vec::new(),
+            unknown_symbols: Vec::new(),
+            project: Project::default(),
+            sequence_index: 0,
+            project_path: None,
             is_playing: false,
             playback_status: PlaybackStatus::Idle,
-            converter: PhonemeConverter::new()?,
+            playing_segments: Vec::new(),
+            playing_sample_rate: 0,
+            cache_stats: synthesizer.cache_stats(),
+            worker: SynthWorker::spawn(converter, synthesizer),
+            jobs: Vec::new(),
             synth_config: SynthConfig::default(),
-            player: AudioPlayer::new()?,
+            config: Config::default(),
+            config_path: None,
+            player: Box::new(AudioPlayer::new()?),
+            playback: None,
+            recorder: AudioRecorder::new()?,
+            recording: None,
+            recognizer: None,
         })
     }
 
+    /// 再生先を差し替える（音声デバイスの無い環境では NullSink など）
+    pub fn with_audio_sink(mut self, sink: Box<dyn AudioSink>) -> Self {
+        self.player = sink;
+        self
+    }
+
+    /// 保存済みの設定を反映する。出力デバイスを選ぶと path に書き戻す
+    pub fn with_config(mut self, config: Config, path: Option<PathBuf>) -> Self {
+        self.player.set_device(config.output_device.clone());
+        self.config = config;
+        self.config_path = path;
+        self
+    }
+
+    /// キーに対応する音素をカーソル位置に入力する
     pub fn select_phoneme(&mut self, key: char) {
-        if let Some(phoneme) = self.phoneme_db.get_by_key(key) {
-            self.selected_phonemes.push(phoneme.clone());
+        if let Some(phoneme) = self.phoneme_db.get_by_key(key).cloned() {
+            self.insert_phoneme(phoneme);
         }
     }
 
+    /// 範囲選択中は範囲を置き換え、上書きモードではカーソル位置の音素を置き換える
+    pub fn insert_phoneme(&mut self, phoneme: Phoneme) {
+        let len = self.selected_phonemes.len();
+        let (range, label) = match self.selection() {
+            Some(range) => (range, format!("Replace with [{}]", phoneme.ipa)),
+            None if self.replace_mode && self.cursor < len => (
+                self.cursor..self.cursor + 1,
+                format!("Replace with [{}]", phoneme.ipa),
+            ),
+            None => (self.cursor..self.cursor, format!("Insert [{}]", phoneme.ipa)),
+        };
+        self.splice(range, vec![phoneme], label);
+    }
+
     pub fn delete_last_phoneme(&mut self) {
-        self.selected_phonemes.pop();
+        let len = self.selected_phonemes.len();
+        if len > 0 {
+            self.splice(len - 1..len, Vec::new(), "Delete");
+        }
+    }
+
+    /// Backspace: 範囲選択またはカーソルの前の音素を削除
+    pub fn delete_backward(&mut self) {
+        let range = match self.selection() {
+            Some(range) => range,
+            None if self.cursor > 0 => self.cursor - 1..self.cursor,
+            None => return,
+        };
+        self.splice(range, Vec::new(), "Delete");
+    }
+
+    /// Delete: 範囲選択またはカーソル位置の音素を削除
+    pub fn delete_forward(&mut self) {
+        let range = match self.selection() {
+            Some(range) => range,
+            None if self.cursor < self.selected_phonemes.len() => self.cursor..self.cursor + 1,
+            None => return,
+        };
+        self.splice(range, Vec::new(), "Delete");
+    }
+
+    /// 音素列をすべて削除する
+    pub fn clear_sequence(&mut self) {
+        let len = self.selected_phonemes.len();
+        self.splice(0..len, Vec::new(), "Clear");
+    }
+
+    /// カーソルを移動する（extend ならカーソルまでを範囲選択する）
+    pub fn move_cursor(&mut self, delta: isize, extend: bool) {
+        let target = self.cursor.saturating_add_signed(delta);
+        self.move_cursor_to(target, extend);
+    }
+
+    pub fn move_cursor_to(&mut self, position: usize, extend: bool) {
+        if extend {
+            self.selection_anchor.get_or_insert(self.cursor);
+        } else {
+            self.selection_anchor = None;
+        }
+        self.cursor = position.min(self.selected_phonemes.len());
+    }
+
+    /// 範囲選択中の音素の位置（空の範囲は None）
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.selection_anchor?;
+        let range = anchor.min(self.cursor)..anchor.max(self.cursor);
+        (!range.is_empty()).then_some(range)
+    }
+
+    pub fn toggle_replace_mode(&mut self) {
+        self.replace_mode = !self.replace_mode;
+    }
+
+    pub fn copy_selection(&mut self) {
+        match self.selection() {
+            Some(range) => {
+                self.clipboard = self.selected_phonemes[range].to_vec();
+                self.message = Some(format!("Copied {} phonemes", self.clipboard.len()));
+            }
+            None => self.message = Some("Nothing selected".to_string()),
+        }
+    }
+
+    pub fn cut_selection(&mut self) {
+        self.copy_selection();
+        if let Some(range) = self.selection() {
+            self.splice(range, Vec::new(), "Cut");
+        }
+    }
+
+    /// クリップボードの音素をカーソル位置に貼り付ける（範囲選択中は置き換え）
+    pub fn paste(&mut self) {
+        if self.clipboard.is_empty() {
+            return;
+        }
+        let range = self.selection().unwrap_or(self.cursor..self.cursor);
+        self.splice(range, self.clipboard.clone(), "Paste");
+    }
+
+    /// 範囲選択（なければカーソル位置の音素）を前後に1つ移動する
+    pub fn move_segment(&mut self, delta: isize) {
+        let len = self.selected_phonemes.len();
+        let range = match self.selection() {
+            Some(range) => range,
+            None if len == 0 => return,
+            None => {
+                let index = self.cursor.min(len - 1);
+                index..index + 1
+            }
+        };
+
+        let delta = delta.signum();
+        if (delta < 0 && range.start == 0) || (delta > 0 && range.end >= len) || delta == 0 {
+            return;
+        }
+        self.rotate(range.clone(), delta);
+        self.history.record("Move", Edit::Move { range, delta });
     }
 
+    /// カーソルの前の音素の長音・短音を切り替える（母音のみ）
+    pub fn toggle_length(&mut self) {
+        let Some(index) = self.cursor.checked_sub(1) else {
+            return;
+        };
+        let target = &self.selected_phonemes[index];
+        let toggled = self
+            .phoneme_db
+            .lengthen(target)
+            .or_else(|| self.inventory.shorten(target));
+
+        if let Some(phoneme) = toggled {
+            self.splice(index..index + 1, vec![phoneme], "Toggle length");
+        }
+    }
+
+    pub fn toggle_diphthong_policy(&mut self) {
+        let from = self.diphthong_policy;
+        let to = from.toggled();
+        self.diphthong_policy = to;
+        self.history.record(
+            format!("Diphthongs: {}", to.display_name()),
+            Edit::Diphthongs { from, to },
+        );
+    }
+
+    pub fn cycle_reversal_strategy(&mut self) {
+        let from = self.reversal_strategy;
+        let to = from.next();
+        self.reversal_strategy = to;
+        self.history.record(
+            format!("Strategy: {}", to.display_name()),
+            Edit::Strategy { from, to },
+        );
+    }
+
+    pub fn undo(&mut self) {
+        self.message = Some(match self.history.undo() {
+            Some(command) => {
+                self.apply_edit(&command.edit.inverse());
+                format!("Undo: {}", command.label)
+            }
+            None => "Nothing to undo".to_string(),
+        });
+    }
+
+    pub fn redo(&mut self) {
+        self.message = Some(match self.history.redo() {
+            Some(command) => {
+                self.apply_edit(&command.edit);
+                format!("Redo: {}", command.label)
+            }
+            None => "Nothing to redo".to_string(),
+        });
+    }
+
+    /// 音素列の range を inserted で置き換えて履歴に残す（音素列の編集はすべてここを通す）
+    fn splice(&mut self, range: Range<usize>, inserted: Vec<Phoneme>, label: impl Into<String>) {
+        if range.is_empty() && inserted.is_empty() {
+            return;
+        }
+        let edit = Edit::Splice {
+            index: range.start,
+            removed: self.selected_phonemes[range].to_vec(),
+            inserted,
+        };
+        self.apply_edit(&edit);
+        self.history.record(label, edit);
+    }
+
+    // 履歴に残さずに編集を適用する（undo/redo と各操作の共通部分）
+    fn apply_edit(&mut self, edit: &Edit) {
+        match edit {
+            Edit::Splice {
+                index,
+                removed,
+                inserted,
+            } => {
+                self.selected_phonemes
+                    .splice(*index..*index + removed.len(), inserted.iter().cloned());
+                self.cursor = index + inserted.len();
+                self.selection_anchor = None;
+            }
+            Edit::Move { range, delta } => self.rotate(range.clone(), *delta),
+            Edit::Strategy { to, .. } => self.reversal_strategy = *to,
+            Edit::Diphthongs { to, .. } => self.diphthong_policy = *to,
+            Edit::Language { to, .. } => self.set_language(*to),
+        }
+    }
+
+    // range の音素を1つ前後に移動し、選択範囲・カーソルを移動先に追従させる
+    fn rotate(&mut self, range: Range<usize>, delta: isize) {
+        if delta < 0 {
+            self.selected_phonemes[range.start - 1..range.end].rotate_left(1);
+        } else {
+            self.selected_phonemes[range.start..range.end + 1].rotate_right(1);
+        }
+
+        let start = range.start.saturating_add_signed(delta);
+        let moved = start..start + range.len();
+        if moved.len() > 1 {
+            self.selection_anchor = Some(moved.start);
+            self.cursor = moved.end;
+        } else {
+            self.selection_anchor = None;
+            self.cursor = moved.start;
+        }
+    }
+
+    /// 逆順結果（適用済みの修正を含む）
     pub fn get_reversed_phonemes(&self) -> Vec<Phoneme> {
-        self.selected_phonemes.iter().rev().cloned().collect()
+        let mut phonemes = self.reversed_with(self.reversal_strategy);
+        // 選択や方式が変わって逆順結果が変わったら修正は無効
+        if phonemes == self.repair_base {
+            for repair in &self.repairs {
+                repair.apply(&mut phonemes);
+            }
+        }
+        phonemes
+    }
+
+    /// 逆順結果のうち現在の言語で発音できない箇所
+    pub fn violations(&self) -> Vec<Violation> {
+        validate(
+            &self.get_reversed_phonemes(),
+            self.current_language(),
+            &self.phoneme_db,
+        )
+    }
+
+    /// 修正可能な最初の違反に修正を適用する
+    pub fn apply_fix(&mut self) {
+        let base = self.reversed_with(self.reversal_strategy);
+        if base != self.repair_base {
+            self.repair_base = base;
+            self.repairs.clear();
+        }
+
+        let fixable = self
+            .violations()
+            .into_iter()
+            .find_map(|v| v.repair.map(|repair| (v.index, v.kind, repair)));
+
+        self.message = Some(match fixable {
+            Some((index, kind, repair)) => {
+                let message = format!("Fixed #{} ({}): {}", index + 1, kind.display_name(), repair);
+                self.repairs.push(repair);
+                message
+            }
+            None => "No fixable violations".to_string(),
+        });
+    }
+
+    /// プレビューで全方式を並べて表示するための逆順化
+    pub fn reversed_with(&self, strategy: ReversalStrategy) -> Vec<Phoneme> {
+        reverse(&self.selected_phonemes, strategy, self.diphthong_policy)
     }
 
     pub fn toggle_view(&mut self) {
         self.current_view = match self.current_view {
             View::PhonemeSelection => View::Preview,
             View::Preview => View::PhonemeSelection,
+            View::Settings => self.settings_return,
+            View::Devices => self.devices_return,
         };
     }
 
+    /// 合成設定の画面を開く（閉じると元の画面に戻る）
+    pub fn open_settings(&mut self) {
+        if self.current_view != View::Settings {
+            self.settings_return = self.current_view;
+            self.current_view = View::Settings;
+        }
+    }
+
+    pub fn close_settings(&mut self) {
+        self.current_view = self.settings_return;
+    }
+
+    pub fn selected_setting(&self) -> SynthParam {
+        SynthParam::ALL[self.settings_cursor]
+    }
+
+    pub fn select_setting(&mut self, delta: isize) {
+        let count = SynthParam::ALL.len() as isize;
+        self.settings_cursor = (self.settings_cursor as isize + delta).rem_euclid(count) as usize;
+    }
+
+    /// 選択中の設定を steps 段階変更する（範囲の端で止まる）。次の合成から反映される
+    pub fn adjust_setting(&mut self, steps: i32) {
+        let param = self.selected_setting();
+        self.synth_config.adjust(param, steps);
+        self.message = Some(format!(
+            "{}: {}",
+            param.display_name(),
+            self.synth_config.value_text(param)
+        ));
+    }
+
+    pub fn synth_config(&self) -> &SynthConfig {
+        &self.synth_config
+    }
+
+    /// 出力デバイスの画面を開き、デバイスを列挙し直す（閉じると元の画面に戻る）
+    pub fn open_devices(&mut self) {
+        if self.current_view != View::Devices {
+            self.devices_return = self.current_view;
+            self.current_view = View::Devices;
+        }
+        self.devices = output_devices();
+        self.device_cursor = match self.player.device() {
+            Some(selected) => self
+                .devices
+                .iter()
+                .position(|info| &info.device == selected)
+                .map_or(0, |index| index + 1),
+            None => 0,
+        };
+    }
+
+    pub fn close_devices(&mut self) {
+        self.current_view = self.devices_return;
+    }
+
+    pub fn move_device_cursor(&mut self, delta: isize) {
+        let count = self.devices.len() as isize + 1;
+        self.device_cursor = (self.device_cursor as isize + delta).rem_euclid(count) as usize;
+    }
+
+    /// 選択中の行のデバイスを出力先にし、設定ファイルに保存して元の画面に戻る
+    pub fn choose_device(&mut self) {
+        let device = self
+            .device_cursor
+            .checked_sub(1)
+            .and_then(|index| self.devices.get(index))
+            .map(|info| info.device.clone());
+        let name = device
+            .as_ref()
+            .map_or("System default".to_string(), |d| d.to_string());
+        self.player.set_device(device.clone());
+        self.config.output_device = device;
+
+        self.message = Some(match &self.config_path {
+            Some(path) => match self.config.save(path) {
+                Ok(()) => format!("Output device: {} (saved)", name),
+                Err(e) => format!("Output device: {} (Error: {})", name, e),
+            },
+            None => format!("Output device: {}", name),
+        });
+        self.close_devices();
+    }
+
+    /// 再生に使う出力デバイス（None はシステムの既定）
+    pub fn output_device(&self) -> Option<&OutputDevice> {
+        self.player.device()
+    }
+
     pub fn quit(&mut self) {
         self.should_quit = true;
     }
 
+    pub fn open_input(&mut self, kind: InputKind) {
+        self.input = Some(InputPrompt {
+            kind,
+            buffer: String::new(),
+        });
+    }
+
+    pub fn cancel_input(&mut self) {
+        self.input = None;
+    }
+
+    pub fn submit_input(&mut self) -> Result<()> {
+        let Some(prompt) = self.input.take() else {
+            return Ok(());
+        };
+        let text = prompt.buffer.trim();
+
+        match prompt.kind {
+            InputKind::ImportAudio => self.import_audio(Path::new(text)),
+            InputKind::Kana => {
+                self.input_text(text);
+                Ok(())
+            }
+            InputKind::EnglishText => self.input_english(text),
+            InputKind::SaveProject => self.save_project(Path::new(text)),
+            InputKind::OpenProject => self.open_project(Path::new(text)),
+        }
+    }
+
+    /// プロジェクトの保存・読み込みのプロンプトを開く（前回のパスを入力済みにする）
+    pub fn open_project_input(&mut self, kind: InputKind) {
+        self.open_input(kind);
+        if let (Some(prompt), Some(path)) = (self.input.as_mut(), &self.project_path) {
+            prompt.buffer = path.display().to_string();
+        }
+    }
+
+    /// 現在の言語に応じたテキスト入力（日本語: かな・ローマ字、英語: 英文）を開く
+    pub fn open_text_input(&mut self) {
+        self.open_input(match self.current_language() {
+            Language::Japanese => InputKind::Kana,
+            Language::English => InputKind::EnglishText,
+        });
+    }
+
+    /// かな・ローマ字を音素に変換して選択中の音素列に追加する
+    pub fn input_text(&mut self, text: &str) {
+        // かなは常に日本語の音素にする（英語表示中は n/a として残る）
+        let japanese = self.inventory.for_language(Language::Japanese);
+        self.append_mapping(kana::to_phonemes(text, &japanese));
+    }
+
+    /// 英文を espeak-ng で音素に変換して選択中の音素列に追加する
+    pub fn input_english(&mut self, text: &str) -> Result<()> {
+        let english = self.inventory.for_language(Language::English);
+        let mapping = english_to_phonemes(text, &english)?;
+        self.append_mapping(mapping);
+        Ok(())
+    }
+
+    fn append_mapping(&mut self, mapping: PhonemeMapping) {
+        self.message = Some(format!(
+            "Added {} phonemes ({} unknown)",
+            mapping.phonemes.len(),
+            mapping.unknown.len()
+        ));
+        let range = self.selection().unwrap_or(self.cursor..self.cursor);
+        self.splice(range, mapping.phonemes, "Text input");
+        self.unknown_symbols = mapping.unknown;
+    }
+
+    /// WAVファイルの音素を認識し、選択中の音素列を置き換える
+    pub fn import_audio(&mut self, path: &Path) -> Result<()> {
+        // 認識器を起動する前にWAVとして読めるか確認する
+        read_wav(path)?;
+
+        if self.recognizer.is_none() {
+            self.recognizer = Some(PhonemeRecognizer::new()?);
+        }
+        let symbols = self.recognizer.as_ref().unwrap().recognize(path)?;
+
+        self.apply_recognized(&symbols);
+        Ok(())
+    }
+
+    /// 編集中のシーケンスを書き戻してプロジェクトをファイルに保存する
+    pub fn save_project(&mut self, path: &Path) -> Result<()> {
+        self.store_sequence();
+        self.project.save(path)?;
+        self.project_path = Some(path.to_path_buf());
+        self.message = Some(format!(
+            "Saved {} sequence(s) to {}",
+            self.project.sequences.len(),
+            path.display()
+        ));
+        Ok(())
+    }
+
+    /// プロジェクトを読み込んで最初のシーケンスを編集対象にする
+    pub fn open_project(&mut self, path: &Path) -> Result<()> {
+        self.project = Project::load(path)?;
+        self.project_path = Some(path.to_path_buf());
+        if self.project.sequences.is_empty() {
+            self.store_sequence_at(0, Vec::new());
+        }
+        self.load_sequence(0);
+        self.message = Some(format!(
+            "Opened {} ({} sequence(s))",
+            path.display(),
+            self.project.sequences.len()
+        ));
+        Ok(())
+    }
+
+    /// 空のシーケンスを追加して編集対象にする（言語・逆順化の設定は引き継ぐ）
+    pub fn new_sequence(&mut self) {
+        self.store_sequence();
+        let index = self.project.sequences.len();
+        self.store_sequence_at(index, Vec::new());
+        self.load_sequence(index);
+        self.message = Some(format!("New sequence: {}", self.sequence_name()));
+    }
+
+    /// 前後のシーケンスに切り替える（端では反対側に回り込む）
+    pub fn switch_sequence(&mut self, delta: isize) {
+        self.store_sequence();
+        let count = self.project.sequences.len() as isize;
+        let index = (self.sequence_index as isize + delta).rem_euclid(count) as usize;
+        self.load_sequence(index);
+        self.message = Some(format!(
+            "Sequence {}/{}: {}",
+            index + 1,
+            count,
+            self.sequence_name()
+        ));
+    }
+
+    pub fn sequence_name(&self) -> String {
+        self.project.sequences.get(self.sequence_index).map_or_else(
+            || format!("Sequence {}", self.sequence_index + 1),
+            |sequence| sequence.name.clone(),
+        )
+    }
+
+    /// (編集中のシーケンスの番号, シーケンス数)。番号は1始まり
+    pub fn sequence_position(&self) -> (usize, usize) {
+        let count = self.project.sequences.len().max(self.sequence_index + 1);
+        (self.sequence_index + 1, count)
+    }
+
+    fn store_sequence(&mut self) {
+        let phonemes = self.selected_phonemes.clone();
+        self.store_sequence_at(self.sequence_index, phonemes);
+    }
+
+    // 現在の設定で index のシーケンスを書き換える（末尾なら追加）
+    fn store_sequence_at(&mut self, index: usize, phonemes: Vec<Phoneme>) {
+        let name = match self.project.sequences.get(index) {
+            Some(sequence) => sequence.name.clone(),
+            None => format!("Sequence {}", index + 1),
+        };
+        let sequence = Sequence {
+            strategy: self.reversal_strategy,
+            diphthongs: self.diphthong_policy,
+            ..Sequence::new(name, &phonemes, &self.synth_config)
+        };
+        match self.project.sequences.get_mut(index) {
+            Some(slot) => *slot = sequence,
+            None => self.project.sequences.push(sequence),
+        }
+    }
+
+    // シーケンスの音素と設定を読み込む（履歴はシーケンスごとなので消す）
+    fn load_sequence(&mut self, index: usize) {
+        let sequence = self.project.sequences[index].clone();
+        let mapping = map_to_database(&sequence.phonemes, &self.inventory);
+
+        self.sequence_index = index;
+        self.selected_phonemes = mapping.phonemes;
+        self.unknown_symbols = mapping.unknown;
+        self.cursor = self.selected_phonemes.len();
+        self.selection_anchor = None;
+        self.history = History::default();
+        self.reversal_strategy = sequence.strategy;
+        self.diphthong_policy = sequence.diphthongs;
+        self.synth_config = sequence.synth_config();
+        self.set_language(sequence.language);
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// 録音の開始・停止。停止時は録音を認識して音素列を置き換える
+    pub fn toggle_recording(&mut self) -> Result<()> {
+        let Some(handle) = self.recording.take() else {
+            self.stop_playback();
+            self.recording = Some(self.recorder.start()?);
+            self.message = Some("Recording...".to_string());
+            return Ok(());
+        };
+
+        let audio = handle.stop()?;
+        if audio.samples.is_empty() {
+            self.message = Some("Recording is empty".to_string());
+            return Ok(());
+        }
+
+        // 認識器はファイルパスを受け取るため一時WAVに書き出す
+        let file = tempfile::Builder::new().suffix(".wav").tempfile()?;
+        write_wav(file.path(), &audio)?;
+        self.import_audio(file.path())
+    }
+
+    /// 録音中なら (入力レベル, 録音時間)
+    pub fn recording_level(&self) -> Option<(f32, Duration)> {
+        self.recording
+            .as_ref()
+            .map(|handle| (handle.level(), handle.duration()))
+    }
+
+    /// 認識結果をデータベースの音素に対応付けて選択中の音素列にする
+    /// （シーケンスの読み込みと同じく全言語の inventory で対応付け、現在の言語に
+    /// ない音素は選択に残したまま知らせる）
+    pub fn apply_recognized(&mut self, symbols: &[String]) {
+        let mapping = map_to_database(symbols, &self.inventory);
+        let unavailable = mapping.phonemes.iter().filter(|p| !self.is_available(p)).count();
+
+        self.message = Some(format!(
+            "Imported {} phonemes ({} unknown, {} not in the {} inventory)",
+            mapping.phonemes.len(),
+            mapping.unknown.len(),
+            unavailable,
+            self.synth_config.language.display_name()
+        ));
+        let len = self.selected_phonemes.len();
+        self.splice(0..len, mapping.phonemes, "Import");
+        self.unknown_symbols = mapping.unknown;
+
+        if !self.selected_phonemes.is_empty() {
+            self.current_view = View::Preview;
+        }
+    }
+
     pub fn toggle_language(&mut self) {
-        self.synth_config.language = match self.synth_config.language {
+        let from = self.synth_config.language;
+        let to = match from {
             Language::Japanese => Language::English,
             Language::English => Language::Japanese,
         };
+        self.set_language(to);
+        self.history.record(
+            format!("Language: {}", to.display_name()),
+            Edit::Language { from, to },
+        );
+    }
+
+    fn set_language(&mut self, language: Language) {
+        self.synth_config.language = language;
+        self.phoneme_db = self.inventory.for_language(self.synth_config.language);
+
+        // 新しい言語にない音素は選択に残したまま知らせる
+        let unavailable = self
+            .selected_phonemes
+            .iter()
+            .filter(|p| !self.is_available(p))
+            .count();
+        self.message = (unavailable > 0).then(|| {
+            format!(
+                "{} selected phoneme(s) not in the {} inventory",
+                unavailable,
+                self.synth_config.language.display_name()
+            )
+        });
+    }
+
+    /// 現在の言語のインベントリに含まれる音素か
+    pub fn is_available(&self, phoneme: &Phoneme) -> bool {
+        self.phoneme_db.find_by_ipa(&phoneme.ipa).is_some()
     }
 
     pub fn current_language(&self) -> Language {
         self.synth_config.language
     }
 
-    pub fn play_original(&mut self) -> Result<()> {
-        if self.selected_phonemes.is_empty() {
-            self.playback_status = PlaybackStatus::Error("No phonemes selected".to_string());
-            return Ok(());
-        }
+    pub fn synthesizer_name(&self) -> &'static str {
+        self.worker.synthesizer_name()
+    }
 
-        self.playback_status = PlaybackStatus::Synthesizing;
+    pub fn audio_sink_name(&self) -> &'static str {
+        self.player.name()
+    }
 
-        // 音素列をIPA文字列のVecに変換
-        let ipa_phonemes: Vec<String> = self.selected_phonemes.iter().map(|p| p.ipa.clone()).collect();
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache_stats
+    }
 
-        // IPA → eSpeak変換
-        let espeak_phonemes = self.converter.convert_ipa_to_espeak(&ipa_phonemes)?;
+    /// 元の音素列の合成をワーカーに依頼する（結果は tick で再生される）
+    pub fn play_original(&mut self) {
+        let render = Render::original(&self.selected_phonemes);
+        self.queue_playback(render);
+    }
 
-        // 一時ファイルに合成
-        let temp_file = NamedTempFile::new()?;
-        let temp_path = temp_file.path();
+    pub fn play_reversed(&mut self) {
+        self.play_mode(ReversalMode::Phoneme);
+    }
 
-        synthesize_phonemes(&espeak_phonemes, temp_path, &self.synth_config)?;
+    /// 元の音声の波形をそのまま反転して再生する（音素の逆順との比較用）
+    pub fn play_waveform_reversed(&mut self) {
+        self.play_mode(ReversalMode::Waveform);
+    }
 
-        // 音声を読み込んで再生
-        self.playback_status = PlaybackStatus::Playing;
-        let audio = crate::audio::read_wav(temp_path)?;
-        self.player.play(&audio)?;
+    /// 元の音素を1つずつ合成し、逆の順につないで再生する（調音結合の影響を受けない）
+    pub fn play_concatenative(&mut self) {
+        self.play_mode(ReversalMode::Concatenative);
+    }
 
-        self.playback_status = PlaybackStatus::Idle;
-        Ok(())
+    /// クロスフェードの長さを 5ms 単位で変更する
+    pub fn adjust_crossfade(&mut self, steps: i32) {
+        let ms = (self.crossfade.as_millis() as i64 + i64::from(steps) * 5)
+            .clamp(0, MAX_CROSSFADE.as_millis() as i64);
+        self.crossfade = Duration::from_millis(ms as u64);
+        self.message = Some(format!("Crossfade: {} ms", ms));
     }
 
-    pub fn play_reversed(&mut self) -> Result<()> {
-        if self.selected_phonemes.is_empty() {
-            self.playback_status = PlaybackStatus::Error("No phonemes selected".to_string());
-            return Ok(());
-        }
+    /// 再生中の音素の位置（音素境界が分かる再生のみ）
+    pub fn playing_segment(&self) -> Option<usize> {
+        let (position, _) = self.playback_progress()?;
+        segment_at(&self.playing_segments, self.playing_sample_rate, position)
+    }
 
-        self.playback_status = PlaybackStatus::Synthesizing;
+    pub fn playing_segments(&self) -> &[Segment] {
+        &self.playing_segments
+    }
 
-        // 逆順音素列をIPA文字列のVecに変換
-        let reversed_phonemes = self.get_reversed_phonemes();
-        let ipa_phonemes: Vec<String> = reversed_phonemes.iter().map(|p| p.ipa.clone()).collect();
+    /// 元の音声 → 波形の反転 → 音素の逆順を続けて再生する
+    pub fn play_demo(&mut self) {
+        self.play_mode(ReversalMode::Demo);
+    }
 
-        // IPA → eSpeak変換
-        let espeak_phonemes = self.converter.convert_ipa_to_espeak(&ipa_phonemes)?;
+    /// A/B 比較: 音素の逆順と波形の反転を切り替えて再生する
+    pub fn toggle_ab(&mut self) {
+        self.ab_mode = self.ab_mode.ab_toggled();
+        self.message = Some(format!("A/B: {}", self.ab_mode.display_name()));
+        self.play_mode(self.ab_mode);
+    }
 
-        // 一時ファイルに合成
-        let temp_file = NamedTempFile::new()?;
-        let temp_path = temp_file.path();
+    pub fn play_mode(&mut self, mode: ReversalMode) {
+        let render = self.reversed_render(mode);
+        self.queue_playback(render);
+    }
 
-        synthesize_phonemes(&espeak_phonemes, temp_path, &self.synth_config)?;
+    pub fn toggle_pause(&mut self) {
+        if let Some(handle) = &self.playback {
+            if handle.is_paused() {
+                handle.resume();
+                self.playback_status = PlaybackStatus::Playing;
+            } else {
+                handle.pause();
+                self.playback_status = PlaybackStatus::Paused;
+            }
+        }
+    }
 
-        // 音声を読み込んで再生
-        self.playback_status = PlaybackStatus::Playing;
-        let audio = crate::audio::read_wav(temp_path)?;
-        self.player.play(&audio)?;
+    /// 再生を止め、待機中・合成中の再生ジョブも取り消す（保存は取り消さない）
+    pub fn stop_playback(&mut self) {
+        let cancelled = self.cancel_jobs(|kind| *kind == JobKind::Play);
+        if cancelled > 0 {
+            self.message = Some(format!("Cancelled {} job(s)", cancelled));
+            self.playback_status = PlaybackStatus::Idle;
+        }
+        if let Some(handle) = self.playback.take() {
+            handle.stop();
+            self.playback_status = PlaybackStatus::Idle;
+        }
+        self.playing_segments.clear();
+        self.is_playing = false;
+    }
 
-        self.playback_status = PlaybackStatus::Idle;
-        Ok(())
+    /// 合成待ちのジョブがあるか
+    pub fn is_busy(&self) -> bool {
+        !self.jobs.is_empty()
     }
 
-    pub fn save_reversed(&mut self) -> Result<String> {
-        if self.selected_phonemes.is_empty() {
-            self.playback_status = PlaybackStatus::Error("No phonemes selected".to_string());
-            return Err(crate::error::PhonemeReverserError::Synthesis(
-                "No phonemes to save".to_string(),
-            )
-            .into());
+    /// メインループから毎フレーム呼び出し、合成ジョブと再生の進行を状態に反映する
+    pub fn tick(&mut self) {
+        if let Some(error) = self.recording.as_ref().and_then(|handle| handle.error()) {
+            self.recording = None;
+            self.message = Some(format!("Error: Recording failed: {}", error));
         }
 
-        self.playback_status = PlaybackStatus::Synthesizing;
+        while let Some(event) = self.worker.try_recv() {
+            self.handle_worker_event(event);
+        }
 
-        // 逆順音素列をIPA文字列のVecに変換
-        let reversed_phonemes = self.get_reversed_phonemes();
-        let ipa_phonemes: Vec<String> = reversed_phonemes.iter().map(|p| p.ipa.clone()).collect();
+        let Some(handle) = &self.playback else {
+            return;
+        };
 
-        // IPA → eSpeak変換
-        let espeak_phonemes = self.converter.convert_ipa_to_espeak(&ipa_phonemes)?;
+        if handle.is_finished() {
+            self.playback_status = match handle.error() {
+                Some(e) => PlaybackStatus::Error(e),
+                None => self.next_status(PlaybackStatus::Done),
+            };
+            self.playback = None;
+            self.playing_segments.clear();
+            self.is_playing = false;
+        }
+    }
+
+    /// 再生中なら (再生位置, 全体の長さ)
+    pub fn playback_progress(&self) -> Option<(Duration, Duration)> {
+        self.playback
+            .as_ref()
+            .map(|handle| (handle.position(), handle.duration()))
+    }
+
+    /// A/B で選択中の逆順音声を wav/ にタイムスタンプ名で保存するジョブを投入し、保存先を返す
+    pub fn save_reversed(&mut self) -> Result<String> {
+        self.save_mode(self.ab_mode)
+    }
 
+    pub fn save_demo(&mut self) -> Result<String> {
+        self.save_mode(ReversalMode::Demo)
+    }
+
+    fn save_mode(&mut self, mode: ReversalMode) -> Result<String> {
         // wav/ディレクトリを作成（存在しない場合）
         let wav_dir = PathBuf::from("wav");
         std::fs::create_dir_all(&wav_dir)?;
 
-        // タイムスタンプ形式のファイル名を生成
+        // タイムスタンプ形式のファイル名を生成（音素の逆順以外は方式を付ける）
         let timestamp = Local::now().format("%Y%m%d%H%M%S").to_string();
-        let filename = format!("{}.wav", timestamp);
-        let output_path = wav_dir.join(&filename);
+        let filename = match mode {
+            ReversalMode::Phoneme => format!("{}.wav", timestamp),
+            ReversalMode::Waveform => format!("{}-waveform.wav", timestamp),
+            ReversalMode::Concatenative => format!("{}-concat.wav", timestamp),
+            ReversalMode::Demo => format!("{}-demo.wav", timestamp),
+        };
+        self.save_to(&wav_dir.join(&filename), mode)?;
+        Ok(format!("wav/{}", filename))
+    }
+
+    pub fn save_to(&mut self, path: &Path, mode: ReversalMode) -> Result<()> {
+        if self.selected_phonemes.is_empty() {
+            self.playback_status = PlaybackStatus::Error("No phonemes selected".to_string());
+            return Err(PhonemeReverserError::Synthesis("No phonemes to save".to_string()));
+        }
 
-        // 音声合成
-        synthesize_phonemes(&espeak_phonemes, &output_path, &self.synth_config)?;
+        let render = self.reversed_render(mode);
+        self.submit(render, JobKind::Save(path.to_path_buf()))
+    }
 
-        self.playback_status = PlaybackStatus::Idle;
-        Ok(format!("wav/{}", filename))
+    fn reversed_render(&self, mode: ReversalMode) -> Render {
+        Render::reversed(
+            mode,
+            &self.selected_phonemes,
+            &self.get_reversed_phonemes(),
+            self.crossfade,
+        )
+    }
+
+    fn queue_playback(&mut self, render: Render) {
+        if self.selected_phonemes.is_empty() {
+            self.playback_status = PlaybackStatus::Error("No phonemes selected".to_string());
+            return;
+        }
+
+        // 新しい再生の依頼は、まだ再生されていない前の依頼を置き換える
+        self.cancel_jobs(|kind| *kind == JobKind::Play);
+        if let Err(e) = self.submit(render, JobKind::Play) {
+            self.playback_status = PlaybackStatus::Error(e.to_string());
+        }
+    }
+
+    fn submit(&mut self, render: Render, kind: JobKind) -> Result<()> {
+        let handle = self.worker.submit(render, &self.synth_config, kind)?;
+        self.jobs.push(handle);
+        // 再生中は再生の表示を優先する
+        if self.playback.is_none() && !self.is_synthesizing() {
+            self.playback_status = PlaybackStatus::Queued;
+        }
+        Ok(())
+    }
+
+    /// 条件に合うジョブを取り消し、その数を返す
+    fn cancel_jobs(&mut self, matches: impl Fn(&JobKind) -> bool) -> usize {
+        let before = self.jobs.len();
+        self.jobs.retain(|job| {
+            if matches(&job.kind) {
+                job.cancel();
+                false
+            } else {
+                true
+            }
+        });
+        before - self.jobs.len()
+    }
+
+    fn handle_worker_event(&mut self, event: WorkerEvent) {
+        match event {
+            WorkerEvent::Started(id) => {
+                if self.playback.is_none() && self.jobs.iter().any(|job| job.id == id) {
+                    self.playback_status = PlaybackStatus::Synthesizing;
+                }
+            }
+            WorkerEvent::Finished {
+                id,
+                outcome,
+                cache_stats,
+            } => {
+                self.cache_stats = cache_stats;
+                // 取り消し済みのジョブの結果は捨てる
+                let Some(position) = self.jobs.iter().position(|job| job.id == id) else {
+                    return;
+                };
+                self.jobs.remove(position);
+
+                match outcome {
+                    Ok(JobOutput::Audio(segmented)) => {
+                        match self.start_playback(&segmented.audio) {
+                            Ok(()) => {
+                                self.playing_segments = segmented.segments;
+                                self.playing_sample_rate = segmented.audio.sample_rate;
+                            }
+                            Err(e) => self.playback_status = PlaybackStatus::Error(e.to_string()),
+                        }
+                    }
+                    Ok(JobOutput::Saved(path)) => {
+                        self.message = Some(format!("Saved {}", path.display()));
+                        if self.playback.is_none() {
+                            self.playback_status = self.next_status(PlaybackStatus::Done);
+                        }
+                    }
+                    Err(e) => self.playback_status = PlaybackStatus::Error(e.to_string()),
+                }
+            }
+            WorkerEvent::Cancelled(id) => self.jobs.retain(|job| job.id != id),
+        }
+    }
+
+    // 完了後もジョブが残っていれば順番待ちの表示に戻す
+    fn next_status(&self, done: PlaybackStatus) -> PlaybackStatus {
+        if self.jobs.is_empty() {
+            done
+        } else {
+            PlaybackStatus::Queued
+        }
+    }
+
+    fn is_synthesizing(&self) -> bool {
+        self.playback_status == PlaybackStatus::Synthesizing
+    }
+
+    fn start_playback(&mut self, audio: &AudioData) -> Result<()> {
+        if let Some(handle) = self.playback.take() {
+            handle.stop();
+        }
+        self.playing_segments.clear();
+        self.playback = Some(self.player.play(audio)?);
+        self.is_playing = true;
+        self.playback_status = PlaybackStatus::Playing;
+        Ok(())
     }
 }
 
@@ -240,6 +1222,112 @@ mod tests {
         assert_eq!(app.selected_phonemes[0].ipa, "a");
     }
 
+    fn ipa_of(app: &App) -> String {
+        app.selected_phonemes.iter().map(|p| p.ipa.as_str()).collect()
+    }
+
+    #[test]
+    fn test_cursor_insert_and_delete() {
+        let mut app = App::new().unwrap();
+        for key in ['k', 'a', 'i'] {
+            app.select_phoneme(key);
+        }
+        assert_eq!(app.cursor, 3);
+
+        // 途中に挿入
+        app.move_cursor(-1, false);
+        app.select_phoneme('m');
+        assert_eq!(ipa_of(&app), "kami");
+        assert_eq!(app.cursor, 3);
+
+        app.delete_forward();
+        assert_eq!(ipa_of(&app), "kam");
+        app.move_cursor_to(1, false);
+        app.delete_backward();
+        assert_eq!(ipa_of(&app), "am");
+        assert_eq!(app.cursor, 0);
+
+        // 先頭での Backspace・末尾での Delete は何もしない
+        app.delete_backward();
+        app.move_cursor(10, false);
+        app.delete_forward();
+        assert_eq!(ipa_of(&app), "am");
+        assert_eq!(app.cursor, 2);
+    }
+
+    #[test]
+    fn test_replace_mode() {
+        let mut app = App::new().unwrap();
+        for key in ['k', 'a'] {
+            app.select_phoneme(key);
+        }
+        app.toggle_replace_mode();
+        app.move_cursor_to(0, false);
+        app.select_phoneme('t');
+        app.select_phoneme('o');
+        // 末尾では追加になる
+        app.select_phoneme('k');
+        assert_eq!(ipa_of(&app), "tok");
+    }
+
+    #[test]
+    fn test_selection_copy_cut_paste() {
+        let mut app = App::new().unwrap();
+        for key in ['k', 'a', 'm', 'i'] {
+            app.select_phoneme(key);
+        }
+        app.move_cursor_to(0, false);
+        app.move_cursor(2, true);
+        assert_eq!(app.selection(), Some(0..2));
+
+        app.copy_selection();
+        app.move_cursor_to(4, false);
+        assert_eq!(app.selection(), None);
+        app.paste();
+        assert_eq!(ipa_of(&app), "kamika");
+        assert_eq!(app.cursor, 6);
+
+        app.move_cursor_to(2, false);
+        app.move_cursor(2, true);
+        app.cut_selection();
+        assert_eq!(ipa_of(&app), "kaka");
+        assert_eq!(app.cursor, 2);
+
+        // 範囲選択中の入力は範囲を置き換える
+        app.move_cursor(2, true);
+        app.select_phoneme('o');
+        assert_eq!(ipa_of(&app), "kao");
+
+        app.copy_selection();
+        assert_eq!(app.message.as_deref(), Some("Nothing selected"));
+    }
+
+    #[test]
+    fn test_move_segment() {
+        let mut app = App::new().unwrap();
+        for key in ['k', 'a', 'm', 'i'] {
+            app.select_phoneme(key);
+        }
+        // カーソル位置の音素を移動
+        app.move_cursor_to(2, false);
+        app.move_segment(-1);
+        assert_eq!(ipa_of(&app), "kmai");
+        assert_eq!(app.cursor, 1);
+
+        // 範囲選択を移動
+        app.move_cursor_to(2, false);
+        app.move_cursor(2, true);
+        app.move_segment(-1);
+        assert_eq!(ipa_of(&app), "kaim");
+        assert_eq!(app.selection(), Some(1..3));
+
+        // 端では動かない
+        app.move_segment(1);
+        app.move_segment(1);
+        assert_eq!(ipa_of(&app), "kmai");
+        assert_eq!(app.selection(), Some(2..4));
+    }
+
     #[test]
     fn test_delete_from_empty() {
         let mut app = App::new().unwrap();
@@ -289,6 +1377,199 @@ mod tests {
         assert_eq!(app.current_language(), Language::Japanese);
     }
 
+    #[test]
+    fn test_apply_recognized_fills_selection() {
+        let mut app = App::new().unwrap();
+        let symbols: Vec<String> = ["k", "a", "ʘ", "ɾ", "i"].iter().map(|s| s.to_string()).collect();
+        app.apply_recognized(&symbols);
+
+        let ipa: Vec<&str> = app.selected_phonemes.iter().map(|p| p.ipa.as_str()).collect();
+        assert_eq!(ipa, vec!["k", "a", "r", "i"]);
+        assert_eq!(app.unknown_symbols.len(), 1);
+        assert_eq!(app.unknown_symbols[0].symbol, "ʘ");
+        assert_eq!(app.current_view, View::Preview);
+    }
+
+    #[test]
+    fn test_apply_recognized_matches_loaded_sequences() {
+        // 英語表示中の日本語の音素は、読み込み時と同じく未知ではなく言語外として扱う
+        let mut app = App::new().unwrap();
+        app.toggle_language();
+        let symbols: Vec<String> = ["k", "ɯ", "ɕ", "i"].iter().map(|s| s.to_string()).collect();
+        app.apply_recognized(&symbols);
+
+        let ipa: Vec<&str> = app.selected_phonemes.iter().map(|p| p.ipa.as_str()).collect();
+        assert_eq!(ipa, vec!["k", "ɯ", "ɕ", "i"]);
+        assert!(app.unknown_symbols.is_empty());
+        assert!(!app.is_available(&app.selected_phonemes[1]));
+        assert!(!app.is_available(&app.selected_phonemes[2]));
+        assert!(app.message.as_deref().unwrap().contains("2 not in the English inventory"));
+    }
+
+    #[test]
+    fn test_import_audio_rejects_missing_file() {
+        let mut app = App::new().unwrap();
+        app.open_input(InputKind::ImportAudio);
+        app.input.as_mut().unwrap().buffer.push_str("nonexistent.wav");
+
+        assert!(app.submit_input().is_err());
+        assert!(app.input.is_none());
+        assert!(app.selected_phonemes.is_empty());
+    }
+
+    #[test]
+    fn test_not_recording_by_default() {
+        let app = App::new().unwrap();
+        assert!(!app.is_recording());
+        assert!(app.recording_level().is_none());
+    }
+
+    #[test]
+    fn test_playback_controls_without_playback() {
+        let mut app = App::new().unwrap();
+        app.toggle_pause();
+        app.stop_playback();
+        app.tick();
+
+        assert_eq!(app.playback_status, PlaybackStatus::Idle);
+        assert!(app.playback_progress().is_none());
+        assert!(!app.is_playing);
+    }
+
+    fn test_app() -> App {
+        App::with_backends(
+            PhonemeDatabase::new(),
+            PhonemeConverter::new().unwrap(),
+            Box::new(crate::synth::TestSynthesizer),
+        )
+        .unwrap()
+    }
+
+    fn select_ipa(app: &mut App, symbols: &[&str]) {
+        app.selected_phonemes = symbols
+            .iter()
+            .map(|ipa| app.phoneme_db.find_by_ipa(ipa).unwrap().clone())
+            .collect();
+    }
+
+    // ワーカーの結果が届くまで tick を繰り返す
+    fn wait_for_jobs(app: &mut App) {
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while app.is_busy() {
+            assert!(std::time::Instant::now() < deadline, "worker did not finish");
+            std::thread::sleep(Duration::from_millis(1));
+            app.tick();
+        }
+    }
+
+    #[test]
+    fn test_save_runs_on_worker() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reversed.wav");
+        let mut app = test_app();
+        select_ipa(&mut app, &["k", "a", "m", "i"]);
+
+        app.save_to(&path, ReversalMode::Phoneme).unwrap();
+        assert_eq!(app.playback_status, PlaybackStatus::Queued);
+        assert!(!path.exists() || app.is_busy());
+
+        wait_for_jobs(&mut app);
+        assert_eq!(app.playback_status, PlaybackStatus::Done);
+        assert!(path.exists());
+        assert!(app.message.as_deref().unwrap().starts_with("Saved "));
+    }
+
+    #[test]
+    fn test_stop_cancels_pending_playback_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("kept.wav");
+        let mut app = test_app();
+        select_ipa(&mut app, &["a", "i"]);
+
+        app.play_reversed();
+        app.play_original();
+        app.save_to(&path, ReversalMode::Phoneme).unwrap();
+        assert!(app.is_busy());
+
+        app.stop_playback();
+        assert_eq!(app.playback_status, PlaybackStatus::Idle);
+        assert_eq!(app.message.as_deref(), Some("Cancelled 1 job(s)"));
+
+        // 保存は取り消さず、取り消した再生の結果は反映されない
+        assert!(app.is_busy());
+        wait_for_jobs(&mut app);
+        assert!(path.exists());
+        assert!(!app.is_playing);
+        assert!(app.playback.is_none());
+    }
+
+    #[test]
+    fn test_ab_toggle_and_demo_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_app();
+        select_ipa(&mut app, &["k", "a", "m", "i"]);
+
+        app.toggle_ab();
+        assert_eq!(app.ab_mode, ReversalMode::Waveform);
+        assert_eq!(app.message.as_deref(), Some("A/B: Waveform-reversed"));
+        app.toggle_ab();
+        assert_eq!(app.ab_mode, ReversalMode::Phoneme);
+        app.stop_playback();
+
+        let single = dir.path().join("waveform.wav");
+        let demo = dir.path().join("demo.wav");
+        app.save_to(&single, ReversalMode::Waveform).unwrap();
+        app.save_to(&demo, ReversalMode::Demo).unwrap();
+        wait_for_jobs(&mut app);
+
+        let single = read_wav(&single).unwrap();
+        let demo = read_wav(&demo).unwrap();
+        assert!(demo.samples.len() > 2 * single.samples.len());
+    }
+
+    #[test]
+    fn test_crossfade_and_concatenative_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_app();
+        select_ipa(&mut app, &["k", "a"]);
+
+        app.adjust_crossfade(-100);
+        assert_eq!(app.crossfade, Duration::ZERO);
+        app.adjust_crossfade(100);
+        assert_eq!(app.crossfade, MAX_CROSSFADE);
+        assert_eq!(app.message.as_deref(), Some("Crossfade: 100 ms"));
+
+        let path = dir.path().join("concat.wav");
+        app.save_to(&path, ReversalMode::Concatenative).unwrap();
+        wait_for_jobs(&mut app);
+        assert!(!read_wav(&path).unwrap().samples.is_empty());
+        assert!(app.playing_segment().is_none());
+    }
+
+    #[test]
+    fn test_play_without_phonemes_is_an_error() {
+        let mut app = test_app();
+        app.play_reversed();
+        assert!(matches!(app.playback_status, PlaybackStatus::Error(_)));
+        assert!(!app.is_busy());
+    }
+
+    #[test]
+    fn test_app_uses_injected_synthesizer() {
+        let app = App::with_backends(
+            PhonemeDatabase::new(),
+            PhonemeConverter::new().unwrap(),
+            Box::new(crate::synth::TestSynthesizer),
+        )
+        .unwrap();
+        assert_eq!(app.synthesizer_name(), "test");
+        assert!(app.cache_stats().is_none());
+        // 既定のバックエンドはキャッシュ付き
+        let app = App::new().unwrap();
+        assert_eq!(app.synthesizer_name(), "espeak-ng");
+        assert_eq!(app.cache_stats().unwrap().entries, 0);
+    }
+
     #[test]
     fn test_toggle_language() {
         let mut app = App::new().unwrap();
@@ -300,4 +1581,309 @@ mod tests {
         app.toggle_language();
         assert_eq!(app.current_language(), Language::Japanese);
     }
+
+    #[test]
+    fn test_toggle_length() {
+        let mut app = App::new().unwrap();
+        app.toggle_length();
+        assert!(app.selected_phonemes.is_empty());
+
+        app.select_phoneme('k');
+        app.toggle_length();
+        assert_eq!(app.selected_phonemes[0].ipa, "k", "Consonants have no long form");
+
+        app.select_phoneme('a');
+        app.toggle_length();
+        assert_eq!(app.selected_phonemes[1].ipa, "aː");
+        assert_eq!(app.selected_phonemes[1].espeak, "a:");
+
+        app.toggle_length();
+        assert_eq!(app.selected_phonemes[1].ipa, "a");
+    }
+
+    #[test]
+    fn test_cycle_reversal_strategy() {
+        let mut app = App::new().unwrap();
+        for key in ['k', 'a', 'm', 'i'] {
+            app.select_phoneme(key);
+        }
+        let ipa = |phonemes: Vec<Phoneme>| phonemes.into_iter().map(|p| p.ipa).collect::<String>();
+
+        assert_eq!(app.reversal_strategy, ReversalStrategy::Segment);
+        assert_eq!(ipa(app.get_reversed_phonemes()), "imak");
+
+        app.cycle_reversal_strategy();
+        assert_eq!(app.reversal_strategy, ReversalStrategy::Mora);
+        assert_eq!(ipa(app.get_reversed_phonemes()), "mika");
+        assert_eq!(ipa(app.reversed_with(ReversalStrategy::CvSwap)), "akim");
+    }
+
+    #[test]
+    fn test_undo_redo_sequence_edits() {
+        let mut app = App::new().unwrap();
+        for key in ['k', 'a', 'm', 'i'] {
+            app.select_phoneme(key);
+        }
+        app.delete_backward();
+        assert_eq!(ipa_of(&app), "kam");
+
+        app.undo();
+        assert_eq!(ipa_of(&app), "kami");
+        assert_eq!(app.message.as_deref(), Some("Undo: Delete"));
+        app.undo();
+        assert_eq!(ipa_of(&app), "kam");
+        assert_eq!(app.cursor, 3);
+
+        app.redo();
+        assert_eq!(ipa_of(&app), "kami");
+        assert_eq!(app.message.as_deref(), Some("Redo: Insert [i]"));
+
+        // 移動と全削除
+        app.move_cursor_to(0, false);
+        app.move_segment(1);
+        assert_eq!(ipa_of(&app), "akmi");
+        app.clear_sequence();
+        assert!(app.selected_phonemes.is_empty());
+
+        app.undo();
+        assert_eq!(ipa_of(&app), "akmi");
+        app.undo();
+        assert_eq!(ipa_of(&app), "kami");
+        assert_eq!(app.cursor, 0);
+
+        // 新しい編集で redo は消える
+        app.select_phoneme('a');
+        app.redo();
+        assert_eq!(app.message.as_deref(), Some("Nothing to redo"));
+    }
+
+    #[test]
+    fn test_project_save_and_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("project.json");
+
+        let mut app = App::new().unwrap();
+        for key in ['k', 'a'] {
+            app.select_phoneme(key);
+        }
+        app.cycle_reversal_strategy();
+        app.new_sequence();
+        assert!(app.selected_phonemes.is_empty());
+        assert_eq!(app.sequence_position(), (2, 2));
+        app.toggle_language();
+        app.select_phoneme('m');
+        app.save_project(&path).unwrap();
+
+        let mut app = App::new().unwrap();
+        app.open_project(&path).unwrap();
+        assert_eq!(ipa_of(&app), "ka");
+        assert_eq!(app.reversal_strategy, ReversalStrategy::Mora);
+        assert_eq!(app.current_language(), Language::Japanese);
+
+        app.switch_sequence(1);
+        assert_eq!(ipa_of(&app), "m");
+        assert_eq!(app.sequence_name(), "Sequence 2");
+        assert_eq!(app.current_language(), Language::English);
+        assert_eq!(app.reversal_strategy, ReversalStrategy::Mora);
+
+        // 切り替え前の編集は書き戻される
+        app.select_phoneme('a');
+        app.switch_sequence(1);
+        app.switch_sequence(-1);
+        assert_eq!(ipa_of(&app), "ma");
+
+        app.open_project_input(InputKind::SaveProject);
+        assert_eq!(app.input.as_ref().unwrap().buffer, path.display().to_string());
+    }
+
+    #[test]
+    fn test_settings_view_adjusts_synth_config() {
+        let mut app = App::new().unwrap();
+        app.toggle_view();
+        app.open_settings();
+        assert_eq!(app.current_view, View::Settings);
+
+        app.adjust_setting(-1);
+        assert_eq!(app.synth_config().speed, 170);
+        app.adjust_setting(-1000);
+        assert_eq!(app.synth_config().speed, 80);
+
+        app.select_setting(-1);
+        assert_eq!(app.selected_setting(), SynthParam::Variant);
+        app.adjust_setting(8);
+        assert_eq!(app.synth_config().variant.as_deref(), Some("f1"));
+        assert_eq!(app.message.as_deref(), Some("Voice variant: f1"));
+
+        app.close_settings();
+        assert_eq!(app.current_view, View::Preview);
+    }
+
+    #[test]
+    fn test_undo_settings_changes() {
+        let mut app = App::new().unwrap();
+        app.cycle_reversal_strategy();
+        app.toggle_diphthong_policy();
+        app.toggle_language();
+        assert_eq!(app.synth_config.language, Language::English);
+
+        app.undo();
+        assert_eq!(app.synth_config.language, Language::Japanese);
+        assert!(app.phoneme_db.find_by_ipa("ɴ").is_some());
+        app.undo();
+        assert_eq!(app.diphthong_policy, DiphthongPolicy::default());
+        app.undo();
+        assert_eq!(app.reversal_strategy, ReversalStrategy::Segment);
+
+        app.undo();
+        assert_eq!(app.message.as_deref(), Some("Nothing to undo"));
+    }
+
+    #[test]
+    fn test_text_input_appends_phonemes() {
+        let mut app = App::new().unwrap();
+        app.select_phoneme('a');
+
+        app.open_text_input();
+        assert_eq!(app.input.as_ref().unwrap().kind, InputKind::Kana);
+        app.input.as_mut().unwrap().buffer.push_str("kitte 漢");
+        app.submit_input().unwrap();
+
+        let ipa: Vec<&str> = app.selected_phonemes.iter().map(|p| p.ipa.as_str()).collect();
+        assert_eq!(ipa, vec!["a", "k", "i", "t", "t", "e"]);
+        assert_eq!(app.unknown_symbols.len(), 1);
+        assert_eq!(app.message.as_deref(), Some("Added 5 phonemes (1 unknown)"));
+        assert_eq!(kana::to_kana(&app.get_reversed_phonemes()), "えってぃか");
+
+        // 英語表示中は英文入力になる
+        app.toggle_language();
+        app.open_text_input();
+        assert_eq!(app.input.as_ref().unwrap().kind, InputKind::EnglishText);
+    }
+
+    #[test]
+    fn test_apply_fix_repairs_reversed_sequence() {
+        let mut app = App::new().unwrap();
+        // 「かつ」の逆順 [u ts a k] は語末の k が違反
+        for key in ['k', 'a', 'c', 'u'] {
+            app.select_phoneme(key);
+        }
+        let ipa = |app: &App| app.get_reversed_phonemes().into_iter().map(|p| p.ipa).collect::<String>();
+
+        let violations = app.violations();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].index, 3);
+
+        app.apply_fix();
+        assert_eq!(ipa(&app), "utsakɯ");
+        assert!(app.violations().is_empty());
+
+        app.apply_fix();
+        assert_eq!(app.message.as_deref(), Some("No fixable violations"));
+
+        // 選択が変わると修正は破棄される
+        app.select_phoneme('a');
+        assert_eq!(ipa(&app), "autsak");
+    }
+
+    #[test]
+    fn test_reversal_follows_diphthong_policy() {
+        let mut app = App::new().unwrap();
+        app.toggle_language();
+        app.select_phoneme('m');
+        app.select_phoneme('1');
+
+        let reversed: Vec<String> = app.get_reversed_phonemes().into_iter().map(|p| p.ipa).collect();
+        assert_eq!(reversed, vec!["aɪ", "m"]);
+
+        app.toggle_diphthong_policy();
+        assert_eq!(app.diphthong_policy, DiphthongPolicy::Flip);
+        let reversed: Vec<String> = app.get_reversed_phonemes().into_iter().map(|p| p.ipa).collect();
+        assert_eq!(reversed, vec!["ɪa", "m"]);
+    }
+
+    #[test]
+    fn test_toggle_language_switches_inventory() {
+        let mut app = App::new().unwrap();
+        app.select_phoneme('U');
+        app.select_phoneme('k');
+        assert_eq!(app.selected_phonemes[0].ipa, "ɯ");
+
+        app.toggle_language();
+        assert!(app.phoneme_db.find_by_ipa("aɪ").is_some());
+        assert!(app.phoneme_db.find_by_ipa("ɯ").is_none());
+        assert!(!app.is_available(&app.selected_phonemes[0]));
+        assert!(app.is_available(&app.selected_phonemes[1]));
+        assert!(app.message.as_deref().unwrap().starts_with("1 selected"));
+
+        // 同じキーでも英語の音素が選ばれる
+        app.select_phoneme('U');
+        assert_eq!(app.selected_phonemes[2].ipa, "ʊ");
+
+        app.toggle_language();
+        assert!(app.message.is_some());
+        assert!(app.is_available(&app.selected_phonemes[0]));
+    }
+
+    #[test]
+    fn test_choose_output_device_is_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let mut app = App::new().unwrap().with_config(Config::default(), Some(path.clone()));
+
+        app.open_devices();
+        assert_eq!(app.current_view, View::Devices);
+        // 実際のデバイスの代わりに一覧を差し替える
+        let usb = OutputDevice {
+            host: "ALSA".to_string(),
+            name: "hw:CARD=USB,DEV=0".to_string(),
+        };
+        app.devices = vec![DeviceInfo {
+            device: usb.clone(),
+            is_default: false,
+            formats: Vec::new(),
+        }];
+        app.device_cursor = 0;
+        app.move_device_cursor(1);
+        app.choose_device();
+
+        assert_eq!(app.current_view, View::PhonemeSelection);
+        assert_eq!(app.output_device(), Some(&usb));
+        assert_eq!(Config::load(&path).unwrap().output_device, Some(usb.clone()));
+
+        // 保存した設定で起動すると同じデバイスが選ばれる
+        let config = Config::load(&path).unwrap();
+        let app = App::new().unwrap().with_config(config, None);
+        assert_eq!(app.output_device(), Some(&usb));
+
+        // 先頭の行でシステムの既定に戻す
+        let mut app = app.with_config(Config::load(&path).unwrap(), Some(path.clone()));
+        app.devices = Vec::new();
+        app.device_cursor = 0;
+        app.move_device_cursor(-1);
+        assert_eq!(app.device_cursor, 0);
+        app.choose_device();
+        assert_eq!(app.output_device(), None);
+        assert_eq!(Config::load(&path).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_playback_with_null_sink() {
+        let mut app = test_app().with_audio_sink(Box::new(crate::playback::NullSink));
+        assert_eq!(app.audio_sink_name(), "null");
+        select_ipa(&mut app, &["k", "a"]);
+
+        app.play_reversed();
+        assert_eq!(app.playback_status, PlaybackStatus::Queued);
+        wait_for_jobs(&mut app);
+        assert_eq!(app.playback_status, PlaybackStatus::Playing);
+        assert!(app.playback_progress().is_some());
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while app.is_playing {
+            assert!(std::time::Instant::now() < deadline, "playback did not finish");
+            std::thread::sleep(Duration::from_millis(5));
+            app.tick();
+        }
+        assert_eq!(app.playback_status, PlaybackStatus::Done);
+    }
 }
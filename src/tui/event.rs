@@ -1,5 +1,5 @@
-use crate::tui::app::{App, View};
-use crossterm::event::{self, Event, KeyCode, KeyEvent};
+use crate::tui::app::{App, InputKind, View};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
 use std::time::Duration;
 
 pub fn handle_events(app: &mut App) -> std::io::Result<()> {
@@ -12,53 +12,158 @@ pub fn handle_events(app: &mut App) -> std::io::Result<()> {
 }
 
 fn handle_key_event(app: &mut App, key: KeyEvent) {
+    // 入力プロンプト表示中はすべてのキーを入力として扱う
+    if app.input.is_some() {
+        handle_input_keys(app, key);
+        return;
+    }
+
     match app.current_view {
         View::PhonemeSelection => handle_phoneme_selection_keys(app, key),
         View::Preview => handle_preview_keys(app, key),
+        View::Settings => handle_settings_keys(app, key),
+        View::Devices => handle_devices_keys(app, key),
     }
 }
 
-fn handle_phoneme_selection_keys(app: &mut App, key: KeyEvent) {
+fn handle_input_keys(app: &mut App, key: KeyEvent) {
     match key.code {
-        KeyCode::Char('q') => app.quit(),
-        KeyCode::Char('L') => app.toggle_language(),
-        KeyCode::Char(c) => app.select_phoneme(c),
-        KeyCode::Backspace => app.delete_last_phoneme(),
+        KeyCode::Esc => app.cancel_input(),
         KeyCode::Enter => {
-            if !app.selected_phonemes.is_empty() {
-                app.toggle_view();
+            if let Err(e) = app.submit_input() {
+                app.message = Some(format!("Error: {}", e));
+            }
+        }
+        KeyCode::Backspace => {
+            if let Some(prompt) = app.input.as_mut() {
+                prompt.buffer.pop();
+            }
+        }
+        KeyCode::Char(c) => {
+            if let Some(prompt) = app.input.as_mut() {
+                prompt.buffer.push(c);
             }
         }
         _ => {}
     }
 }
 
+fn handle_phoneme_selection_keys(app: &mut App, key: KeyEvent) {
+    // 文字キーは音素の入力に使うため、コマンドは Ctrl との組み合わせにする
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+
+    match key.code {
+        KeyCode::Char('z') if ctrl => app.undo(),
+        KeyCode::Char('y') if ctrl => app.redo(),
+        KeyCode::Char('l') if ctrl => app.clear_sequence(),
+        KeyCode::Char('c') if ctrl => app.copy_selection(),
+        KeyCode::Char('x') if ctrl => app.cut_selection(),
+        KeyCode::Char('v') if ctrl => app.paste(),
+        KeyCode::Char('o') if ctrl => app.open_input(InputKind::ImportAudio),
+        KeyCode::Char('t') if ctrl => app.open_text_input(),
+        KeyCode::Char('s') if ctrl => app.open_project_input(InputKind::SaveProject),
+        KeyCode::Char('p') if ctrl => app.open_project_input(InputKind::OpenProject),
+        KeyCode::Char('n') if ctrl => app.new_sequence(),
+        KeyCode::Char('r') if ctrl => {
+            if let Err(e) = app.toggle_recording() {
+                app.message = Some(format!("Error: {}", e));
+            }
+        }
+        KeyCode::Char('q') => app.quit(),
+        KeyCode::Char('L') => app.toggle_language(),
+        KeyCode::Char(':') => app.toggle_length(),
+        KeyCode::Char(c) if !ctrl => app.select_phoneme(c),
+        // カーソル移動（Shift で範囲選択、Alt で音素・範囲を移動）
+        KeyCode::Left if alt => app.move_segment(-1),
+        KeyCode::Right if alt => app.move_segment(1),
+        KeyCode::Left => app.move_cursor(-1, shift),
+        KeyCode::Right => app.move_cursor(1, shift),
+        KeyCode::Home => app.move_cursor_to(0, shift),
+        KeyCode::End => app.move_cursor_to(app.selected_phonemes.len(), shift),
+        KeyCode::Backspace => app.delete_backward(),
+        KeyCode::Delete => app.delete_forward(),
+        KeyCode::Insert => app.toggle_replace_mode(),
+        KeyCode::F(2) => app.open_settings(),
+        KeyCode::F(3) => app.open_devices(),
+        KeyCode::PageUp => app.switch_sequence(-1),
+        KeyCode::PageDown => app.switch_sequence(1),
+        KeyCode::Enter if !app.selected_phonemes.is_empty() => app.toggle_view(),
+        _ => {}
+    }
+}
+
 fn handle_preview_keys(app: &mut App, key: KeyEvent) {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
     match key.code {
+        KeyCode::Char('z') if ctrl => app.undo(),
+        KeyCode::Char('y') if ctrl => app.redo(),
         KeyCode::Char('q') => app.quit(),
         KeyCode::Char('L') => app.toggle_language(),
         KeyCode::Esc => app.toggle_view(),
-        KeyCode::Char('p') => {
-            if let Err(e) = app.play_original() {
-                app.playback_status = crate::tui::app::PlaybackStatus::Error(e.to_string());
-            }
-        }
-        KeyCode::Char('r') => {
-            if let Err(e) = app.play_reversed() {
-                app.playback_status = crate::tui::app::PlaybackStatus::Error(e.to_string());
-            }
-        }
-        KeyCode::Char('s') => {
-            match app.save_reversed() {
-                Ok(_filename) => {
-                    app.playback_status = crate::tui::app::PlaybackStatus::Idle;
-                    // ファイル名を状態に保存したい場合はここで実装
-                }
-                Err(e) => {
-                    app.playback_status = crate::tui::app::PlaybackStatus::Error(e.to_string());
-                }
-            }
+        KeyCode::F(2) => app.open_settings(),
+        KeyCode::F(3) => app.open_devices(),
+        KeyCode::Char('p') => app.play_original(),
+        KeyCode::Char('r') => app.play_reversed(),
+        // 波形の反転との比較
+        KeyCode::Char('w') => app.play_waveform_reversed(),
+        KeyCode::Char('a') => app.toggle_ab(),
+        KeyCode::Char('m') => app.play_demo(),
+        // 音素ごとの合成とクロスフェード
+        KeyCode::Char('c') => app.play_concatenative(),
+        KeyCode::Char('[') => app.adjust_crossfade(-1),
+        KeyCode::Char(']') => app.adjust_crossfade(1),
+        KeyCode::Char(' ') => app.toggle_pause(),
+        KeyCode::Char('x') => app.stop_playback(),
+        KeyCode::Char('d') => app.toggle_diphthong_policy(),
+        KeyCode::Char('v') => app.cycle_reversal_strategy(),
+        KeyCode::Char('f') => app.apply_fix(),
+        // 保存はワーカーで行い、完了時にメッセージを表示する
+        KeyCode::Char('s') => save(app, App::save_reversed),
+        KeyCode::Char('S') => save(app, App::save_demo),
+        _ => {}
+    }
+}
+
+fn save(app: &mut App, save: fn(&mut App) -> crate::error::Result<String>) {
+    match save(app) {
+        Ok(filename) => app.message = Some(format!("Saving {}...", filename)),
+        Err(e) => {
+            app.playback_status = crate::tui::app::PlaybackStatus::Error(e.to_string());
         }
+    }
+}
+
+fn handle_settings_keys(app: &mut App, key: KeyEvent) {
+    // Shift で10段階ずつ変更する
+    let steps = if key.modifiers.contains(KeyModifiers::SHIFT) { 10 } else { 1 };
+
+    match key.code {
+        KeyCode::Char('q') => app.quit(),
+        KeyCode::Esc | KeyCode::Enter | KeyCode::F(2) => app.close_settings(),
+        KeyCode::F(3) => app.open_devices(),
+        KeyCode::Up => app.select_setting(-1),
+        KeyCode::Down => app.select_setting(1),
+        KeyCode::Left => app.adjust_setting(-steps),
+        KeyCode::Right => app.adjust_setting(steps),
+        // 設定を変えながら試聴できるようにする
+        KeyCode::Char('p') => app.play_original(),
+        KeyCode::Char('r') => app.play_reversed(),
+        KeyCode::Char(' ') => app.toggle_pause(),
+        KeyCode::Char('x') => app.stop_playback(),
+        _ => {}
+    }
+}
+
+fn handle_devices_keys(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Char('q') => app.quit(),
+        KeyCode::Esc | KeyCode::F(3) => app.close_devices(),
+        KeyCode::Up => app.move_device_cursor(-1),
+        KeyCode::Down => app.move_device_cursor(1),
+        KeyCode::Enter => app.choose_device(),
         _ => {}
     }
 }
@@ -0,0 +1,205 @@
+use crate::reversal::{DiphthongPolicy, ReversalStrategy};
+use crate::synth::Language;
+use crate::tui::phoneme_db::Phoneme;
+use std::collections::VecDeque;
+use std::ops::Range;
+
+// 履歴に残す操作の上限（古いものから捨てる）
+pub const DEFAULT_LIMIT: usize = 100;
+
+/// A reversible change to the editing state.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Edit {
+    /// `removed` at `index` was replaced by `inserted` (insert, delete,
+    /// replace, paste, clear and imports).
+    Splice {
+        index: usize,
+        removed: Vec<Phoneme>,
+        inserted: Vec<Phoneme>,
+    },
+    /// The phonemes in `range` moved by `delta` positions.
+    Move {
+        range: Range<usize>,
+        delta: isize,
+    },
+    Strategy {
+        from: ReversalStrategy,
+        to: ReversalStrategy,
+    },
+    Diphthongs {
+        from: DiphthongPolicy,
+        to: DiphthongPolicy,
+    },
+    Language {
+        from: Language,
+        to: Language,
+    },
+}
+
+impl Edit {
+    /// The edit that undoes this one.
+    pub fn inverse(&self) -> Edit {
+        match self {
+            Edit::Splice {
+                index,
+                removed,
+                inserted,
+            } => Edit::Splice {
+                index: *index,
+                removed: inserted.clone(),
+                inserted: removed.clone(),
+            },
+            Edit::Move { range, delta } => Edit::Move {
+                range: range.start.saturating_add_signed(*delta)
+                    ..range.end.saturating_add_signed(*delta),
+                delta: -delta,
+            },
+            Edit::Strategy { from, to } => Edit::Strategy {
+                from: *to,
+                to: *from,
+            },
+            Edit::Diphthongs { from, to } => Edit::Diphthongs {
+                from: *to,
+                to: *from,
+            },
+            Edit::Language { from, to } => Edit::Language {
+                from: *to,
+                to: *from,
+            },
+        }
+    }
+}
+
+/// An edit with the label shown in the status bar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Command {
+    pub label: String,
+    pub edit: Edit,
+}
+
+/// Bounded undo/redo stacks.
+#[derive(Debug)]
+pub struct History {
+    undo: VecDeque<Command>,
+    redo: Vec<Command>,
+    limit: usize,
+}
+
+impl History {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            limit: limit.max(1),
+        }
+    }
+
+    /// Record a new command; this discards the redo stack.
+    pub fn record(&mut self, label: impl Into<String>, edit: Edit) {
+        self.redo.clear();
+        if self.undo.len() == self.limit {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(Command {
+            label: label.into(),
+            edit,
+        });
+    }
+
+    /// Take the command to undo; the caller applies `edit.inverse()`.
+    pub fn undo(&mut self) -> Option<Command> {
+        let command = self.undo.pop_back()?;
+        self.redo.push(command.clone());
+        Some(command)
+    }
+
+    /// Take the command to redo; the caller applies `edit` again.
+    pub fn redo(&mut self) -> Option<Command> {
+        let command = self.redo.pop()?;
+        self.undo.push_back(command.clone());
+        Some(command)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(DEFAULT_LIMIT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strategy(to: ReversalStrategy) -> Edit {
+        Edit::Strategy {
+            from: ReversalStrategy::Segment,
+            to,
+        }
+    }
+
+    #[test]
+    fn test_undo_redo_order() {
+        let mut history = History::default();
+        history.record("first", strategy(ReversalStrategy::Mora));
+        history.record("second", strategy(ReversalStrategy::Syllable));
+
+        assert_eq!(history.undo().unwrap().label, "second");
+        assert_eq!(history.undo().unwrap().label, "first");
+        assert!(history.undo().is_none());
+
+        assert_eq!(history.redo().unwrap().label, "first");
+        assert!(history.can_undo());
+        assert!(history.can_redo());
+    }
+
+    #[test]
+    fn test_record_discards_redo() {
+        let mut history = History::default();
+        history.record("first", strategy(ReversalStrategy::Mora));
+        history.undo();
+        history.record("second", strategy(ReversalStrategy::CvSwap));
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut history = History::new(2);
+        for label in ["a", "b", "c"] {
+            history.record(label, strategy(ReversalStrategy::Mora));
+        }
+        assert_eq!(history.undo().unwrap().label, "c");
+        assert_eq!(history.undo().unwrap().label, "b");
+        assert!(history.undo().is_none());
+    }
+
+    #[test]
+    fn test_inverse() {
+        let splice = Edit::Splice {
+            index: 1,
+            removed: Vec::new(),
+            inserted: Vec::new(),
+        };
+        assert_eq!(splice.inverse().inverse(), splice);
+
+        let moved = Edit::Move {
+            range: 2..4,
+            delta: -1,
+        };
+        assert_eq!(
+            moved.inverse(),
+            Edit::Move {
+                range: 1..3,
+                delta: 1
+            }
+        );
+    }
+}
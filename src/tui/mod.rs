@@ -1,4 +1,6 @@
 pub mod phoneme_db;
 pub mod app;
+pub mod history;
+pub mod worker;
 pub mod ui;
 pub mod event;
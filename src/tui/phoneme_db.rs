@@ -1,3 +1,9 @@
+use crate::error::{PhonemeReverserError, Result};
+use crate::synth::Language;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Phoneme {
     pub ipa: String,
@@ -5,226 +11,359 @@ pub struct Phoneme {
     pub description_ja: String,
     pub key: char,
     pub category: PhonemeCategory,
+    /// Segments of a multi-segment phoneme (diphthong, affricate, long
+    /// vowel) in pronunciation order. Empty for single segments.
+    pub components: Vec<PhonemePart>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhonemePart {
+    pub ipa: String,
+    pub espeak: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum PhonemeCategory {
     Vowel,
     Consonant,
+    Affricate,
+    Diphthong,
+    LongVowel,
 }
 
+impl PhonemeCategory {
+    /// Whether the phoneme is listed with the vowels.
+    pub fn is_vowel(&self) -> bool {
+        matches!(
+            self,
+            PhonemeCategory::Vowel | PhonemeCategory::Diphthong | PhonemeCategory::LongVowel
+        )
+    }
+
+    fn is_compound(&self) -> bool {
+        matches!(
+            self,
+            PhonemeCategory::Affricate | PhonemeCategory::Diphthong
+        )
+    }
+}
+
+// 長音記号（IPA / eSpeak）
+pub(crate) const IPA_LENGTH_MARK: char = 'ː';
+const ESPEAK_LENGTH_MARK: char = ':';
+
 pub struct PhonemeDatabase {
     vowels: Vec<Phoneme>,
     consonants: Vec<Phoneme>,
+    // IPA → その音素を持つ言語
+    languages: HashMap<String, Vec<Language>>,
+}
+
+// 組み込みの音素インベントリ
+const BUNDLED_INVENTORY: &str = include_str!("../../data/phonemes.toml");
+const BUNDLED_NAME: &str = "<bundled>/phonemes.toml";
+
+#[derive(Debug, Deserialize)]
+#[serde(bound(deserialize = "E: Deserialize<'de>"))]
+struct InventoryFile<E> {
+    #[serde(default)]
+    phoneme: Vec<E>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct PhonemeEntry {
+    ipa: String,
+    espeak: String,
+    description_ja: String,
+    key: char,
+    category: PhonemeCategory,
+    #[serde(default = "all_languages")]
+    languages: Vec<Language>,
+    // 構成音素のIPA（二重母音・破擦音では必須）
+    #[serde(default)]
+    components: Vec<String>,
+}
+
+fn all_languages() -> Vec<Language> {
+    Language::ALL.to_vec()
+}
+
+impl PhonemeEntry {
+    fn shares_language(&self, other: &PhonemeEntry) -> Option<Language> {
+        self.languages
+            .iter()
+            .copied()
+            .find(|language| other.languages.contains(language))
+    }
+}
+
+/// Where an entry was defined, for error messages.
+#[derive(Debug, Clone)]
+struct Location {
+    file: String,
+    // JSON は行番号を取得できないためエントリ番号で示す
+    line: Option<usize>,
+    index: usize,
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}", self.file, line),
+            None => write!(f, "{} (entry #{})", self.file, self.index + 1),
+        }
+    }
+}
+
+/// Accumulates inventory files in load order.
+///
+/// An entry whose IPA is already defined replaces the earlier one, so user
+/// files can override bundled phonemes.
+#[derive(Default)]
+struct InventoryLoader {
+    entries: Vec<(PhonemeEntry, Location)>,
+}
+
+impl InventoryLoader {
+    fn add_source(&mut self, file: &str, text: &str) -> Result<()> {
+        if file.ends_with(".json") {
+            self.add_json(file, text)
+        } else {
+            self.add_toml(file, text)
+        }
+    }
+
+    fn add_toml(&mut self, file: &str, text: &str) -> Result<()> {
+        let parsed: InventoryFile<toml::Spanned<PhonemeEntry>> =
+            toml::from_str(text).map_err(|e| {
+                let line = e.span().map(|span| line_of(text, span.start));
+                inventory_error(file, line, e.message())
+            })?;
+
+        for (index, entry) in parsed.phoneme.into_iter().enumerate() {
+            let line = Some(line_of(text, entry.span().start));
+            self.insert(
+                entry.into_inner(),
+                Location {
+                    file: file.to_string(),
+                    line,
+                    index,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    fn add_json(&mut self, file: &str, text: &str) -> Result<()> {
+        let parsed: InventoryFile<PhonemeEntry> = serde_json::from_str(text).map_err(|e| {
+            // serde_json のメッセージには位置情報が含まれるので取り除く
+            let message = e.to_string();
+            let message = message
+                .rsplit_once(" at line ")
+                .map_or(message.as_str(), |(head, _)| head);
+            inventory_error(file, Some(e.line()), message)
+        })?;
+
+        for (index, entry) in parsed.phoneme.into_iter().enumerate() {
+            self.insert(
+                entry,
+                Location {
+                    file: file.to_string(),
+                    line: None,
+                    index,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    fn insert(&mut self, entry: PhonemeEntry, location: Location) {
+        match self.entries.iter_mut().find(|(e, _)| e.ipa == entry.ipa) {
+            Some(existing) => *existing = (entry, location),
+            None => self.entries.push((entry, location)),
+        }
+    }
+
+    fn build(self) -> Result<PhonemeDatabase> {
+        // キーは言語ごとに一意であればよい
+        for (i, (entry, location)) in self.entries.iter().enumerate() {
+            for (other, other_location) in &self.entries[..i] {
+                if other.key != entry.key {
+                    continue;
+                }
+                if let Some(language) = entry.shares_language(other) {
+                    return Err(PhonemeReverserError::PhonemeInventory(format!(
+                        "{}: duplicate key '{}' for /{}/ in {} (already used by /{}/ at {})",
+                        location,
+                        entry.key,
+                        entry.ipa,
+                        language.display_name(),
+                        other.ipa,
+                        other_location
+                    )));
+                }
+            }
+        }
+
+        let mut phonemes = Vec::with_capacity(self.entries.len());
+        for (entry, location) in &self.entries {
+            phonemes.push(Phoneme {
+                ipa: entry.ipa.clone(),
+                espeak: entry.espeak.clone(),
+                description_ja: entry.description_ja.clone(),
+                key: entry.key,
+                category: entry.category,
+                components: self.resolve_components(entry, location)?,
+            });
+        }
+
+        let languages = self
+            .entries
+            .iter()
+            .map(|(entry, _)| (entry.ipa.clone(), entry.languages.clone()))
+            .collect();
+
+        let (vowels, consonants): (Vec<Phoneme>, Vec<Phoneme>) =
+            phonemes.into_iter().partition(|p| p.category.is_vowel());
+
+        Ok(PhonemeDatabase {
+            vowels,
+            consonants,
+            languages,
+        })
+    }
+}
+
+impl InventoryLoader {
+    /// 構成音素をインベントリ内の音素（言語は問わない）に対応付ける
+    fn resolve_components(
+        &self,
+        entry: &PhonemeEntry,
+        location: &Location,
+    ) -> Result<Vec<PhonemePart>> {
+        if entry.category.is_compound() && entry.components.len() < 2 {
+            return Err(PhonemeReverserError::PhonemeInventory(format!(
+                "{}: /{}/ needs at least two components",
+                location, entry.ipa
+            )));
+        }
+
+        entry
+            .components
+            .iter()
+            .map(|ipa| {
+                self.entries
+                    .iter()
+                    .find(|(e, _)| &e.ipa == ipa)
+                    .map(|(e, _)| PhonemePart {
+                        ipa: e.ipa.clone(),
+                        espeak: e.espeak.clone(),
+                    })
+                    .ok_or_else(|| {
+                        PhonemeReverserError::PhonemeInventory(format!(
+                            "{}: unknown component /{}/ of /{}/",
+                            location, ipa, entry.ipa
+                        ))
+                    })
+            })
+            .collect()
+    }
+}
+
+fn line_of(text: &str, offset: usize) -> usize {
+    text[..offset.min(text.len())].matches('\n').count() + 1
+}
+
+fn inventory_error(file: &str, line: Option<usize>, message: &str) -> PhonemeReverserError {
+    let message = message.trim();
+    PhonemeReverserError::PhonemeInventory(match line {
+        Some(line) => format!("{}:{}: {}", file, line, message),
+        None => format!("{}: {}", file, message),
+    })
 }
 
 impl PhonemeDatabase {
+    /// The bundled inventory.
     pub fn new() -> Self {
-        let vowels = vec![
-            Phoneme {
-                ipa: "a".to_string(),
-                espeak: "a".to_string(),
-                description_ja: "日本語「あ」".to_string(),
-                key: 'a',
-                category: PhonemeCategory::Vowel,
-            },
-            Phoneme {
-                ipa: "i".to_string(),
-                espeak: "i".to_string(),
-                description_ja: "日本語「い」".to_string(),
-                key: 'i',
-                category: PhonemeCategory::Vowel,
-            },
-            Phoneme {
-                ipa: "u".to_string(),
-                espeak: "u".to_string(),
-                description_ja: "日本語「う」".to_string(),
-                key: 'u',
-                category: PhonemeCategory::Vowel,
-            },
-            Phoneme {
-                ipa: "e".to_string(),
-                espeak: "e".to_string(),
-                description_ja: "日本語「え」".to_string(),
-                key: 'e',
-                category: PhonemeCategory::Vowel,
-            },
-            Phoneme {
-                ipa: "o".to_string(),
-                espeak: "o".to_string(),
-                description_ja: "日本語「お」".to_string(),
-                key: 'o',
-                category: PhonemeCategory::Vowel,
-            },
-            Phoneme {
-                ipa: "ə".to_string(),
-                espeak: "@".to_string(),
-                description_ja: "曖昧母音 (about)".to_string(),
-                key: '@',
-                category: PhonemeCategory::Vowel,
-            },
-            Phoneme {
-                ipa: "ɑ".to_string(),
-                espeak: "A".to_string(),
-                description_ja: "後舌開母音 (father)".to_string(),
-                key: 'A',
-                category: PhonemeCategory::Vowel,
-            },
-            Phoneme {
-                ipa: "ɔ".to_string(),
-                espeak: "O".to_string(),
-                description_ja: "後舌半開円唇母音 (thought)".to_string(),
-                key: 'O',
-                category: PhonemeCategory::Vowel,
-            },
-        ];
-
-        let consonants = vec![
-            Phoneme {
-                ipa: "p".to_string(),
-                espeak: "p".to_string(),
-                description_ja: "無声両唇破裂音「ぱ」".to_string(),
-                key: 'p',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "b".to_string(),
-                espeak: "b".to_string(),
-                description_ja: "有声両唇破裂音「ば」".to_string(),
-                key: 'b',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "t".to_string(),
-                espeak: "t".to_string(),
-                description_ja: "無声歯茎破裂音「た」".to_string(),
-                key: 't',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "d".to_string(),
-                espeak: "d".to_string(),
-                description_ja: "有声歯茎破裂音「だ」".to_string(),
-                key: 'd',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "k".to_string(),
-                espeak: "k".to_string(),
-                description_ja: "無声軟口蓋破裂音「か」".to_string(),
-                key: 'k',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "g".to_string(),
-                espeak: "g".to_string(),
-                description_ja: "有声軟口蓋破裂音「が」".to_string(),
-                key: 'g',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "m".to_string(),
-                espeak: "m".to_string(),
-                description_ja: "両唇鼻音「ま」".to_string(),
-                key: 'm',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "n".to_string(),
-                espeak: "n".to_string(),
-                description_ja: "歯茎鼻音「な」".to_string(),
-                key: 'n',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "ŋ".to_string(),
-                espeak: "N".to_string(),
-                description_ja: "軟口蓋鼻音 (sing)".to_string(),
-                key: 'N',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "s".to_string(),
-                espeak: "s".to_string(),
-                description_ja: "無声歯茎摩擦音「さ」".to_string(),
-                key: 's',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "z".to_string(),
-                espeak: "z".to_string(),
-                description_ja: "有声歯茎摩擦音「ざ」".to_string(),
-                key: 'z',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "ʃ".to_string(),
-                espeak: "S".to_string(),
-                description_ja: "無声後部歯茎摩擦音「しゃ」".to_string(),
-                key: 'S',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "ʒ".to_string(),
-                espeak: "Z".to_string(),
-                description_ja: "有声後部歯茎摩擦音 (vision)".to_string(),
-                key: 'Z',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "h".to_string(),
-                espeak: "h".to_string(),
-                description_ja: "無声声門摩擦音「は」".to_string(),
-                key: 'h',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "f".to_string(),
-                espeak: "f".to_string(),
-                description_ja: "無声唇歯摩擦音 (fan)".to_string(),
-                key: 'f',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "v".to_string(),
-                espeak: "v".to_string(),
-                description_ja: "有声唇歯摩擦音 (van)".to_string(),
-                key: 'v',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "l".to_string(),
-                espeak: "l".to_string(),
-                description_ja: "歯茎側音 (light)".to_string(),
-                key: 'l',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "r".to_string(),
-                espeak: "r".to_string(),
-                description_ja: "歯茎ふるえ音 (巻き舌)".to_string(),
-                key: 'r',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "w".to_string(),
-                espeak: "w".to_string(),
-                description_ja: "有声両唇軟口蓋接近音「わ」".to_string(),
-                key: 'w',
-                category: PhonemeCategory::Consonant,
-            },
-            Phoneme {
-                ipa: "j".to_string(),
-                espeak: "j".to_string(),
-                description_ja: "有声硬口蓋接近音「や」".to_string(),
-                key: 'y',
-                category: PhonemeCategory::Consonant,
-            },
-        ];
-
-        Self { vowels, consonants }
+        Self::load(&[]).expect("bundled phoneme inventory is valid")
+    }
+
+    /// Load the bundled inventory followed by `user_files` (TOML, or JSON by
+    /// `.json` extension).
+    pub fn load(user_files: &[PathBuf]) -> Result<Self> {
+        let mut loader = InventoryLoader::default();
+        loader.add_source(BUNDLED_NAME, BUNDLED_INVENTORY)?;
+
+        for path in user_files {
+            let text = std::fs::read_to_string(path).map_err(|e| {
+                PhonemeReverserError::PhonemeInventory(format!("{}: {}", path.display(), e))
+            })?;
+            loader.add_source(&path.display().to_string(), &text)?;
+        }
+
+        loader.build()
+    }
+
+    /// The inventory and key layout of a single language.
+    pub fn for_language(&self, language: Language) -> PhonemeDatabase {
+        let has = |p: &&Phoneme| self.has_language(&p.ipa, language);
+
+        PhonemeDatabase {
+            vowels: self.vowels.iter().filter(has).cloned().collect(),
+            consonants: self.consonants.iter().filter(has).cloned().collect(),
+            languages: self.languages.clone(),
+        }
+    }
+
+    fn has_language(&self, ipa: &str, language: Language) -> bool {
+        self.languages
+            .get(ipa)
+            .is_some_and(|languages| languages.contains(&language))
     }
 
+    /// The long form of a vowel (`a` → `aː`).
+    ///
+    /// An inventory entry for the long vowel is preferred; otherwise one is
+    /// derived from the short vowel.
+    pub fn lengthen(&self, phoneme: &Phoneme) -> Option<Phoneme> {
+        if phoneme.category != PhonemeCategory::Vowel {
+            return None;
+        }
+
+        let ipa = format!("{}{}", phoneme.ipa, IPA_LENGTH_MARK);
+        if let Some(long) = self.find_by_ipa(&ipa) {
+            return Some(long.clone());
+        }
+
+        Some(Phoneme {
+            ipa,
+            espeak: format!("{}{}", phoneme.espeak, ESPEAK_LENGTH_MARK),
+            description_ja: format!("{}（長音）", phoneme.description_ja),
+            key: phoneme.key,
+            category: PhonemeCategory::LongVowel,
+            components: vec![PhonemePart {
+                ipa: phoneme.ipa.clone(),
+                espeak: phoneme.espeak.clone(),
+            }],
+        })
+    }
+
+    /// The short vowel a long vowel was derived from.
+    pub fn shorten(&self, phoneme: &Phoneme) -> Option<Phoneme> {
+        if phoneme.category != PhonemeCategory::LongVowel {
+            return None;
+        }
+        let base = phoneme
+            .components
+            .first()
+            .map(|part| part.ipa.as_str())
+            .unwrap_or_else(|| phoneme.ipa.trim_end_matches(IPA_LENGTH_MARK));
+        self.find_by_ipa(base).cloned()
+    }
+
+    /// Look up a phoneme by key. With several languages loaded, keys can be
+    /// shared between languages and the first definition wins.
     pub fn get_by_key(&self, key: char) -> Option<&Phoneme> {
         self.vowels
             .iter()
@@ -232,6 +371,13 @@ impl PhonemeDatabase {
             .find(|p| p.key == key)
     }
 
+    pub fn find_by_ipa(&self, ipa: &str) -> Option<&Phoneme> {
+        self.vowels
+            .iter()
+            .chain(self.consonants.iter())
+            .find(|p| p.ipa == ipa)
+    }
+
     pub fn get_vowels(&self) -> &[Phoneme] {
         &self.vowels
     }
@@ -254,8 +400,52 @@ mod tests {
     #[test]
     fn test_phoneme_database_creation() {
         let db = PhonemeDatabase::new();
-        assert_eq!(db.get_vowels().len(), 8, "Should have 8 vowels");
-        assert_eq!(db.get_consonants().len(), 20, "Should have 20 consonants");
+        assert_eq!(db.get_vowels().len(), 20, "Should have 20 vowels");
+        assert_eq!(db.get_consonants().len(), 27, "Should have 27 consonants");
+    }
+
+    #[test]
+    fn test_language_inventories() {
+        let db = PhonemeDatabase::new();
+
+        let ja = db.for_language(Language::Japanese);
+        assert_eq!(ja.get_vowels().len(), 6);
+        assert_eq!(ja.get_consonants().len(), 21);
+        for ipa in ["ɯ", "ɸ", "ɕ", "ts", "tɕ", "dʒ", "ɴ"] {
+            assert!(
+                ja.find_by_ipa(ipa).is_some(),
+                "Japanese should have /{}/",
+                ipa
+            );
+        }
+        assert!(ja.find_by_ipa("aɪ").is_none());
+
+        let en = db.for_language(Language::English);
+        assert_eq!(en.get_vowels().len(), 19);
+        assert_eq!(en.get_consonants().len(), 22);
+        for ipa in ["ɪ", "ʊ", "æ", "aɪ", "oʊ"] {
+            assert!(
+                en.find_by_ipa(ipa).is_some(),
+                "English should have /{}/",
+                ipa
+            );
+        }
+        assert!(en.find_by_ipa("ɯ").is_none());
+    }
+
+    #[test]
+    fn test_key_layout_depends_on_language() {
+        let db = PhonemeDatabase::new();
+        let ja = db.for_language(Language::Japanese);
+        let en = db.for_language(Language::English);
+
+        assert_eq!(ja.get_by_key('U').unwrap().ipa, "ɯ");
+        assert_eq!(en.get_by_key('U').unwrap().ipa, "ʊ");
+        assert_eq!(ja.get_by_key('N').unwrap().ipa, "ɴ");
+        assert_eq!(en.get_by_key('N').unwrap().ipa, "ŋ");
+        assert_eq!(ja.get_by_key('u').unwrap().ipa, "u");
+        assert!(ja.get_by_key('1').is_none());
+        assert_eq!(en.get_by_key('1').unwrap().ipa, "aɪ");
     }
 
     #[test]
@@ -276,28 +466,201 @@ mod tests {
         assert_eq!(phoneme.category, PhonemeCategory::Consonant);
     }
 
+    #[test]
+    fn test_find_by_ipa() {
+        let db = PhonemeDatabase::new();
+        assert_eq!(db.find_by_ipa("ʃ").unwrap().key, 'S');
+        assert_eq!(db.find_by_ipa("j").unwrap().key, 'y');
+        assert!(db.find_by_ipa("ʘ").is_none());
+    }
+
     #[test]
     fn test_invalid_key_returns_none() {
         let db = PhonemeDatabase::new();
         assert!(db.get_by_key('X').is_none());
-        assert!(db.get_by_key('1').is_none());
+        assert!(db
+            .for_language(Language::Japanese)
+            .get_by_key('1')
+            .is_none());
+    }
+
+    fn load_str(file: &str, text: &str) -> Result<PhonemeDatabase> {
+        let mut loader = InventoryLoader::default();
+        loader.add_source(BUNDLED_NAME, BUNDLED_INVENTORY)?;
+        loader.add_source(file, text)?;
+        loader.build()
     }
 
     #[test]
-    fn test_all_vowels_have_unique_keys() {
+    fn test_user_file_adds_phonemes() {
+        let db = load_str(
+            "extra.toml",
+            r#"
+[[phoneme]]
+ipa = "ʔ"
+espeak = "?"
+description_ja = "声門閉鎖音"
+key = "?"
+category = "consonant"
+languages = ["ja"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(db.get_consonants().len(), 28);
+        assert_eq!(db.get_by_key('?').unwrap().ipa, "ʔ");
+        assert!(db
+            .for_language(Language::English)
+            .find_by_ipa("ʔ")
+            .is_none());
+    }
+
+    #[test]
+    fn test_compound_phonemes_have_components() {
+        let db = PhonemeDatabase::new();
+
+        let diphthong = db.find_by_ipa("aɪ").unwrap();
+        assert_eq!(diphthong.category, PhonemeCategory::Diphthong);
+        assert!(diphthong.category.is_vowel());
+        let parts: Vec<&str> = diphthong
+            .components
+            .iter()
+            .map(|c| c.espeak.as_str())
+            .collect();
+        assert_eq!(parts, vec!["a", "I"]);
+
+        let affricate = db.find_by_ipa("tʃ").unwrap();
+        assert_eq!(affricate.category, PhonemeCategory::Affricate);
+        assert!(db.get_consonants().contains(affricate));
+        assert!(db.find_by_ipa("a").unwrap().components.is_empty());
+    }
+
+    #[test]
+    fn test_compound_phoneme_validation() {
+        let entry = |components: &str| {
+            format!(
+                "[[phoneme]]\nipa = \"pf\"\nespeak = \"pf\"\ndescription_ja = \"pf\"\nkey = \"P\"\ncategory = \"affricate\"\n{}",
+                components
+            )
+        };
+
+        let err = load_str("aff.toml", &entry("")).err().unwrap().to_string();
+        assert!(err.contains("aff.toml:1"), "{}", err);
+        assert!(err.contains("at least two components"), "{}", err);
+
+        let err = load_str(
+            "aff.toml",
+            &entry("components = [\"p\", \"ɸ\"]\nlanguages = [\"en\"]"),
+        );
+        assert!(err.is_ok(), "Components may come from another language");
+
+        let err = load_str("aff.toml", &entry("components = [\"p\", \"ʘ\"]"))
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("unknown component /ʘ/"), "{}", err);
+    }
+
+    #[test]
+    fn test_lengthen_and_shorten() {
         let db = PhonemeDatabase::new();
-        let mut keys: Vec<char> = db.get_vowels().iter().map(|p| p.key).collect();
-        keys.sort();
-        keys.dedup();
-        assert_eq!(keys.len(), 8, "All vowel keys should be unique");
+        let a = db.find_by_ipa("a").unwrap();
+
+        let long = db.lengthen(a).unwrap();
+        assert_eq!(long.ipa, "aː");
+        assert_eq!(long.espeak, "a:");
+        assert_eq!(long.category, PhonemeCategory::LongVowel);
+        assert!(db.lengthen(&long).is_none());
+        assert_eq!(&db.shorten(&long).unwrap(), a);
+
+        assert!(db.lengthen(db.find_by_ipa("k").unwrap()).is_none());
+        assert!(db.lengthen(db.find_by_ipa("aɪ").unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_user_file_overrides_same_ipa() {
+        let db = load_str(
+            "override.json",
+            r#"{"phoneme": [
+                {"ipa": "a", "espeak": "a", "description_ja": "あ", "key": "9", "category": "vowel"}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(db.get_vowels().len(), 20);
+        assert_eq!(db.get_by_key('9').unwrap().ipa, "a");
+        assert!(db.get_by_key('a').is_none());
+    }
+
+    #[test]
+    fn test_duplicate_key_is_rejected() {
+        let text = r#"
+[[phoneme]]
+ipa = "ɸ"
+espeak = "F"
+description_ja = "無声両唇摩擦音"
+key = "k"
+category = "consonant"
+"#;
+        let err = load_str("dup.toml", text).err().unwrap().to_string();
+        assert!(err.contains("dup.toml:2"), "{}", err);
+        assert!(err.contains("duplicate key 'k'"), "{}", err);
+
+        // 言語が重ならなければ同じキーを使える
+        let text = text.replace(
+            "category = \"consonant\"",
+            "category = \"consonant\"\nlanguages = []",
+        );
+        assert!(load_str("dup.toml", &text).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_category_reports_line() {
+        let text = r#"
+[[phoneme]]
+ipa = "ɸ"
+espeak = "F"
+description_ja = "無声両唇摩擦音"
+key = "F"
+category = "fricative"
+"#;
+        let err = load_str("bad.toml", text).err().unwrap().to_string();
+        assert!(err.contains("bad.toml:7"), "{}", err);
+        assert!(err.contains("fricative"), "{}", err);
+
+        let json = "{\"phoneme\": [\n{\"ipa\": \"x\", \"espeak\": \"x\", \"description_ja\": \"x\",\n\"key\": \"X\", \"category\": \"click\"}]}";
+        let err = load_str("bad.json", json).err().unwrap().to_string();
+        assert!(err.contains("bad.json:3"), "{}", err);
+    }
+
+    #[test]
+    fn test_missing_user_file_fails() {
+        let result = PhonemeDatabase::load(&[PathBuf::from("no/such/inventory.toml")]);
+        assert!(matches!(
+            result,
+            Err(PhonemeReverserError::PhonemeInventory(_))
+        ));
+    }
+
+    #[test]
+    fn test_all_vowels_have_unique_keys() {
+        for language in Language::ALL {
+            let db = PhonemeDatabase::new().for_language(language);
+            let mut keys: Vec<char> = db.get_vowels().iter().map(|p| p.key).collect();
+            let count = keys.len();
+            keys.sort();
+            keys.dedup();
+            assert_eq!(keys.len(), count, "All vowel keys should be unique");
+        }
     }
 
     #[test]
     fn test_all_consonants_have_unique_keys() {
-        let db = PhonemeDatabase::new();
-        let mut keys: Vec<char> = db.get_consonants().iter().map(|p| p.key).collect();
-        keys.sort();
-        keys.dedup();
-        assert_eq!(keys.len(), 20, "All consonant keys should be unique");
+        for language in Language::ALL {
+            let db = PhonemeDatabase::new().for_language(language);
+            let mut keys: Vec<char> = db.get_consonants().iter().map(|p| p.key).collect();
+            let count = keys.len();
+            keys.sort();
+            keys.dedup();
+            assert_eq!(keys.len(), count, "All consonant keys should be unique");
+        }
     }
 }
@@ -1,9 +1,12 @@
-use crate::tui::app::{App, View};
+use crate::kana;
+use crate::reversal::ReversalStrategy;
+use crate::synth::{Language, SynthParam, VOICE_VARIANTS};
+use crate::tui::app::{App, PlaybackStatus, View};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph},
     Frame,
 };
 
@@ -11,6 +14,8 @@ pub fn render(frame: &mut Frame, app: &App) {
     match app.current_view {
         View::PhonemeSelection => render_phoneme_selection(frame, app),
         View::Preview => render_preview(frame, app),
+        View::Settings => render_settings(frame, app),
+        View::Devices => render_devices(frame, app),
     }
 }
 
@@ -82,13 +87,33 @@ fn render_phoneme_selection(frame: &mut Frame, app: &App) {
     );
     frame.render_widget(consonants_list, content_chunks[1]);
 
-    // 選択済み音素リスト
-    let selected_items: Vec<String> = app
+    // 選択済み音素リスト（現在の言語にない音素は赤、範囲選択は背景色で表示）
+    // カーソルは挿入位置の直後の音素に「▸」を付けて示す
+    let selection = app.selection();
+    let mut selected_items: Vec<Line> = app
         .selected_phonemes
         .iter()
         .enumerate()
-        .map(|(i, p)| format!("{}. [{}] {}", i + 1, p.ipa, p.description_ja))
+        .map(|(i, p)| {
+            let marker = if i == app.cursor { "▸" } else { " " };
+            let mut text = format!("{} {}. [{}] {}", marker, i + 1, p.ipa, p.description_ja);
+            let mut style = Style::default();
+            if !app.is_available(p) {
+                text.push_str(" (n/a)");
+                style = style.fg(Color::Red);
+            }
+            if selection.as_ref().is_some_and(|range| range.contains(&i)) {
+                style = style.bg(Color::Blue);
+            }
+            if i == app.cursor && app.replace_mode {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            Line::from(Span::styled(text, style))
+        })
         .collect();
+    if !selected_items.is_empty() && app.cursor == selected_items.len() {
+        selected_items.push(Line::from("▸ _"));
+    }
 
     let selected_text = if selected_items.is_empty() {
         vec![
@@ -100,37 +125,134 @@ fn render_phoneme_selection(frame: &mut Frame, app: &App) {
             )),
         ]
     } else {
-        let mut lines: Vec<Line> = selected_items.iter().map(|s| Line::from(s.as_str())).collect();
+        let total = app.selected_phonemes.len();
+        let mut lines = selected_items;
         lines.push(Line::from(""));
-        lines.push(Line::from(format!("Total: {}", selected_items.len())));
+        lines.push(Line::from(format!("Total: {}", total)));
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             "[Enter] Preview",
             Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
         )));
         lines.push(Line::from(Span::styled(
-            "[Backspace] Delete",
+            "[Backspace/Del] Delete  [Ctrl+L] Clear  [Ins] Replace",
             Style::default().fg(Color::Red),
         )));
+        lines.push(Line::from(Span::styled(
+            "[←→] Cursor  [Shift+←→] Select  [Alt+←→] Move",
+            Style::default().fg(Color::Gray),
+        )));
+        lines.push(Line::from(Span::styled(
+            "[Ctrl+C/X/V] Copy/Cut/Paste  [Ctrl+Z/Y] Undo/Redo",
+            Style::default().fg(Color::Gray),
+        )));
+        lines.push(Line::from(Span::styled(
+            "[Ctrl+S/P] Save/Open  [Ctrl+N] New  [PgUp/PgDn] Sequence  [F2/F3] Settings/Device",
+            Style::default().fg(Color::Gray),
+        )));
         lines
     };
 
+    // 取り込んだ音声のうちデータベースに対応付けられなかった記号
+    let mut selected_text = selected_text;
+    if !app.unknown_symbols.is_empty() {
+        selected_text.push(Line::from(""));
+        selected_text.push(Line::from(Span::styled(
+            format!("Unknown: {}", unknown_symbols_text(app)),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
     let selected_list = Paragraph::new(selected_text).block(
         Block::default()
             .borders(Borders::ALL)
-            .title("Selected Phonemes")
+            .title(format!(
+                "{} [{}/{}]  Selected Phonemes ({}/{}) {}",
+                app.sequence_name(),
+                app.sequence_position().0,
+                app.sequence_position().1,
+                app.cursor,
+                app.selected_phonemes.len(),
+                if app.replace_mode { "REP" } else { "INS" }
+            ))
             .style(Style::default().fg(Color::White)),
     );
     frame.render_widget(selected_list, content_chunks[2]);
 
-    // ステータスバー
+    // ステータスバー（入力中はプロンプトを表示）
     let language = app.current_language();
-    let status_text = format!("Language: {}  |  [Shift+L] Switch  |  [q] Quit", language.display_name());
-    let status = Paragraph::new(status_text)
-        .style(Style::default().fg(Color::White))
+    let status = match &app.input {
+        Some(prompt) => Paragraph::new(format!(
+            "{}: {}_  |  [Enter] OK  |  [Esc] Cancel",
+            prompt.kind.label(),
+            prompt.buffer
+        ))
+        .style(Style::default().fg(Color::Yellow)),
+        None => Paragraph::new(with_message(
+            app,
+            format!(
+                "Language: {}  |  Synth: {}  |  [Shift+L] Switch  |  [Ctrl+T] Text  |  [Ctrl+O] Import WAV  |  [Ctrl+R] Record  |  [q] Quit",
+                language.display_name(),
+                app.synthesizer_name()
+            ),
+        ))
+        .style(Style::default().fg(Color::White)),
+    };
+    let status = status
         .alignment(Alignment::Left)
         .block(Block::default().borders(Borders::ALL));
-    frame.render_widget(status, chunks[2]);
+
+    // 録音中はステータスバーの代わりに入力レベルメーターを表示
+    match level_meter(app) {
+        Some(meter) => frame.render_widget(meter, chunks[2]),
+        None => frame.render_widget(status, chunks[2]),
+    }
+}
+
+fn level_meter(app: &App) -> Option<Gauge<'static>> {
+    let (level, elapsed) = app.recording_level()?;
+
+    // RMS を -60dB〜0dB の範囲で表示する
+    let db = 20.0 * level.max(1e-6).log10();
+    let ratio = ((db + 60.0) / 60.0).clamp(0.0, 1.0) as f64;
+    let color = if db > -6.0 { Color::Red } else { Color::Green };
+
+    Some(
+        Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("● REC  [Ctrl+R] Stop & Recognize"),
+            )
+            .gauge_style(Style::default().fg(color))
+            .ratio(ratio)
+            .label(format!("{:.1}s  {:.0} dB", elapsed.as_secs_f64(), db)),
+    )
+}
+
+fn unknown_symbols_text(app: &App) -> String {
+    app.unknown_symbols
+        .iter()
+        .map(|u| format!("{} (#{})", u.symbol, u.index + 1))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// 合成待ち・合成中は黄色、再生中は緑、エラーは赤
+fn status_color(status: &PlaybackStatus) -> Color {
+    match status {
+        PlaybackStatus::Queued | PlaybackStatus::Synthesizing => Color::Yellow,
+        PlaybackStatus::Playing => Color::Green,
+        PlaybackStatus::Error(_) => Color::Red,
+        _ => Color::Gray,
+    }
+}
+
+fn with_message(app: &App, status: String) -> String {
+    match &app.message {
+        Some(message) => format!("{}  |  {}", message, status),
+        None => status,
+    }
 }
 
 fn render_preview(frame: &mut Frame, app: &App) {
@@ -142,6 +264,7 @@ fn render_preview(frame: &mut Frame, app: &App) {
         .constraints([
             Constraint::Length(3),      // タイトル
             Constraint::Min(0),          // コンテンツ
+            Constraint::Length(3),       // 再生プログレスバー
             Constraint::Length(3),       // ステータスバー
         ])
         .split(size);
@@ -161,17 +284,89 @@ fn render_preview(frame: &mut Frame, app: &App) {
         .collect::<Vec<_>>()
         .join(" ");
 
-    let reversed: String = app
-        .get_reversed_phonemes()
-        .iter()
-        .map(|p| format!("[{}]", p.ipa))
-        .collect::<Vec<_>>()
-        .join(" ");
-
-    let content_text = vec![
+    let mut content_text = vec![
         Line::from(""),
         Line::from(format!("Original:  {}", original)),
-        Line::from(format!("Reversed:  {}", reversed)),
+    ];
+    if app.current_language() == Language::Japanese {
+        content_text.push(Line::from(format!(
+            "Kana:      {}  →  {}",
+            kana::to_kana(&app.selected_phonemes),
+            kana::to_kana(&app.get_reversed_phonemes())
+        )));
+    }
+
+    // 全ての逆順化方式を並べ、再生・保存に使う方式を強調する
+    let violations = app.violations();
+    for strategy in ReversalStrategy::ALL {
+        let label = format!("{:<9} ", format!("{}:", strategy.display_name()));
+
+        if strategy != app.reversal_strategy {
+            let reversed: String = app
+                .reversed_with(strategy)
+                .iter()
+                .map(|p| format!("[{}]", p.ipa))
+                .collect::<Vec<_>>()
+                .join(" ");
+            content_text.push(Line::from(format!("  {}{}", label, reversed)));
+            continue;
+        }
+
+        // 選択中の方式は修正を反映し、音素配列の違反箇所を赤で示す
+        let active = Style::default().fg(Color::Green).add_modifier(Modifier::BOLD);
+        let mut spans = vec![Span::styled(format!("> {}", label), active)];
+        for (i, p) in app.get_reversed_phonemes().iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw(" "));
+            }
+            let style = if violations.iter().any(|v| v.index == i) {
+                Style::default()
+                    .fg(Color::Red)
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+            } else {
+                active
+            };
+            spans.push(Span::styled(format!("[{}]", p.ipa), style));
+        }
+        content_text.push(Line::from(spans));
+    }
+
+    for violation in &violations {
+        let repair = match &violation.repair {
+            Some(repair) => repair.to_string(),
+            None => "no fix".to_string(),
+        };
+        content_text.push(Line::from(Span::styled(
+            format!(
+                "  ! #{} {}: {}",
+                violation.index + 1,
+                violation.kind.display_name(),
+                repair
+            ),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    content_text.extend([
+        Line::from(Span::styled(
+            "[v] Next Strategy  [f] Apply Fix  [Ctrl+Z/Y] Undo/Redo  [F2/F3] Settings/Device",
+            Style::default().fg(Color::Gray),
+        )),
+        Line::from(Span::styled(
+            format!(
+                "Diphthongs: {}  [d] Toggle",
+                app.diphthong_policy.display_name()
+            ),
+            Style::default().fg(Color::Gray),
+        )),
+    ]);
+    if !app.unknown_symbols.is_empty() {
+        content_text.push(Line::from(Span::styled(
+            format!("Skipped:   {}", unknown_symbols_text(app)),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    content_text.extend([
         Line::from(""),
         Line::from(Span::styled(
             "[p] Play Original",
@@ -182,31 +377,266 @@ fn render_preview(frame: &mut Frame, app: &App) {
             Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
         )),
         Line::from(Span::styled(
-            "[s] Save to <timestamp>.wav",
+            "[w] Play Waveform-reversed  [a] A/B Toggle  [m] Demo (orig → wave → phoneme)",
+            Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            format!(
+                "[c] Play Concatenative (per phoneme)  [ [ / ] ] Crossfade: {} ms",
+                app.crossfade.as_millis()
+            ),
+            Style::default().fg(Color::Magenta),
+        )),
+        Line::from(Span::styled(
+            format!(
+                "[s] Save {} to <timestamp>.wav  [S] Save Demo",
+                app.ab_mode.display_name()
+            ),
             Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
         )),
+        Line::from(Span::styled(
+            "[Space] Pause/Resume  [x] Stop/Cancel",
+            Style::default().fg(Color::Gray),
+        )),
         Line::from(""),
         Line::from(Span::styled(
             format!("Status: {:?}", app.playback_status),
-            Style::default().fg(Color::Gray),
+            Style::default().fg(status_color(&app.playback_status)),
         )),
-    ];
+    ]);
+    // 音素ごとに合成した音声の再生中は、再生位置の音素を強調する
+    if !app.playing_segments().is_empty() {
+        let current = app.playing_segment();
+        let mut spans = vec![Span::raw("Playing:   ")];
+        for (i, segment) in app.playing_segments().iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw(" "));
+            }
+            let style = if current == Some(i) {
+                Style::default().fg(Color::Black).bg(Color::Green).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            spans.push(Span::styled(format!("[{}]", segment.phoneme), style));
+        }
+        content_text.push(Line::from(spans));
+    }
+    if let Some(stats) = app.cache_stats() {
+        content_text.push(Line::from(Span::styled(
+            format!(
+                "Cache: {} hits ({} from disk), {} misses, {} entries",
+                stats.hits + stats.disk_hits,
+                stats.disk_hits,
+                stats.misses,
+                stats.entries
+            ),
+            Style::default().fg(Color::Gray),
+        )));
+    }
 
     let content = Paragraph::new(content_text)
         .block(Block::default().borders(Borders::ALL))
         .alignment(Alignment::Left);
     frame.render_widget(content, chunks[1]);
 
+    // 再生プログレスバー
+    frame.render_widget(playback_gauge(app), chunks[2]);
+
     // ステータスバー
     let language = app.current_language();
-    let status_text = format!("Language: {}  |  [Shift+L] Switch  |  [Esc] Back  |  [q] Quit", language.display_name());
+    let status_text = with_message(
+        app,
+        format!(
+            "Language: {}  |  Synth: {}  |  Sink: {}  |  [Shift+L] Switch  |  [Esc] Back  |  [q] Quit",
+            language.display_name(),
+            app.synthesizer_name(),
+            app.audio_sink_name()
+        ),
+    );
     let status = Paragraph::new(status_text)
         .style(Style::default().fg(Color::White))
         .alignment(Alignment::Left)
         .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(status, chunks[3]);
+}
+
+fn render_settings(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // タイトル
+            Constraint::Min(0),    // 設定一覧
+            Constraint::Length(3), // 再生プログレスバー
+            Constraint::Length(3), // ステータスバー
+        ])
+        .split(frame.area());
+
+    let title = Paragraph::new("Synthesis Settings")
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(title, chunks[0]);
+
+    // 選択中の項目を反転表示し、値の範囲を併記する
+    let config = app.synth_config();
+    let mut lines: Vec<Line> = SynthParam::ALL
+        .iter()
+        .enumerate()
+        .map(|(index, param)| {
+            let range = match param {
+                SynthParam::Variant => format!("default, {}", VOICE_VARIANTS.join(", ")),
+                _ => format!("{}-{}", param.range().start(), param.range().end()),
+            };
+            let selected = index == app.settings_cursor;
+            let style = if selected {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{} {:<18} {:>8}",
+                        if selected { "▸" } else { " " },
+                        param.display_name(),
+                        config.value_text(*param)
+                    ),
+                    style,
+                ),
+                Span::styled(format!("  ({})", range), Style::default().fg(Color::Gray)),
+            ])
+        })
+        .collect();
+    lines.extend([
+        Line::from(""),
+        Line::from(Span::styled(
+            "[↑↓] Select  [←→] Adjust  [Shift+←→] ×10  [p/r] Play Original/Reversed",
+            Style::default().fg(Color::Gray),
+        )),
+    ]);
+
+    let settings = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Voice: {}", config.voice_name())),
+    );
+    frame.render_widget(settings, chunks[1]);
+
+    frame.render_widget(playback_gauge(app), chunks[2]);
+
+    let status = Paragraph::new(with_message(
+        app,
+        format!(
+            "Synth: {}  |  [F3] Device  |  [Esc] Back  |  [q] Quit",
+            app.synthesizer_name()
+        ),
+    ))
+    .style(Style::default().fg(Color::White))
+    .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(status, chunks[3]);
+}
+
+fn render_devices(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // タイトル
+            Constraint::Min(0),    // デバイス一覧
+            Constraint::Length(3), // ステータスバー
+        ])
+        .split(frame.area());
+
+    let title = Paragraph::new("Output Device")
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(title, chunks[0]);
+
+    // 先頭はシステムの既定。使用中のデバイスに ● を付け、選択中の行は対応形式も表示する
+    let rows = std::iter::once((None, "System default".to_string(), false)).chain(
+        app.devices
+            .iter()
+            .map(|info| (Some(&info.device), info.device.to_string(), info.is_default)),
+    );
+    let mut lines = Vec::new();
+    for (index, (device, label, is_default)) in rows.enumerate() {
+        let selected = index == app.device_cursor;
+        let style = if selected {
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let in_use = device == app.output_device();
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "{} {} {}",
+                    if selected { "▸" } else { " " },
+                    if in_use { "●" } else { " " },
+                    label
+                ),
+                style,
+            ),
+            Span::styled(
+                if is_default { "  [default]" } else { "" },
+                Style::default().fg(Color::Gray),
+            ),
+        ]));
+
+        let formats = index
+            .checked_sub(1)
+            .filter(|_| selected)
+            .map_or(&[][..], |i| app.devices[i].formats.as_slice());
+        for format in formats {
+            lines.push(Line::from(Span::styled(
+                format!("      {}", format),
+                Style::default().fg(Color::Gray),
+            )));
+        }
+    }
+    if app.devices.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No output devices found",
+            Style::default().fg(Color::Red),
+        )));
+    }
+    lines.extend([
+        Line::from(""),
+        Line::from(Span::styled(
+            "[↑↓] Select  [Enter] Use and save",
+            Style::default().fg(Color::Gray),
+        )),
+    ]);
+
+    let devices = Paragraph::new(lines).block(Block::default().borders(Borders::ALL));
+    frame.render_widget(devices, chunks[1]);
+
+    // cpal 以外のシンクではデバイスの選択は保存のみで再生には使われない
+    let status = Paragraph::new(with_message(
+        app,
+        format!("Sink: {}  |  [Esc] Back  |  [q] Quit", app.audio_sink_name()),
+    ))
+    .style(Style::default().fg(Color::White))
+    .block(Block::default().borders(Borders::ALL));
     frame.render_widget(status, chunks[2]);
 }
 
+fn playback_gauge(app: &App) -> Gauge<'static> {
+    let (ratio, label) = match app.playback_progress() {
+        Some((position, duration)) if !duration.is_zero() => (
+            (position.as_secs_f64() / duration.as_secs_f64()).clamp(0.0, 1.0),
+            format!("{:.1}s / {:.1}s", position.as_secs_f64(), duration.as_secs_f64()),
+        ),
+        _ => (0.0, String::from("--")),
+    };
+
+    Gauge::default()
+        .block(Block::default().borders(Borders::ALL).title("Playback"))
+        .gauge_style(Style::default().fg(Color::Green))
+        .ratio(ratio)
+        .label(label)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
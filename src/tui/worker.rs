@@ -0,0 +1,415 @@
+use crate::audio::write_wav;
+use crate::converter::PhonemeConverter;
+use crate::error::{PhonemeReverserError, Result};
+use crate::reversal::ReversalMode;
+use crate::segment::SegmentedAudio;
+use crate::synth::{CacheStats, SynthConfig, Synthesizer};
+use crate::tui::phoneme_db::Phoneme;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+pub type JobId = u64;
+
+/// What to do with the rendered audio.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobKind {
+    /// Hand the audio back to the event loop for playback.
+    Play,
+    /// Write the audio to a WAV file on the worker thread.
+    Save(PathBuf),
+}
+
+/// What to render, as IPA sequences.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Render {
+    Original(Vec<String>),
+    Reversed {
+        mode: ReversalMode,
+        original: Vec<String>,
+        reversed: Vec<String>,
+        /// Overlap between segments of [`ReversalMode::Concatenative`].
+        crossfade: Duration,
+    },
+}
+
+impl Render {
+    pub fn original(phonemes: &[Phoneme]) -> Self {
+        Render::Original(ipa(phonemes))
+    }
+
+    pub fn reversed(
+        mode: ReversalMode,
+        original: &[Phoneme],
+        reversed: &[Phoneme],
+        crossfade: Duration,
+    ) -> Self {
+        Render::Reversed {
+            mode,
+            original: ipa(original),
+            reversed: ipa(reversed),
+            crossfade,
+        }
+    }
+}
+
+/// Result of a finished job.
+#[derive(Debug)]
+pub enum JobOutput {
+    /// Audio to play, with phoneme boundaries when the mode knows them.
+    Audio(SegmentedAudio),
+    Saved(PathBuf),
+}
+
+/// Progress reported back to the event loop.
+#[derive(Debug)]
+pub enum WorkerEvent {
+    Started(JobId),
+    Finished {
+        id: JobId,
+        outcome: Result<JobOutput>,
+        /// Cache counters after the job, if the backend keeps any.
+        cache_stats: Option<CacheStats>,
+    },
+    /// The job was cancelled before or while it ran; its result is dropped.
+    Cancelled(JobId),
+}
+
+struct Job {
+    id: JobId,
+    render: Render,
+    config: SynthConfig,
+    kind: JobKind,
+    cancelled: Arc<AtomicBool>,
+}
+
+/// Handle of a submitted job, used to cancel it.
+#[derive(Debug, Clone)]
+pub struct JobHandle {
+    pub id: JobId,
+    pub kind: JobKind,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl JobHandle {
+    /// Skip the job if it has not started yet, and drop its result otherwise.
+    /// A synthesis that is already running is not interrupted.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Runs conversion and synthesis on a background thread.
+///
+/// Jobs are processed one at a time in submission order; progress arrives as
+/// [`WorkerEvent`]s that the event loop drains with [`try_recv`](Self::try_recv).
+pub struct SynthWorker {
+    jobs: Option<Sender<Job>>,
+    events: Receiver<WorkerEvent>,
+    next_id: JobId,
+    synthesizer_name: &'static str,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SynthWorker {
+    /// Move the converter and synthesizer to a new worker thread.
+    pub fn spawn(converter: PhonemeConverter, synthesizer: Box<dyn Synthesizer>) -> Self {
+        let (job_tx, job_rx) = mpsc::channel::<Job>();
+        let (event_tx, event_rx) = mpsc::channel();
+        let synthesizer_name = synthesizer.name();
+
+        let thread = std::thread::spawn(move || {
+            // 送信側（SynthWorker）が破棄されると終了する
+            for job in job_rx {
+                let event = if job.cancelled.load(Ordering::SeqCst) {
+                    WorkerEvent::Cancelled(job.id)
+                } else {
+                    if event_tx.send(WorkerEvent::Started(job.id)).is_err() {
+                        break;
+                    }
+                    let outcome = run(&job, &converter, synthesizer.as_ref());
+                    if job.cancelled.load(Ordering::SeqCst) {
+                        WorkerEvent::Cancelled(job.id)
+                    } else {
+                        WorkerEvent::Finished {
+                            id: job.id,
+                            outcome,
+                            cache_stats: synthesizer.cache_stats(),
+                        }
+                    }
+                };
+                if event_tx.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            jobs: Some(job_tx),
+            events: event_rx,
+            next_id: 0,
+            synthesizer_name,
+            thread: Some(thread),
+        }
+    }
+
+    /// Queue a job rendering `render` with `config`.
+    pub fn submit(
+        &mut self,
+        render: Render,
+        config: &SynthConfig,
+        kind: JobKind,
+    ) -> Result<JobHandle> {
+        self.next_id += 1;
+        let handle = JobHandle {
+            id: self.next_id,
+            kind: kind.clone(),
+            cancelled: Arc::new(AtomicBool::new(false)),
+        };
+        let job = Job {
+            id: handle.id,
+            render,
+            config: config.clone(),
+            kind,
+            cancelled: handle.cancelled.clone(),
+        };
+
+        self.jobs
+            .as_ref()
+            .and_then(|jobs| jobs.send(job).ok())
+            .ok_or_else(|| {
+                PhonemeReverserError::Synthesis("Synthesis worker has stopped".to_string())
+            })?;
+        Ok(handle)
+    }
+
+    /// Next pending event, without blocking.
+    pub fn try_recv(&self) -> Option<WorkerEvent> {
+        self.events.try_recv().ok()
+    }
+
+    pub fn synthesizer_name(&self) -> &'static str {
+        self.synthesizer_name
+    }
+}
+
+impl Drop for SynthWorker {
+    fn drop(&mut self) {
+        // キューを閉じて、実行中のジョブが終わるのを待つ
+        self.jobs = None;
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// 音素列を IPA → eSpeak 変換して音声合成する（CLIと共通の経路）
+fn run(
+    job: &Job,
+    converter: &PhonemeConverter,
+    synthesizer: &dyn Synthesizer,
+) -> Result<JobOutput> {
+    let synthesize = |ipa: &[String]| {
+        let espeak_phonemes = converter.convert_ipa_to_espeak(ipa)?;
+        synthesizer.synthesize(&espeak_phonemes, &job.config)
+    };
+    let segmented = match &job.render {
+        Render::Original(ipa) => SegmentedAudio::from(synthesize(ipa)?),
+        Render::Reversed {
+            mode,
+            original,
+            reversed,
+            crossfade,
+        } => mode.render(original, reversed, *crossfade, synthesize)?,
+    };
+    match &job.kind {
+        JobKind::Play => Ok(JobOutput::Audio(segmented)),
+        JobKind::Save(path) => {
+            write_wav(path, &segmented.audio)?;
+            Ok(JobOutput::Saved(path.clone()))
+        }
+    }
+}
+
+fn ipa(phonemes: &[Phoneme]) -> Vec<String> {
+    phonemes.iter().map(|p| p.ipa.clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::AudioData;
+    use crate::synth::TestSynthesizer;
+    use crate::tui::phoneme_db::PhonemeDatabase;
+    use std::time::Instant;
+
+    fn worker() -> SynthWorker {
+        SynthWorker::spawn(PhonemeConverter::new().unwrap(), Box::new(TestSynthesizer))
+    }
+
+    fn phonemes() -> Vec<Phoneme> {
+        let db = PhonemeDatabase::new();
+        ["k", "a"]
+            .iter()
+            .map(|ipa| db.find_by_ipa(ipa).unwrap().clone())
+            .collect()
+    }
+
+    fn next_event(worker: &SynthWorker) -> WorkerEvent {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            if let Some(event) = worker.try_recv() {
+                return event;
+            }
+            assert!(Instant::now() < deadline, "worker did not respond");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_play_job_reports_start_and_audio() {
+        let mut worker = worker();
+        let handle = worker
+            .submit(
+                Render::original(&phonemes()),
+                &SynthConfig::default(),
+                JobKind::Play,
+            )
+            .unwrap();
+
+        assert!(matches!(next_event(&worker), WorkerEvent::Started(id) if id == handle.id));
+        match next_event(&worker) {
+            WorkerEvent::Finished { id, outcome, .. } => {
+                assert_eq!(id, handle.id);
+                let Ok(JobOutput::Audio(output)) = outcome else {
+                    panic!("unexpected outcome: {:?}", outcome);
+                };
+                assert!(!output.audio.samples.is_empty());
+            }
+            event => panic!("unexpected event: {:?}", event),
+        }
+        assert_eq!(worker.synthesizer_name(), "test");
+    }
+
+    #[test]
+    fn test_concatenative_job_returns_segments() {
+        let mut worker = worker();
+        let reversed: Vec<Phoneme> = phonemes().into_iter().rev().collect();
+        let render = Render::reversed(
+            ReversalMode::Concatenative,
+            &phonemes(),
+            &reversed,
+            Duration::from_millis(5),
+        );
+        worker
+            .submit(render, &SynthConfig::default(), JobKind::Play)
+            .unwrap();
+
+        next_event(&worker);
+        match next_event(&worker) {
+            WorkerEvent::Finished {
+                outcome: Ok(JobOutput::Audio(output)),
+                ..
+            } => {
+                let phonemes: Vec<&str> =
+                    output.segments.iter().map(|s| s.phoneme.as_str()).collect();
+                assert_eq!(phonemes, vec!["a", "k"]);
+            }
+            event => panic!("unexpected event: {:?}", event),
+        }
+    }
+
+    #[test]
+    fn test_save_job_writes_wav() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.wav");
+        let mut worker = worker();
+        worker
+            .submit(
+                Render::reversed(ReversalMode::Demo, &phonemes(), &phonemes(), Duration::ZERO),
+                &SynthConfig::default(),
+                JobKind::Save(path.clone()),
+            )
+            .unwrap();
+
+        next_event(&worker);
+        match next_event(&worker) {
+            WorkerEvent::Finished { outcome, .. } => {
+                assert!(matches!(outcome, Ok(JobOutput::Saved(saved)) if saved == path));
+            }
+            event => panic!("unexpected event: {:?}", event),
+        }
+        assert!(path.exists());
+    }
+
+    /// Blocks each rendering until the test releases it.
+    struct Gated(std::sync::Mutex<Receiver<()>>);
+
+    impl Synthesizer for Gated {
+        fn name(&self) -> &'static str {
+            "gated"
+        }
+
+        fn synthesize(&self, espeak_phonemes: &str, config: &SynthConfig) -> Result<AudioData> {
+            let _ = self.0.lock().unwrap().recv();
+            TestSynthesizer.synthesize(espeak_phonemes, config)
+        }
+    }
+
+    #[test]
+    fn test_cancelled_jobs_are_dropped() {
+        let (release, gate) = mpsc::channel();
+        let mut worker = SynthWorker::spawn(
+            PhonemeConverter::new().unwrap(),
+            Box::new(Gated(std::sync::Mutex::new(gate))),
+        );
+        let config = SynthConfig::default();
+        let running = worker
+            .submit(Render::original(&phonemes()), &config, JobKind::Play)
+            .unwrap();
+        let queued = worker
+            .submit(Render::original(&phonemes()), &config, JobKind::Play)
+            .unwrap();
+        assert!(matches!(next_event(&worker), WorkerEvent::Started(id) if id == running.id));
+
+        // 合成中のジョブは結果を捨て、待機中のジョブは合成しない
+        running.cancel();
+        queued.cancel();
+        release.send(()).unwrap();
+        assert!(matches!(next_event(&worker), WorkerEvent::Cancelled(id) if id == running.id));
+        assert!(matches!(next_event(&worker), WorkerEvent::Cancelled(id) if id == queued.id));
+        assert!(worker.try_recv().is_none());
+    }
+
+    #[test]
+    fn test_errors_are_reported() {
+        let mut worker = worker();
+        let db = PhonemeDatabase::new();
+        // 変換テーブルにない記号
+        let mut unknown = db.find_by_ipa("a").unwrap().clone();
+        unknown.ipa = "☃".to_string();
+        worker
+            .submit(
+                Render::original(&[unknown]),
+                &SynthConfig::default(),
+                JobKind::Play,
+            )
+            .unwrap();
+
+        next_event(&worker);
+        assert!(matches!(
+            next_event(&worker),
+            WorkerEvent::Finished {
+                outcome: Err(_),
+                ..
+            }
+        ));
+    }
+}